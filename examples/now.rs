@@ -1,18 +1,18 @@
 extern crate kuzzle_sdk;
 use kuzzle_sdk::kuzzle::Kuzzle;
 use kuzzle_sdk::protocols::Http;
-use kuzzle_sdk::types::KuzzleOptions;
+use kuzzle_sdk::types::{KuzzleOptions, QueryOptions};
 
 fn main() {
     // Instanciate your Kuzzle client
-    let mut kuzzle = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+    let kuzzle = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
 
     kuzzle
         .connect()
         .expect("Unable to connect to Kuzzle server.");
 
     // Access Kuzzle's features via its controllers
-    match kuzzle.server().now() {
+    match kuzzle.server().now(QueryOptions::new()) {
         // This will display the current timestamp in Epoch millisecond format
         Ok(timestamp) => println!("{}", timestamp),
         Err(error) => eprintln!("{}", error),
@@ -1,206 +1,430 @@
 use crate::kuzzle::Kuzzle;
-use crate::types::{KuzzleRequest, QueryOptions, SdkError, TokenValidity, User, UserRight};
+use crate::types::{
+    ApiKey, ApiKeySearchResult, Error, KuzzleRequest, QueryOptions, SdkError, Session,
+    TokenValidity, User, UserRight,
+};
+use serde::Serialize;
 use serde_json::{Map, Value};
 use std::collections::HashMap;
-use std::error::Error;
+use std::time::Duration;
 
-pub struct AuthController<'a>(pub &'a mut Kuzzle);
+pub struct AuthController<'a>(pub &'a Kuzzle);
 
 impl<'a> AuthController<'a> {
-    fn kuzzle(&'a mut self) -> &'a mut Kuzzle {
+    fn kuzzle(&self) -> &Kuzzle {
         self.0
     }
 
-    pub fn check_token(&'a mut self, token: &str) -> Result<TokenValidity, Box<Error>> {
+    pub fn check_token(&self, token: &str, options: QueryOptions) -> Result<TokenValidity, Error> {
+        self.kuzzle().call(actions::check_token(token)?, options)
+    }
+
+    /// Creates an API key for the current user.
+    /// `expires_in` sets the lifespan of the key, which never expires if `None`.
+    /// The key token is only available from the returned `ApiKey`:
+    /// it can't be retrieved afterwards.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::{KuzzleOptions, QueryOptions};
+    ///
+    /// let kuzzle = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+    /// kuzzle.connect().unwrap();
+    /// kuzzle
+    ///     .auth()
+    ///     .login("local", "admin", "password", QueryOptions::new())
+    ///     .unwrap();
+    ///
+    /// let api_key = kuzzle
+    ///     .auth()
+    ///     .create_api_key("Nightly backups", None, QueryOptions::new())
+    ///     .unwrap();
+    /// println!("{}", api_key.token());
+    /// ```
+    pub fn create_api_key(
+        &self,
+        description: &str,
+        expires_in: Option<Duration>,
+        options: QueryOptions,
+    ) -> Result<ApiKey, Error> {
+        self.kuzzle()
+            .call(actions::create_api_key(description, expires_in)?, options)
+    }
+
+    pub fn create_my_credentials(
+        &self,
+        strategy: &str,
+        username: &str,
+        password: &str,
+        options: QueryOptions,
+    ) -> Result<Map<String, Value>, Error> {
+        self.kuzzle().call(
+            actions::create_my_credentials(strategy, username, password)?,
+            options,
+        )
+    }
+
+    /// Creates credentials for the current user, with any payload
+    /// accepted by the given authentication strategy.
+    pub fn create_my_credentials_with<C: Serialize>(
+        &self,
+        strategy: &str,
+        credentials: C,
+        options: QueryOptions,
+    ) -> Result<Map<String, Value>, Error> {
+        self.kuzzle().call(
+            actions::create_my_credentials_with(strategy, credentials)?,
+            options,
+        )
+    }
+
+    pub fn credentials_exist(&self, strategy: &str, options: QueryOptions) -> Result<bool, Error> {
+        self.kuzzle()
+            .call(actions::credentials_exist(strategy)?, options)
+    }
+
+    /// Deletes an API key of the current user, revoking its token.
+    pub fn delete_api_key(&self, id: &str, options: QueryOptions) -> Result<(), Error> {
+        self.kuzzle().call(actions::delete_api_key(id)?, options)
+    }
+
+    pub fn delete_my_credentials(
+        &self,
+        strategy: &str,
+        options: QueryOptions,
+    ) -> Result<bool, Error> {
+        self.kuzzle()
+            .call(actions::delete_my_credentials(strategy)?, options)
+    }
+
+    pub fn get_current_user(&self, strategy: &str, options: QueryOptions) -> Result<User, Error> {
+        self.kuzzle()
+            .call(actions::get_current_user(strategy)?, options)
+    }
+
+    pub fn get_my_credentials(
+        &self,
+        strategy: &str,
+        options: QueryOptions,
+    ) -> Result<Map<String, Value>, Error> {
+        self.kuzzle()
+            .call(actions::get_my_credentials(strategy)?, options)
+    }
+
+    pub fn get_my_rights(
+        &self,
+        strategy: &str,
+        options: QueryOptions,
+    ) -> Result<Vec<UserRight>, Error> {
+        self.kuzzle()
+            .call(actions::get_my_rights(strategy)?, options)
+    }
+
+    pub fn get_strategies(&self, options: QueryOptions) -> Result<Vec<String>, Error> {
+        self.kuzzle().call(actions::get_strategies()?, options)
+    }
+
+    pub fn login(
+        &self,
+        strategy: &str,
+        username: &str,
+        password: &str,
+        options: QueryOptions,
+    ) -> Result<Session, Error> {
+        let overrides_jwt = options.overrides_jwt();
+        let kuzzle = self.kuzzle();
+        let session = kuzzle.call(actions::login(strategy, username, password)?, options)?;
+
+        if !overrides_jwt {
+            kuzzle.set_session(Some(session.clone()));
+        }
+        Ok(session)
+    }
+
+    /// Logs in with any credentials payload accepted by the given authentication
+    /// strategy, such as a one-time code for an SSO plugin.
+    /// `expires_in` sets the lifespan of the token, the server default is used if `None`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::{KuzzleOptions, QueryOptions};
+    /// use serde_json::json;
+    /// use std::time::Duration;
+    ///
+    /// let kuzzle = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+    /// kuzzle.connect().unwrap();
+    ///
+    /// let session = kuzzle.auth().login_with(
+    ///     "sso",
+    ///     json!({ "code": "0f2f6c3e" }),
+    ///     Some(Duration::from_secs(3600)),
+    ///     QueryOptions::new(),
+    /// );
+    /// ```
+    pub fn login_with<C: Serialize>(
+        &self,
+        strategy: &str,
+        credentials: C,
+        expires_in: Option<Duration>,
+        options: QueryOptions,
+    ) -> Result<Session, Error> {
+        let overrides_jwt = options.overrides_jwt();
+        let kuzzle = self.kuzzle();
+        let session = kuzzle.call(
+            actions::login_with(strategy, credentials, expires_in)?,
+            options,
+        )?;
+
+        if !overrides_jwt {
+            kuzzle.set_session(Some(session.clone()));
+        }
+        Ok(session)
+    }
+
+    pub fn logout(&self, options: QueryOptions) -> Result<(), Error> {
+        let overrides_jwt = options.overrides_jwt();
+        let kuzzle = self.kuzzle();
+        kuzzle.call(actions::logout()?, options)?;
+
+        // TODO: Unsubscribe all when websocket will be implemented
+        if !overrides_jwt {
+            kuzzle.set_session(None);
+        }
+        Ok(())
+    }
+
+    /// Renews the current authentication token, which is replaced by the new one.
+    /// `expires_in` sets the lifespan of the new token, the server default is used if `None`.
+    pub fn refresh_token(
+        &self,
+        expires_in: Option<Duration>,
+        options: QueryOptions,
+    ) -> Result<Session, Error> {
+        let overrides_jwt = options.overrides_jwt();
+        let kuzzle = self.kuzzle();
+        let session = kuzzle.call(actions::refresh_token(expires_in)?, options)?;
+
+        if !overrides_jwt {
+            kuzzle.set_session(Some(session.clone()));
+        }
+        Ok(session)
+    }
+
+    /// Searches the API keys of the current user.
+    /// Results are paginated with `QueryOptions::set_from` and `QueryOptions::set_size`.
+    pub fn search_api_keys(
+        &self,
+        query: &HashMap<String, Value>,
+        options: QueryOptions,
+    ) -> Result<ApiKeySearchResult, Error> {
+        self.kuzzle()
+            .call(actions::search_api_keys(query)?, options)
+    }
+
+    /// Updates the credentials of the current user, with any payload
+    /// accepted by the given authentication strategy.
+    pub fn update_my_credentials<C: Serialize>(
+        &self,
+        strategy: &str,
+        content: C,
+        options: QueryOptions,
+    ) -> Result<Map<String, Value>, Error> {
+        self.kuzzle()
+            .call(actions::update_my_credentials(strategy, content)?, options)
+    }
+}
+
+/// The calls of the `auth` controller actions.
+pub(crate) mod actions {
+    use super::*;
+    use crate::controllers::{with_expires_in, Call};
+    use serde_json::json;
+
+    pub(crate) fn check_token(token: &str) -> Result<Call<TokenValidity>, Error> {
         if token.is_empty() {
-            return Err(Box::new(SdkError::new(
+            return Err(Error::InvalidArgument(SdkError::new(
                 "AuthController::check_token",
                 "token argument must not be empty.",
             )));
         }
         let req: KuzzleRequest = KuzzleRequest::new("auth", "checkToken")
             .add_to_body("token", serde_json::to_value(token)?);
-        let response = self.kuzzle().query(req, QueryOptions::new())?;
-        match &response.error() {
+        Ok(Call::new(req, |res| match &res.error() {
             None => {
-                let token_validity: TokenValidity =
-                    serde_json::from_value(response.result().clone())?;
+                let token_validity: TokenValidity = serde_json::from_value(res.result().clone())?;
                 Ok(token_validity)
             }
-            Some(k_err) => Err(Box::new(k_err.clone())),
+            Some(k_err) => Err(Error::from(k_err.clone())),
+        }))
+    }
+
+    pub(crate) fn create_api_key(
+        description: &str,
+        expires_in: Option<Duration>,
+    ) -> Result<Call<ApiKey>, Error> {
+        if description.is_empty() {
+            return Err(Error::InvalidArgument(SdkError::new(
+                "AuthController::create_api_key",
+                "description argument must not be empty.",
+            )));
         }
+
+        let req: KuzzleRequest = KuzzleRequest::new("auth", "createApiKey")
+            .add_to_body("description", Value::from(description));
+        Ok(
+            Call::new(with_expires_in(req, expires_in), |res| match &res.error() {
+                None => Ok(serde_json::from_value(res.result().clone())?),
+                Some(k_err) => Err(Error::from(k_err.clone())),
+            })
+            .require_login("AuthController::create_api_key"),
+        )
     }
 
-    pub fn create_my_credentials(
-        &'a mut self,
+    pub(crate) fn create_my_credentials(
         strategy: &str,
         username: &str,
         password: &str,
-    ) -> Result<Map<String, Value>, Box<Error>> {
+    ) -> Result<Call<Map<String, Value>>, Error> {
         if strategy.is_empty() || username.is_empty() || password.is_empty() {
-            return Err(Box::new(SdkError::new(
+            return Err(Error::InvalidArgument(SdkError::new(
                 "AuthController::create_my_credentials",
                 "strategy, username and password arguments must not be empty.",
             )));
         }
 
-        let kuzzle = self.kuzzle();
-        if kuzzle.jwt().clone().is_empty() {
-            return Err(Box::new(SdkError::new(
-                "AuthController::create_my_credentials",
-                "You need to be logged in to use this function.",
+        create_my_credentials_with(
+            strategy,
+            json!({ "username": username, "password": password }),
+        )
+    }
+
+    pub(crate) fn create_my_credentials_with<C: Serialize>(
+        strategy: &str,
+        credentials: C,
+    ) -> Result<Call<Map<String, Value>>, Error> {
+        let cause = "AuthController::create_my_credentials";
+        if strategy.is_empty() {
+            return Err(Error::InvalidArgument(SdkError::new(
+                cause,
+                "strategy argument must not be empty.",
             )));
         }
 
         let req: KuzzleRequest = KuzzleRequest::new("auth", "createMyCredentials")
             .set_strategy(strategy)
-            .add_to_body("username", serde_json::to_value(username)?)
-            .add_to_body("password", serde_json::to_value(password)?);
-
-        let response = kuzzle.query(req, QueryOptions::new())?;
-        match &response.error() {
-            None => Ok(response.result().as_object().unwrap().clone()),
-            Some(k_err) => Err(Box::new(k_err.clone())),
-        }
+            .set_body(credentials_body(cause, credentials)?);
+        Ok(Call::new(req, |res| match &res.error() {
+            None => Ok(res.result().as_object().unwrap().clone()),
+            Some(k_err) => Err(Error::from(k_err.clone())),
+        })
+        .require_login(cause))
     }
 
-    pub fn credentials_exist(&'a mut self, strategy: &str) -> Result<bool, Box<Error>> {
+    pub(crate) fn credentials_exist(strategy: &str) -> Result<Call<bool>, Error> {
         if strategy.is_empty() {
-            return Err(Box::new(SdkError::new(
+            return Err(Error::InvalidArgument(SdkError::new(
                 "AuthController::credentials_exist",
                 "strategy argument must not be empty.",
             )));
         }
 
-        let kuzzle = self.kuzzle();
-        if kuzzle.jwt().clone().is_empty() {
-            return Err(Box::new(SdkError::new(
-                "AuthController::credentials_exist",
-                "You need to be logged in to use this function.",
-            )));
-        }
-
         let req: KuzzleRequest =
             KuzzleRequest::new("auth", "credentialsExist").set_strategy(strategy);
+        Ok(Call::new(req, |res| match &res.error() {
+            None => Ok(res.result().as_bool().unwrap()),
+            Some(k_err) => Err(Error::from(k_err.clone())),
+        })
+        .require_login("AuthController::credentials_exist"))
+    }
 
-        let response = kuzzle.query(req, QueryOptions::new())?;
-        match &response.error() {
-            None => Ok(response.result().as_bool().unwrap()),
-            Some(k_err) => Err(Box::new(k_err.clone())),
+    pub(crate) fn delete_api_key(id: &str) -> Result<Call<()>, Error> {
+        if id.is_empty() {
+            return Err(Error::InvalidArgument(SdkError::new(
+                "AuthController::delete_api_key",
+                "id argument must not be empty.",
+            )));
         }
+
+        let req: KuzzleRequest = KuzzleRequest::new("auth", "deleteApiKey").set_id(id);
+        Ok(Call::new(req, |res| match &res.error() {
+            None => Ok(()),
+            Some(k_err) => Err(Error::from(k_err.clone())),
+        })
+        .require_login("AuthController::delete_api_key"))
     }
 
-    pub fn delete_my_credentials(&'a mut self, strategy: &str) -> Result<bool, Box<Error>> {
+    pub(crate) fn delete_my_credentials(strategy: &str) -> Result<Call<bool>, Error> {
         if strategy.is_empty() {
-            return Err(Box::new(SdkError::new(
+            return Err(Error::InvalidArgument(SdkError::new(
                 "AuthController::delete_my_credentials",
                 "strategy argument must not be empty.",
             )));
         }
 
-        let kuzzle = self.kuzzle();
-        if kuzzle.jwt().clone().is_empty() {
-            return Err(Box::new(SdkError::new(
-                "AuthController::delete_my_credentials",
-                "You need to be logged in to use this function.",
-            )));
-        }
-
         let req: KuzzleRequest =
             KuzzleRequest::new("auth", "deleteMyCredentials").set_strategy(strategy);
-
-        let response = kuzzle.query(req, QueryOptions::new())?;
-        match &response.error() {
-            None => Ok(response
-                .result()
-                .get("acknowledged")
-                .unwrap()
-                .as_bool()
-                .unwrap()),
-            Some(k_err) => Err(Box::new(k_err.clone())),
-        }
+        Ok(Call::new(req, |res| match &res.error() {
+            None => Ok(res.result().get("acknowledged").unwrap().as_bool().unwrap()),
+            Some(k_err) => Err(Error::from(k_err.clone())),
+        })
+        .require_login("AuthController::delete_my_credentials"))
     }
 
-    pub fn get_current_user(&'a mut self, strategy: &str) -> Result<User, Box<Error>> {
+    pub(crate) fn get_current_user(strategy: &str) -> Result<Call<User>, Error> {
         if strategy.is_empty() {
-            return Err(Box::new(SdkError::new(
+            return Err(Error::InvalidArgument(SdkError::new(
                 "AuthController::get_current_user",
                 "strategy argument must not be empty.",
             )));
         }
 
-        let kuzzle = self.kuzzle();
-        if kuzzle.jwt().clone().is_empty() {
-            return Err(Box::new(SdkError::new(
-                "AuthController::get_current_user",
-                "You need to be logged in to use this function.",
-            )));
-        }
-
         let req: KuzzleRequest =
             KuzzleRequest::new("auth", "getCurrentUser").set_strategy(strategy);
-
-        let response = kuzzle.query(req, QueryOptions::new())?;
-        match &response.error() {
+        Ok(Call::new(req, |res| match &res.error() {
             None => {
-                let user: User = serde_json::from_value(response.result().clone())?;
+                let user: User = serde_json::from_value(res.result().clone())?;
                 Ok(user)
             }
-            Some(k_err) => Err(Box::new(k_err.clone())),
-        }
+            Some(k_err) => Err(Error::from(k_err.clone())),
+        })
+        .require_login("AuthController::get_current_user"))
     }
 
-    pub fn get_my_credentials(
-        &'a mut self,
-        strategy: &str,
-    ) -> Result<Map<String, Value>, Box<Error>> {
+    pub(crate) fn get_my_credentials(strategy: &str) -> Result<Call<Map<String, Value>>, Error> {
         if strategy.is_empty() {
-            return Err(Box::new(SdkError::new(
+            return Err(Error::InvalidArgument(SdkError::new(
                 "AuthController::get_my_credentials",
                 "strategy argument must not be empty.",
             )));
         }
 
-        let kuzzle = self.kuzzle();
-        if kuzzle.jwt().clone().is_empty() {
-            return Err(Box::new(SdkError::new(
-                "AuthController::get_my_credentials",
-                "You need to be logged in to use this function.",
-            )));
-        }
-
         let req: KuzzleRequest =
             KuzzleRequest::new("auth", "getMyCredentials").set_strategy(strategy);
-
-        let response = kuzzle.query(req, QueryOptions::new())?;
-        match &response.error() {
-            None => Ok(response.result().as_object().unwrap().clone()),
-            Some(k_err) => Err(Box::new(k_err.clone())),
-        }
+        Ok(Call::new(req, |res| match &res.error() {
+            None => Ok(res.result().as_object().unwrap().clone()),
+            Some(k_err) => Err(Error::from(k_err.clone())),
+        })
+        .require_login("AuthController::get_my_credentials"))
     }
 
-    pub fn get_my_rights(&'a mut self, strategy: &str) -> Result<Vec<UserRight>, Box<Error>> {
+    pub(crate) fn get_my_rights(strategy: &str) -> Result<Call<Vec<UserRight>>, Error> {
         if strategy.is_empty() {
-            return Err(Box::new(SdkError::new(
+            return Err(Error::InvalidArgument(SdkError::new(
                 "AuthController::get_my_rights",
                 "strategy argument must not be empty.",
             )));
         }
 
-        let kuzzle = self.kuzzle();
-        if kuzzle.jwt().clone().is_empty() {
-            return Err(Box::new(SdkError::new(
-                "AuthController::get_my_rights",
-                "You need to be logged in to use this function.",
-            )));
-        }
-
         let req: KuzzleRequest = KuzzleRequest::new("auth", "getMyRights").set_strategy(strategy);
-
-        let response = kuzzle.query(req, QueryOptions::new())?;
-        match &response.error() {
+        Ok(Call::new(req, |res| match &res.error() {
             None => {
-                let user_rights: Vec<UserRight> = response
+                let user_rights: Vec<UserRight> = res
                     .result()
                     .as_object()
                     .unwrap()
@@ -214,125 +438,145 @@ impl<'a> AuthController<'a> {
 
                 Ok(user_rights)
             }
-            Some(k_err) => Err(Box::new(k_err.clone())),
-        }
+            Some(k_err) => Err(Error::from(k_err.clone())),
+        })
+        .require_login("AuthController::get_my_rights"))
     }
 
-    pub fn get_strategies(&'a mut self) -> Result<Vec<String>, Box<Error>> {
+    pub(crate) fn get_strategies() -> Result<Call<Vec<String>>, Error> {
         let req: KuzzleRequest = KuzzleRequest::new("auth", "getStrategies");
-
-        let response = self.kuzzle().query(req, QueryOptions::new())?;
-        match &response.error() {
+        Ok(Call::new(req, |res| match &res.error() {
             None => {
-                let raw_strats = response.result().as_array().unwrap().clone();
+                let raw_strats = res.result().as_array().unwrap().clone();
 
                 let strategies: Vec<String> = raw_strats
                     .iter()
                     .map(|x| x.as_str().unwrap().to_string())
-                    .collect::<Vec<String>>();;
+                    .collect::<Vec<String>>();
 
                 Ok(strategies)
             }
-            Some(k_err) => Err(Box::new(k_err.clone())),
-        }
+            Some(k_err) => Err(Error::from(k_err.clone())),
+        }))
     }
 
-    pub fn login(
-        &'a mut self,
+    pub(crate) fn login(
         strategy: &str,
         username: &str,
         password: &str,
-    ) -> Result<String, Box<Error>> {
+    ) -> Result<Call<Session>, Error> {
         if strategy.is_empty() || username.is_empty() || password.is_empty() {
-            return Err(Box::new(SdkError::new(
+            return Err(Error::InvalidArgument(SdkError::new(
                 "AuthController::login",
                 "strategy, username and password arguments must not be empty.",
             )));
         }
 
-        let req: KuzzleRequest = KuzzleRequest::new("auth", "login")
-            .set_strategy(strategy)
-            .add_to_body("username", serde_json::to_value(username)?)
-            .add_to_body("password", serde_json::to_value(password)?);
-
-        let kuzzle = self.kuzzle();
-        let response = kuzzle.query(req, QueryOptions::new())?;
-        match &response.error() {
-            None => {
-                let jwt = response
-                    .result()
-                    .as_object()
-                    .unwrap()
-                    .get("jwt")
-                    .unwrap()
-                    .as_str()
-                    .unwrap()
-                    .to_string();
-
-                kuzzle.set_jwt(jwt);
-                Ok(kuzzle.jwt())
-            }
-            Some(k_err) => Err(Box::new(k_err.clone())),
-        }
+        login_with(
+            strategy,
+            json!({ "username": username, "password": password }),
+            None,
+        )
     }
 
-    pub fn logout(&'a mut self) -> Result<(), Box<Error>> {
-        let kuzzle = self.kuzzle();
-        if kuzzle.jwt().clone().is_empty() {
-            return Err(Box::new(SdkError::new(
-                "AuthController::logout",
-                "You need to be logged in to use this function.",
+    pub(crate) fn login_with<C: Serialize>(
+        strategy: &str,
+        credentials: C,
+        expires_in: Option<Duration>,
+    ) -> Result<Call<Session>, Error> {
+        if strategy.is_empty() {
+            return Err(Error::InvalidArgument(SdkError::new(
+                "AuthController::login",
+                "strategy argument must not be empty.",
             )));
         }
 
+        let req: KuzzleRequest = KuzzleRequest::new("auth", "login")
+            .set_strategy(strategy)
+            .set_body(credentials_body("AuthController::login", credentials)?);
+
+        Ok(Call::new(
+            with_expires_in(req, expires_in),
+            |res| match &res.error() {
+                None => Ok(serde_json::from_value(res.result().clone())?),
+                Some(k_err) => Err(Error::from(k_err.clone())),
+            },
+        ))
+    }
+
+    pub(crate) fn logout() -> Result<Call<()>, Error> {
         let req: KuzzleRequest = KuzzleRequest::new("auth", "logout");
+        Ok(Call::new(req, |res| match &res.error() {
+            None => Ok(()),
+            Some(k_err) => Err(Error::from(k_err.clone())),
+        })
+        .require_login("AuthController::logout"))
+    }
 
-        let response = kuzzle.query(req, QueryOptions::new())?;
-        match &response.error() {
-            None => {
-                // TODO: Unsubscribe all when websocket will be implemented
-                kuzzle.set_jwt("".to_string());
-                Ok(())
-            }
-            Some(k_err) => Err(Box::new(k_err.clone())),
-        }
+    pub(crate) fn refresh_token(expires_in: Option<Duration>) -> Result<Call<Session>, Error> {
+        let req: KuzzleRequest = KuzzleRequest::new("auth", "refreshToken");
+
+        Ok(
+            Call::new(with_expires_in(req, expires_in), |res| match &res.error() {
+                None => Ok(serde_json::from_value(res.result().clone())?),
+                Some(k_err) => Err(Error::from(k_err.clone())),
+            })
+            .require_login("AuthController::refresh_token"),
+        )
     }
 
-    pub fn update_my_credentials(
-        &'a mut self,
+    pub(crate) fn search_api_keys(
+        query: &HashMap<String, Value>,
+    ) -> Result<Call<ApiKeySearchResult>, Error> {
+        let req: KuzzleRequest =
+            KuzzleRequest::new("auth", "searchApiKeys").set_body(query.clone());
+        Ok(Call::new(req, |res| match &res.error() {
+            None => Ok(serde_json::from_value(res.result().clone())?),
+            Some(k_err) => Err(Error::from(k_err.clone())),
+        })
+        .require_login("AuthController::search_api_keys"))
+    }
+
+    pub(crate) fn update_my_credentials<C: Serialize>(
         strategy: &str,
-        content: &'a HashMap<String, Value>,
-    ) -> Result<Map<String, Value>, Box<Error>> {
+        content: C,
+    ) -> Result<Call<Map<String, Value>>, Error> {
+        let content = credentials_body("AuthController::update_my_credentials", content)?;
         if content.is_empty() {
-            return Err(Box::new(SdkError::new(
+            return Err(Error::InvalidArgument(SdkError::new(
                 "AuthController::update_my_credentials",
                 "content argument must not be empty.",
             )));
         }
 
         if strategy.is_empty() {
-            return Err(Box::new(SdkError::new(
+            return Err(Error::InvalidArgument(SdkError::new(
                 "AuthController::update_my_credentials",
                 "strategy argument must not be empty.",
             )));
         }
 
-        let kuzzle = self.kuzzle();
-        if kuzzle.jwt().clone().is_empty() {
-            return Err(Box::new(SdkError::new(
-                "AuthController::update_my_credentials",
-                "You need to be logged in to use this function.",
-            )));
-        }
-
         let req: KuzzleRequest = KuzzleRequest::new("auth", "updateMyCredentials")
             .set_strategy(strategy)
-            .set_body(content.clone());
+            .set_body(content);
+        Ok(Call::new(req, |res| match &res.error() {
+            None => Ok(res.result().as_object().unwrap().clone()),
+            Some(k_err) => Err(Error::from(k_err.clone())),
+        })
+        .require_login("AuthController::update_my_credentials"))
+    }
 
-        let response = kuzzle.query(req, QueryOptions::new())?;
-        match &response.error() {
-            None => Ok(response.result().as_object().unwrap().clone()),
-            Some(k_err) => Err(Box::new(k_err.clone())),
+    /// Serializes credentials to the request body: they must be a JSON object.
+    fn credentials_body<C: Serialize>(
+        cause: &str,
+        credentials: C,
+    ) -> Result<HashMap<String, Value>, Error> {
+        match serde_json::to_value(credentials)? {
+            Value::Object(credentials) => Ok(credentials.into_iter().collect()),
+            _ => Err(Error::InvalidArgument(SdkError::new(
+                cause,
+                "credentials must be serialized to a JSON object.",
+            ))),
         }
     }
 }
@@ -343,6 +587,8 @@ mod tests {
     use crate::protocols::Http;
     use crate::types::KuzzleOptions;
     use mockito;
+    use mockito::Matcher;
+    use serde_json::json;
 
     #[test]
     fn check_token_ok() {
@@ -365,14 +611,19 @@ mod tests {
             )
             .create();
 
-        let mut k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
         k.connect().expect("Unable to connect to Kuzzle server");
-        let res = k.auth().check_token("veryCoolAndLongToken");
+        let res = k
+            .auth()
+            .check_token("veryCoolAndLongToken", QueryOptions::new());
 
         assert!(res.is_ok());
         let token_validity = res.unwrap();
         assert!(token_validity.valid());
-        assert_eq!(token_validity.expires_at(), 1538557452248);
+        assert_eq!(
+            token_validity.expires_at().unwrap().timestamp_millis(),
+            1538557452248
+        );
     }
 
     #[test]
@@ -396,9 +647,11 @@ mod tests {
             )
             .create();
 
-        let mut k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
         k.connect().expect("Unable to connect to Kuzzle server");
-        let res = k.auth().check_token("veryCoolAndLongToken");
+        let res = k
+            .auth()
+            .check_token("veryCoolAndLongToken", QueryOptions::new());
 
         assert!(res.is_ok());
         let token_validity = res.unwrap();
@@ -428,22 +681,90 @@ mod tests {
             )
             .create();
 
-        let mut k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
         k.connect().expect("Unable to connect to Kuzzle server");
-        let res = k.auth().check_token("veryCoolAndLongToken");
+        let res = k
+            .auth()
+            .check_token("veryCoolAndLongToken", QueryOptions::new());
 
         assert!(res.is_err());
     }
 
     #[test]
     fn check_token_fail_empty_arg() {
-        let mut k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
         k.connect().expect("Unable to connect to Kuzzle server");
-        let res = k.auth().check_token("");
+        let res = k.auth().check_token("", QueryOptions::new());
 
         assert!(res.is_err());
     }
 
+    #[test]
+    fn create_api_key_ok() {
+        let _m = mockito::mock("POST", "/api-keys/_create?expiresIn=86400000")
+            .match_header("authorization", "Bearer mySuperToken")
+            .match_body(Matcher::JsonString(
+                r#"{"description":"Nightly backups"}"#.to_string(),
+            ))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "auth",
+                    "action": "createApiKey",
+                    "volatile": {},
+                    "result": {
+                      "_id": "ferris-key",
+                      "_source": {
+                        "userId": "ferris",
+                        "description": "Nightly backups",
+                        "expiresAt": 1321085955000,
+                        "ttl": 86400000,
+                        "token": "aSuperApiKeyToken"
+                      }
+                    }
+                }"#,
+            )
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        k.connect().expect("Unable to connect to Kuzzle server");
+        k.set_jwt("mySuperToken".to_string());
+        let res = k.auth().create_api_key(
+            "Nightly backups",
+            Some(Duration::from_secs(86400)),
+            QueryOptions::new(),
+        );
+
+        let api_key = res.unwrap();
+        assert_eq!(api_key.id(), "ferris-key");
+        assert_eq!(api_key.token(), "aSuperApiKeyToken");
+    }
+
+    #[test]
+    fn create_api_key_fail_empty_description() {
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        k.connect().expect("Unable to connect to Kuzzle server");
+        k.set_jwt("mySuperToken".to_string());
+        let res = k.auth().create_api_key("", None, QueryOptions::new());
+
+        assert!(matches!(res, Err(Error::InvalidArgument(_))));
+    }
+
+    #[test]
+    fn create_api_key_fail_not_logged_in() {
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        k.connect().expect("Unable to connect to Kuzzle server");
+        let res = k
+            .auth()
+            .create_api_key("Nightly backups", None, QueryOptions::new());
+
+        assert!(matches!(res, Err(Error::InvalidArgument(_))));
+    }
+
     #[test]
     fn create_my_credentials_ok() {
         let _m = mockito::mock("POST", "/credentials/local/_me/_create")
@@ -466,10 +787,12 @@ mod tests {
             )
             .create();
 
-        let mut k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
         k.connect().expect("Unable to connect to Kuzzle server");
         k.set_jwt("mySuperToken".to_string());
-        let res = k.auth().create_my_credentials("local", "admin", "password");
+        let res = k
+            .auth()
+            .create_my_credentials("local", "admin", "password", QueryOptions::new());
 
         assert!(&res.is_ok());
         assert_eq!(
@@ -478,6 +801,40 @@ mod tests {
         );
     }
 
+    #[test]
+    fn create_my_credentials_with_ok() {
+        let _m = mockito::mock("POST", "/credentials/sso/_me/_create")
+            .match_header("authorization", "Bearer mySuperToken")
+            .match_body(Matcher::JsonString(r#"{"code":"0f2f6c3e"}"#.to_string()))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "auth",
+                    "action": "createMyCredentials",
+                    "volatile": {},
+                    "result": {
+                      "kuid": "myNewUserKUID"
+                    }
+                }"#,
+            )
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        k.connect().expect("Unable to connect to Kuzzle server");
+        k.set_jwt("mySuperToken".to_string());
+        let res = k.auth().create_my_credentials_with(
+            "sso",
+            json!({ "code": "0f2f6c3e" }),
+            QueryOptions::new(),
+        );
+
+        assert_eq!(res.unwrap()["kuid"], "myNewUserKUID");
+    }
+
     #[test]
     fn create_my_credentials_fail_error() {
         let _m = mockito::mock("POST", "/credentials/local/_me/_create")
@@ -501,31 +858,35 @@ mod tests {
             )
             .create();
 
-        let mut k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
         k.connect().expect("Unable to connect to Kuzzle server");
         k.set_jwt("mySuperToken".to_string());
-        let res = k.auth().create_my_credentials("local", "admin", "password");
+        let res = k
+            .auth()
+            .create_my_credentials("local", "admin", "password", QueryOptions::new());
 
         assert!(res.is_err());
     }
 
     #[test]
     fn create_my_credentials_fail_empty_args() {
-        let mut k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
         k.connect().expect("Unable to connect to Kuzzle server");
         k.set_jwt("mySuperToken".to_string());
-        let res = k.auth().create_my_credentials("local", "", "");
+        let res = k
+            .auth()
+            .create_my_credentials("local", "", "", QueryOptions::new());
 
         assert!(res.is_err());
     }
 
     #[test]
     fn create_my_credentials_fail_not_logged_in() {
-        let mut k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
         k.connect().expect("Unable to connect to Kuzzle server");
-        let res = k
-            .auth()
-            .create_my_credentials("local", "username", "password");
+        let res =
+            k.auth()
+                .create_my_credentials("local", "username", "password", QueryOptions::new());
 
         assert!(res.is_err());
     }
@@ -549,10 +910,10 @@ mod tests {
             )
             .create();
 
-        let mut k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
         k.connect().expect("Unable to connect to Kuzzle server");
         k.set_jwt("mySuperToken".to_string());
-        let res = k.auth().credentials_exist("local");
+        let res = k.auth().credentials_exist("local", QueryOptions::new());
 
         assert!(res.is_ok());
         assert!(res.unwrap());
@@ -581,29 +942,68 @@ mod tests {
             )
             .create();
 
-        let mut k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
         k.connect().expect("Unable to connect to Kuzzle server");
         k.set_jwt("mySuperBadToken".to_string());
-        let res = k.auth().credentials_exist("local");
+        let res = k.auth().credentials_exist("local", QueryOptions::new());
 
         assert!(res.is_err());
     }
 
     #[test]
     fn credentials_exist_fail_empty_strategy() {
-        let mut k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
         k.connect().expect("Unable to connect to Kuzzle server");
         k.set_jwt("mySuperToken".to_string());
-        let res = k.auth().credentials_exist("");
+        let res = k.auth().credentials_exist("", QueryOptions::new());
 
         assert!(res.is_err());
     }
 
     #[test]
     fn credentials_exist_fail_no_jwt() {
-        let mut k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
         k.connect().expect("Unable to connect to Kuzzle server");
-        let res = k.auth().credentials_exist("local");
+        let res = k.auth().credentials_exist("local", QueryOptions::new());
+
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn delete_api_key_ok() {
+        let _m = mockito::mock("DELETE", "/api-keys/ferris-key")
+            .match_header("authorization", "Bearer mySuperToken")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "auth",
+                    "action": "deleteApiKey",
+                    "volatile": {},
+                    "result": {
+                      "_id": "ferris-key"
+                    }
+                }"#,
+            )
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        k.connect().expect("Unable to connect to Kuzzle server");
+        k.set_jwt("mySuperToken".to_string());
+        let res = k.auth().delete_api_key("ferris-key", QueryOptions::new());
+
+        assert!(res.is_ok());
+    }
+
+    #[test]
+    fn delete_api_key_fail_empty_id() {
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        k.connect().expect("Unable to connect to Kuzzle server");
+        k.set_jwt("mySuperToken".to_string());
+        let res = k.auth().delete_api_key("", QueryOptions::new());
 
         assert!(res.is_err());
     }
@@ -629,10 +1029,10 @@ mod tests {
             )
             .create();
 
-        let mut k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
         k.connect().expect("Unable to connect to Kuzzle server");
         k.set_jwt("mySuperToken".to_string());
-        let res = k.auth().delete_my_credentials("local");
+        let res = k.auth().delete_my_credentials("local", QueryOptions::new());
 
         assert!(res.is_ok());
         assert!(res.unwrap());
@@ -661,29 +1061,29 @@ mod tests {
             )
             .create();
 
-        let mut k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
         k.connect().expect("Unable to connect to Kuzzle server");
         k.set_jwt("mySuperBadToken".to_string());
-        let res = k.auth().delete_my_credentials("local");
+        let res = k.auth().delete_my_credentials("local", QueryOptions::new());
 
         assert!(res.is_err());
     }
 
     #[test]
     fn delete_my_credentials_fail_empty_strategy() {
-        let mut k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
         k.connect().expect("Unable to connect to Kuzzle server");
         k.set_jwt("mySuperToken".to_string());
-        let res = k.auth().delete_my_credentials("");
+        let res = k.auth().delete_my_credentials("", QueryOptions::new());
 
         assert!(res.is_err());
     }
 
     #[test]
     fn delete_my_credentials_fail_no_jwt() {
-        let mut k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
         k.connect().expect("Unable to connect to Kuzzle server");
-        let res = k.auth().delete_my_credentials("local");
+        let res = k.auth().delete_my_credentials("local", QueryOptions::new());
 
         assert!(res.is_err());
     }
@@ -717,10 +1117,10 @@ mod tests {
             )
             .create();
 
-        let mut k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
         k.connect().expect("Unable to connect to Kuzzle server");
         k.set_jwt("mySuperToken".to_string());
-        let res = k.auth().get_current_user("local");
+        let res = k.auth().get_current_user("local", QueryOptions::new());
 
         assert!(res.is_ok());
         let user: User = res.unwrap();
@@ -751,29 +1151,29 @@ mod tests {
             )
             .create();
 
-        let mut k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
         k.connect().expect("Unable to connect to Kuzzle server");
         k.set_jwt("mySuperBadToken".to_string());
-        let res = k.auth().get_current_user("local");
+        let res = k.auth().get_current_user("local", QueryOptions::new());
 
         assert!(res.is_err());
     }
 
     #[test]
     fn get_current_user_fail_empty_strategy() {
-        let mut k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
         k.connect().expect("Unable to connect to Kuzzle server");
         k.set_jwt("mySuperToken".to_string());
-        let res = k.auth().get_current_user("");
+        let res = k.auth().get_current_user("", QueryOptions::new());
 
         assert!(res.is_err());
     }
 
     #[test]
     fn get_current_user_fail_no_jwt() {
-        let mut k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
         k.connect().expect("Unable to connect to Kuzzle server");
-        let res = k.auth().get_current_user("local");
+        let res = k.auth().get_current_user("local", QueryOptions::new());
 
         assert!(res.is_err());
     }
@@ -800,10 +1200,10 @@ mod tests {
             )
             .create();
 
-        let mut k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
         k.connect().expect("Unable to connect to Kuzzle server");
         k.set_jwt("mySuperToken".to_string());
-        let res = k.auth().get_my_credentials("local");
+        let res = k.auth().get_my_credentials("local", QueryOptions::new());
 
         assert!(res.is_ok());
         let response = res.unwrap();
@@ -837,29 +1237,29 @@ mod tests {
             )
             .create();
 
-        let mut k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
         k.connect().expect("Unable to connect to Kuzzle server");
         k.set_jwt("mySuperBadToken".to_string());
-        let res = k.auth().get_my_credentials("local");
+        let res = k.auth().get_my_credentials("local", QueryOptions::new());
 
         assert!(res.is_err());
     }
 
     #[test]
     fn get_my_credentials_fail_empty_strategy() {
-        let mut k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
         k.connect().expect("Unable to connect to Kuzzle server");
         k.set_jwt("mySuperToken".to_string());
-        let res = k.auth().get_my_credentials("");
+        let res = k.auth().get_my_credentials("", QueryOptions::new());
 
         assert!(res.is_err());
     }
 
     #[test]
     fn get_my_credentials_fail_no_jwt() {
-        let mut k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
         k.connect().expect("Unable to connect to Kuzzle server");
-        let res = k.auth().get_my_credentials("local");
+        let res = k.auth().get_my_credentials("local", QueryOptions::new());
 
         assert!(res.is_err());
     }
@@ -907,10 +1307,10 @@ mod tests {
             )
             .create();
 
-        let mut k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
         k.connect().expect("Unable to connect to Kuzzle server");
         k.set_jwt("mySuperToken".to_string());
-        let res = k.auth().get_my_rights("local");
+        let res = k.auth().get_my_rights("local", QueryOptions::new());
 
         assert!(res.is_ok());
         let my_rights = res.unwrap();
@@ -940,29 +1340,29 @@ mod tests {
             )
             .create();
 
-        let mut k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
         k.connect().expect("Unable to connect to Kuzzle server");
         k.set_jwt("mySuperBadToken".to_string());
-        let res = k.auth().get_my_rights("local");
+        let res = k.auth().get_my_rights("local", QueryOptions::new());
 
         assert!(res.is_err());
     }
 
     #[test]
     fn get_my_rights_fail_empty_strategy() {
-        let mut k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
         k.connect().expect("Unable to connect to Kuzzle server");
         k.set_jwt("mySuperToken".to_string());
-        let res = k.auth().get_my_rights("");
+        let res = k.auth().get_my_rights("", QueryOptions::new());
 
         assert!(res.is_err());
     }
 
     #[test]
     fn get_my_rights_fail_no_jwt() {
-        let mut k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
         k.connect().expect("Unable to connect to Kuzzle server");
-        let res = k.auth().get_my_rights("local");
+        let res = k.auth().get_my_rights("local", QueryOptions::new());
 
         assert!(res.is_err());
     }
@@ -988,15 +1388,44 @@ mod tests {
             )
             .create();
 
-        let mut k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
         k.connect().expect("Unable to connect to Kuzzle server");
-        let res = k.auth().get_strategies();
+        let res = k.auth().get_strategies(QueryOptions::new());
 
         assert!(res.is_ok());
         let strategies = res.unwrap();
         assert_eq!(strategies.len(), 2);
     }
 
+    #[test]
+    fn get_strategies_ok_anonymous() {
+        let _m = mockito::mock("GET", "/strategies")
+            .match_header("authorization", Matcher::Missing)
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "auth",
+                    "action": "getStrategies",
+                    "volatile": {},
+                    "result": ["local"]
+                }"#,
+            )
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        k.connect().expect("Unable to connect to Kuzzle server");
+        k.set_jwt("mySuperToken".to_string());
+        let res = k
+            .auth()
+            .get_strategies(QueryOptions::new().set_anonymous(true));
+
+        assert_eq!(res.unwrap(), vec!["local".to_string()]);
+    }
+
     #[test]
     fn get_strategies_fail_error() {
         let _m = mockito::mock("GET", "/strategies")
@@ -1018,9 +1447,9 @@ mod tests {
             )
             .create();
 
-        let mut k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
         k.connect().expect("Unable to connect to Kuzzle server");
-        let res = k.auth().get_strategies();
+        let res = k.auth().get_strategies(QueryOptions::new());
 
         assert!(res.is_err());
     }
@@ -1048,12 +1477,70 @@ mod tests {
             )
             .create();
 
-        let mut k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
         k.connect().expect("Unable to connect to Kuzzle server");
-        let res = k.auth().login("local", "admin", "password");
+        let res = k
+            .auth()
+            .login("local", "admin", "password", QueryOptions::new());
 
         assert!(res.is_ok());
-        assert_eq!(res.unwrap(), k.jwt());
+        let session = res.unwrap();
+        assert_eq!(session.jwt(), k.jwt());
+        assert_eq!(session.kuid(), "user-kuid");
+        assert_eq!(k.session(), Some(session));
+    }
+
+    #[test]
+    fn login_with_ok() {
+        let _m = mockito::mock("POST", "/_login/sso?expiresIn=3600000")
+            .match_body(Matcher::JsonString(r#"{"code":"0f2f6c3e"}"#.to_string()))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "auth",
+                    "action": "login",
+                    "volatile": {},
+                    "result": {
+                      "_id": "user-kuid",
+                      "jwt": "aSuperJwtToken",
+                      "expiresAt": 1321085955000,
+                      "ttl": 3600000
+                    }
+                }"#,
+            )
+            .create();
+
+        #[derive(Serialize)]
+        struct OneTimeCode {
+            code: &'static str,
+        }
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        k.connect().expect("Unable to connect to Kuzzle server");
+        let res = k.auth().login_with(
+            "sso",
+            OneTimeCode { code: "0f2f6c3e" },
+            Some(Duration::from_secs(3600)),
+            QueryOptions::new(),
+        );
+
+        assert_eq!(res.unwrap().jwt(), "aSuperJwtToken");
+        assert_eq!(k.jwt(), "aSuperJwtToken");
+    }
+
+    #[test]
+    fn login_with_fail_not_an_object() {
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        k.connect().expect("Unable to connect to Kuzzle server");
+        let res = k
+            .auth()
+            .login_with("sso", "0f2f6c3e", None, QueryOptions::new());
+
+        assert!(matches!(res, Err(Error::InvalidArgument(_))));
     }
 
     #[test]
@@ -1078,18 +1565,20 @@ mod tests {
             )
             .create();
 
-        let mut k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
         k.connect().expect("Unable to connect to Kuzzle server");
-        let res = k.auth().login("local", "admin", "badPassword");
+        let res = k
+            .auth()
+            .login("local", "admin", "badPassword", QueryOptions::new());
 
         assert!(res.is_err());
     }
 
     #[test]
     fn login_fail_empty_args() {
-        let mut k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
         k.connect().expect("Unable to connect to Kuzzle server");
-        let res = k.auth().login("local", "", "");
+        let res = k.auth().login("local", "", "", QueryOptions::new());
 
         assert!(res.is_err());
     }
@@ -1113,24 +1602,156 @@ mod tests {
             )
             .create();
 
-        let mut k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
         k.connect().expect("Unable to connect to Kuzzle server");
         k.set_jwt("mySuperToken".to_string());
-        let res = k.auth().logout();
+        let res = k.auth().logout(QueryOptions::new());
 
         assert!(res.is_ok());
         assert_eq!("", k.jwt());
     }
 
+    #[test]
+    fn logout_ok_jwt_override() {
+        let _m = mockito::mock("POST", "/_logout")
+            .match_header("authorization", "Bearer userToken")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "auth",
+                    "action": "logout",
+                    "volatile": {},
+                    "result": {}
+                }"#,
+            )
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        k.connect().expect("Unable to connect to Kuzzle server");
+        k.set_jwt("mySuperToken".to_string());
+        let res = k.auth().logout(QueryOptions::new().set_jwt("userToken"));
+
+        assert!(res.is_ok());
+        assert_eq!("mySuperToken", k.jwt());
+    }
+
+    #[test]
+    fn logout_fail_anonymous() {
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        k.connect().expect("Unable to connect to Kuzzle server");
+        k.set_jwt("mySuperToken".to_string());
+        let res = k.auth().logout(QueryOptions::new().set_anonymous(true));
+
+        assert!(matches!(res, Err(Error::InvalidArgument(_))));
+        assert_eq!("mySuperToken", k.jwt());
+    }
+
     #[test]
     fn logout_fail_no_jwt() {
-        let mut k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        k.connect().expect("Unable to connect to Kuzzle server");
+        let res = k.auth().logout(QueryOptions::new());
+
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn refresh_token_ok() {
+        let _m = mockito::mock("POST", "/_refreshToken?expiresIn=3600000")
+            .match_header("authorization", "Bearer mySuperToken")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "auth",
+                    "action": "refreshToken",
+                    "volatile": {},
+                    "result": {
+                      "_id": "user-kuid",
+                      "jwt": "aRenewedJwtToken",
+                      "expiresAt": 1321085955000,
+                      "ttl": 3600000
+                    }
+                }"#,
+            )
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        k.connect().expect("Unable to connect to Kuzzle server");
+        k.set_jwt("mySuperToken".to_string());
+        let res = k.auth()
+            .refresh_token(Some(Duration::from_secs(3600)), QueryOptions::new());
+
+        assert!(res.is_ok());
+        assert_eq!(res.unwrap().jwt(), "aRenewedJwtToken");
+        assert_eq!(
+            k.session().unwrap().expires_at().unwrap().timestamp_millis(),
+            1321085955000
+        );
+    }
+
+    #[test]
+    fn refresh_token_fail_no_jwt() {
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
         k.connect().expect("Unable to connect to Kuzzle server");
-        let res = k.auth().logout();
+        let res = k.auth().refresh_token(None, QueryOptions::new());
 
         assert!(res.is_err());
     }
 
+    #[test]
+    fn search_api_keys_ok() {
+        let _m = mockito::mock("POST", "/api-keys/_search?from=0&size=10")
+            .match_header("authorization", "Bearer mySuperToken")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "auth",
+                    "action": "searchApiKeys",
+                    "volatile": {},
+                    "result": {
+                      "total": 1,
+                      "hits": [
+                        {
+                          "_id": "ferris-key",
+                          "_source": {
+                            "userId": "ferris",
+                            "description": "Nightly backups",
+                            "expiresAt": -1,
+                            "ttl": -1
+                          }
+                        }
+                      ]
+                    }
+                }"#,
+            )
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        k.connect().expect("Unable to connect to Kuzzle server");
+        k.set_jwt("mySuperToken".to_string());
+        let res = k.auth().search_api_keys(
+            &HashMap::new(),
+            QueryOptions::new().set_from(0).set_size(10),
+        );
+
+        let result = res.unwrap();
+        assert_eq!(result.total(), 1);
+        assert_eq!(result.hits()[0].description(), "Nightly backups");
+        assert_eq!(result.hits()[0].expires_at(), None);
+    }
+
     #[test]
     fn update_my_credentials_ok() {
         let _m = mockito::mock("PUT", "/credentials/local/_me/_update")
@@ -1152,12 +1773,17 @@ mod tests {
             )
             .create();
 
-        let mut k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
         k.connect().expect("Unable to connect to Kuzzle server");
         k.set_jwt("mySuperToken".to_string());
         let mut new_content = HashMap::new();
-        new_content.insert("password".to_string(), serde_json::to_value("newpassword").unwrap());
-        let res = k.auth().update_my_credentials("local", &new_content);
+        new_content.insert(
+            "password".to_string(),
+            serde_json::to_value("newpassword").unwrap(),
+        );
+        let res = k
+            .auth()
+            .update_my_credentials("local", &new_content, QueryOptions::new());
 
         assert!(&res.is_ok());
         assert_eq!(
@@ -1189,35 +1815,50 @@ mod tests {
             )
             .create();
 
-        let mut k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
         k.connect().expect("Unable to connect to Kuzzle server");
         k.set_jwt("mySuperToken".to_string());
         let mut new_content = HashMap::new();
-        new_content.insert("password".to_string(), serde_json::to_value("newpassword").unwrap());
-        let res = k.auth().update_my_credentials("local", &new_content);
+        new_content.insert(
+            "password".to_string(),
+            serde_json::to_value("newpassword").unwrap(),
+        );
+        let res = k
+            .auth()
+            .update_my_credentials("local", &new_content, QueryOptions::new());
 
         assert!(res.is_err());
     }
 
     #[test]
     fn update_my_credentials_fail_empty_args() {
-        let mut k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
         k.connect().expect("Unable to connect to Kuzzle server");
         k.set_jwt("mySuperToken".to_string());
         let mut new_content = HashMap::new();
-        new_content.insert("password".to_string(), serde_json::to_value("newpassword").unwrap());
-        let res = k.auth().update_my_credentials("local", &new_content);
+        new_content.insert(
+            "password".to_string(),
+            serde_json::to_value("newpassword").unwrap(),
+        );
+        let res = k
+            .auth()
+            .update_my_credentials("local", &new_content, QueryOptions::new());
 
         assert!(res.is_err());
     }
 
     #[test]
     fn update_my_credentials_fail_not_logged_in() {
-        let mut k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
         k.connect().expect("Unable to connect to Kuzzle server");
         let mut new_content = HashMap::new();
-        new_content.insert("password".to_string(), serde_json::to_value("newpassword").unwrap());
-        let res = k.auth().update_my_credentials("local", &new_content);
+        new_content.insert(
+            "password".to_string(),
+            serde_json::to_value("newpassword").unwrap(),
+        );
+        let res = k
+            .auth()
+            .update_my_credentials("local", &new_content, QueryOptions::new());
 
         assert!(res.is_err());
     }
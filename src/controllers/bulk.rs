@@ -1,15 +1,188 @@
 use crate::kuzzle::Kuzzle;
-use crate::types::{KuzzleRequest, QueryOptions};
+use crate::types::{Error, KuzzleRequest, QueryOptions, SdkError};
+use serde_json::{Map, Value};
 
-pub struct BulkController<'a>(pub &'a mut Kuzzle);
+pub struct BulkController<'a>(pub &'a Kuzzle);
 
 impl<'a> BulkController<'a> {
-    pub fn import(&'a mut self, options: QueryOptions) {
-        let req: KuzzleRequest = KuzzleRequest::new("bulk", "import");
-        self.kuzzle().query(req, options).is_ok();
+    /// Creates, updates or deletes large amounts of documents as fast as possible.
+    ///
+    /// `bulk_data` follows the Elasticsearch bulk format: each action line
+    /// (e.g. `{"index": {"_id": "foo"}}`) is followed by the document content, if any.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::{KuzzleOptions, QueryOptions};
+    /// use serde_json::json;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let bulk_data = vec![
+    ///     json!({ "index": { "_id": "ferris" } }),
+    ///     json!({ "name": "Ferris" }),
+    /// ];
+    /// let res = kuzzle.bulk().import(
+    ///     "ferris_index",
+    ///     "ferris_collection",
+    ///     &bulk_data,
+    ///     QueryOptions::new(),
+    /// );
+    ///
+    /// ```
+    ///
+    pub fn import(
+        &self,
+        index: &str,
+        collection: &str,
+        bulk_data: &[Value],
+        options: QueryOptions,
+    ) -> Result<Map<String, Value>, Error> {
+        self.kuzzle()
+            .call(actions::import(index, collection, bulk_data)?, options)
     }
 
-    fn kuzzle(&'a mut self) -> &'a mut Kuzzle {
+    fn kuzzle(&self) -> &Kuzzle {
         self.0
     }
 }
+
+/// The calls of the `bulk` controller actions.
+pub(crate) mod actions {
+    use super::*;
+    use crate::controllers::Call;
+
+    pub(crate) fn import(
+        index: &str,
+        collection: &str,
+        bulk_data: &[Value],
+    ) -> Result<Call<Map<String, Value>>, Error> {
+        if index.is_empty() || collection.is_empty() || bulk_data.is_empty() {
+            return Err(Error::InvalidArgument(SdkError::new(
+                "BulkController::import",
+                "index, collection and bulk_data arguments must not be empty.",
+            )));
+        }
+
+        let req: KuzzleRequest = KuzzleRequest::new("bulk", "import")
+            .set_index(index)
+            .set_collection(collection)
+            .add_to_body("bulkData", serde_json::to_value(bulk_data)?);
+        Ok(Call::new(req, |res| match &res.error() {
+            None => Ok(res.result().as_object().unwrap().clone()),
+            Some(k_err) => Err(Error::from(k_err.clone())),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocols::Http;
+    use crate::types::KuzzleOptions;
+    use mockito;
+    use serde_json::json;
+
+    #[test]
+    fn import_ok() {
+        let _m = mockito::mock("POST", "/ferris_index/ferris_collection/_bulk")
+            .match_body(r#"{"bulkData":[{"index":{"_id":"ferris"}},{"name":"Ferris"}]}"#)
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "bulk",
+                    "action": "import",
+                    "collection": "ferris_collection",
+                    "index": "ferris_index",
+                    "volatile": null,
+                    "result": {
+                        "hits": [
+                            { "index": { "_id": "ferris", "status": 201 } }
+                        ],
+                        "total": 1
+                    }
+                }"#,
+            )
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        k.connect().expect("Unable to connect to Kuzzle server");
+        let bulk_data = vec![
+            json!({ "index": { "_id": "ferris" } }),
+            json!({ "name": "Ferris" }),
+        ];
+        let res = k.bulk().import(
+            "ferris_index",
+            "ferris_collection",
+            &bulk_data,
+            QueryOptions::new(),
+        );
+
+        assert!(res.is_ok());
+        assert_eq!(res.unwrap().get("total").unwrap().as_u64().unwrap(), 1);
+    }
+
+    #[test]
+    fn import_fail_error() {
+        let _m = mockito::mock("POST", "/ferris_index/ferris_collection/_bulk")
+            .with_status(403)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 403,
+                    "error": {
+                        "message": "Forbidden action [ferris_index/ferris_collection/bulk/import] for user -1",
+                        "status": 403,
+                        "stack": "ForbiddenError: Forbidden action [ferris_index/ferris_collection/bulk/import] for user -1\n"
+                    },
+                    "controller": "bulk",
+                    "action": "import",
+                    "collection": "ferris_collection",
+                    "index": "ferris_index",
+                    "volatile": null,
+                    "result": null
+                }"#,
+            )
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        k.connect().expect("Unable to connect to Kuzzle server");
+        let bulk_data = vec![
+            json!({ "index": { "_id": "ferris" } }),
+            json!({ "name": "Ferris" }),
+        ];
+        let res = k.bulk().import(
+            "ferris_index",
+            "ferris_collection",
+            &bulk_data,
+            QueryOptions::new(),
+        );
+
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn import_fail_empty_args() {
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        k.connect().expect("Unable to connect to Kuzzle server");
+        let res = k.bulk().import(
+            "ferris_index",
+            "ferris_collection",
+            &[],
+            QueryOptions::new(),
+        );
+
+        assert!(res.is_err());
+    }
+}
@@ -1,16 +1,286 @@
 use crate::kuzzle::Kuzzle;
-use crate::types::{KuzzleRequest, QueryOptions};
+use crate::types::{Error, KuzzleRequest, QueryOptions, SdkError};
+use serde_json::{Map, Value};
 
-pub struct CollectionController<'a>(pub &'a mut Kuzzle);
+pub struct CollectionController<'a>(pub &'a Kuzzle);
 
 impl<'a> CollectionController<'a> {
-    pub fn create(&'a mut self, options: QueryOptions) {
-        &self
-            .kuzzle()
-            .query(KuzzleRequest::new("collection", "create"), options);
+    pub fn create(&self, options: QueryOptions) -> Result<(), Error> {
+        self.kuzzle()
+            .query(KuzzleRequest::new("collection", "create"), options)?;
+        Ok(())
     }
 
-    fn kuzzle(&'a mut self) -> &'a mut Kuzzle {
+    /// Returns the collection mapping.
+    ///
+    /// The returned mapping (`properties`, `dynamic`, `_meta`...) can be
+    /// given back as is to `update_mapping`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::{KuzzleOptions, QueryOptions};
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let res = kuzzle
+    ///     .collection()
+    ///     .get_mapping("ferris_index", "ferris_collection", QueryOptions::new());
+    ///
+    /// ```
+    ///
+    pub fn get_mapping(
+        &self,
+        index: &str,
+        collection: &str,
+        options: QueryOptions,
+    ) -> Result<Map<String, Value>, Error> {
+        self.kuzzle()
+            .call(actions::get_mapping(index, collection)?, options)
+    }
+
+    /// Updates the collection mapping.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::{KuzzleOptions, QueryOptions};
+    /// use serde_json::json;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let mapping = json!({ "properties": { "name": { "type": "keyword" } } });
+    /// let res = kuzzle.collection().update_mapping(
+    ///     "ferris_index",
+    ///     "ferris_collection",
+    ///     mapping.as_object().unwrap(),
+    ///     QueryOptions::new(),
+    /// );
+    ///
+    /// ```
+    ///
+    pub fn update_mapping(
+        &self,
+        index: &str,
+        collection: &str,
+        mapping: &Map<String, Value>,
+        options: QueryOptions,
+    ) -> Result<(), Error> {
+        self.kuzzle().call(
+            actions::update_mapping(index, collection, mapping)?,
+            options,
+        )
+    }
+
+    fn kuzzle(&self) -> &Kuzzle {
         self.0
     }
 }
+
+/// The calls of the `collection` controller actions.
+pub(crate) mod actions {
+    use super::*;
+    use crate::controllers::Call;
+
+    pub(crate) fn get_mapping(
+        index: &str,
+        collection: &str,
+    ) -> Result<Call<Map<String, Value>>, Error> {
+        if index.is_empty() || collection.is_empty() {
+            return Err(Error::InvalidArgument(SdkError::new(
+                "CollectionController::get_mapping",
+                "index and collection arguments must not be empty.",
+            )));
+        }
+
+        let req: KuzzleRequest = KuzzleRequest::new("collection", "getMapping")
+            .set_index(index)
+            .set_collection(collection);
+        // Kuzzle v1 nests the mapping under `<index>.mappings.<collection>`
+        let pointer = format!("/{}/mappings/{}", index, collection);
+        Ok(Call::new(req, move |res| match &res.error() {
+            None => match res.result().pointer(&pointer) {
+                Some(mapping) => Ok(mapping.as_object().unwrap().clone()),
+                None => Ok(res.result().as_object().unwrap().clone()),
+            },
+            Some(k_err) => Err(Error::from(k_err.clone())),
+        }))
+    }
+
+    pub(crate) fn update_mapping(
+        index: &str,
+        collection: &str,
+        mapping: &Map<String, Value>,
+    ) -> Result<Call<()>, Error> {
+        if index.is_empty() || collection.is_empty() {
+            return Err(Error::InvalidArgument(SdkError::new(
+                "CollectionController::update_mapping",
+                "index and collection arguments must not be empty.",
+            )));
+        }
+
+        let req: KuzzleRequest = KuzzleRequest::new("collection", "updateMapping")
+            .set_index(index)
+            .set_collection(collection)
+            .set_body(mapping.clone().into_iter().collect());
+        Ok(Call::new(req, |res| match &res.error() {
+            None => Ok(()),
+            Some(k_err) => Err(Error::from(k_err.clone())),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocols::Http;
+    use crate::types::KuzzleOptions;
+    use mockito;
+    use serde_json::json;
+
+    #[test]
+    fn get_mapping_ok() {
+        let _m = mockito::mock("GET", "/ferris_index/ferris_collection/_mapping")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "collection",
+                    "action": "getMapping",
+                    "collection": "ferris_collection",
+                    "index": "ferris_index",
+                    "volatile": null,
+                    "result": {
+                        "ferris_index": {
+                            "mappings": {
+                                "ferris_collection": {
+                                    "properties": {
+                                        "name": { "type": "keyword" }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }"#,
+            )
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        k.connect().expect("Unable to connect to Kuzzle server");
+        let res =
+            k.collection()
+                .get_mapping("ferris_index", "ferris_collection", QueryOptions::new());
+
+        assert!(res.is_ok());
+        assert_eq!(
+            Value::Object(res.unwrap()),
+            json!({ "properties": { "name": { "type": "keyword" } } })
+        );
+    }
+
+    #[test]
+    fn get_mapping_fail_error() {
+        let _m = mockito::mock("GET", "/ferris_index/ferris_collection/_mapping")
+            .with_status(404)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 404,
+                    "error": {
+                        "message": "Collection \"ferris_index\":\"ferris_collection\" does not exist.",
+                        "status": 404,
+                        "stack": "NotFoundError: Collection \"ferris_index\":\"ferris_collection\" does not exist.\n"
+                    },
+                    "controller": "collection",
+                    "action": "getMapping",
+                    "collection": "ferris_collection",
+                    "index": "ferris_index",
+                    "volatile": null,
+                    "result": null
+                }"#,
+            )
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        k.connect().expect("Unable to connect to Kuzzle server");
+        let res =
+            k.collection()
+                .get_mapping("ferris_index", "ferris_collection", QueryOptions::new());
+
+        assert!(matches!(res, Err(Error::NotFound(_))));
+    }
+
+    #[test]
+    fn get_mapping_fail_empty_args() {
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        k.connect().expect("Unable to connect to Kuzzle server");
+        let res = k
+            .collection()
+            .get_mapping("", "ferris_collection", QueryOptions::new());
+
+        assert!(matches!(res, Err(Error::InvalidArgument(_))));
+    }
+
+    #[test]
+    fn update_mapping_ok() {
+        let _m = mockito::mock("PUT", "/ferris_index/ferris_collection/_mapping")
+            .match_body(r#"{"properties":{"name":{"type":"keyword"}}}"#)
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "collection",
+                    "action": "updateMapping",
+                    "collection": "ferris_collection",
+                    "index": "ferris_index",
+                    "volatile": null,
+                    "result": {
+                        "acknowledged": true
+                    }
+                }"#,
+            )
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        k.connect().expect("Unable to connect to Kuzzle server");
+        let mapping = json!({ "properties": { "name": { "type": "keyword" } } });
+        let res = k.collection().update_mapping(
+            "ferris_index",
+            "ferris_collection",
+            mapping.as_object().unwrap(),
+            QueryOptions::new(),
+        );
+
+        assert!(res.is_ok());
+    }
+
+    #[test]
+    fn update_mapping_fail_empty_args() {
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        k.connect().expect("Unable to connect to Kuzzle server");
+        let res =
+            k.collection()
+                .update_mapping("ferris_index", "", &Map::new(), QueryOptions::new());
+
+        assert!(res.is_err());
+    }
+}
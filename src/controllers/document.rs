@@ -1,15 +1,789 @@
 use crate::kuzzle::Kuzzle;
-use crate::types::{KuzzleRequest, QueryOptions};
+use crate::types::{
+    Document, Error, KuzzleRequest, MultiResult, QueryOptions, SdkError, SearchResult,
+};
+use serde_json::Value;
+use std::collections::HashMap;
 
-pub struct DocumentController<'a>(pub &'a mut Kuzzle);
+pub struct DocumentController<'a>(pub &'a Kuzzle);
 
 impl<'a> DocumentController<'a> {
-    pub fn create(&'a mut self, options: QueryOptions) {
+    pub fn create(&self, options: QueryOptions) -> Result<(), Error> {
         let req: KuzzleRequest = KuzzleRequest::new("document", "create");
-        self.kuzzle().query(req, options).is_ok();
+        self.kuzzle().query(req, options)?;
+        Ok(())
     }
 
-    fn kuzzle(&'a mut self) -> &'a mut Kuzzle {
+    /// Creates multiple documents.
+    /// Each document is given as `{ "_id": ..., "body": { ... } }`, `_id` being optional.
+    /// Items that could not be processed are listed in `MultiResult::errors`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::{KuzzleOptions, QueryOptions};
+    /// use serde_json::json;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let documents = vec![
+    ///     json!({ "_id": "ferris", "body": { "name": "Ferris" } }),
+    ///     json!({ "_id": "corro", "body": { "name": "Corro" } }),
+    /// ];
+    /// let res = kuzzle
+    ///     .document()
+    ///     .mcreate("ferris_index", "ferris_collection", &documents, QueryOptions::new());
+    ///
+    /// ```
+    ///
+    pub fn mcreate(
+        &self,
+        index: &str,
+        collection: &str,
+        documents: &[Value],
+        options: QueryOptions,
+    ) -> Result<MultiResult<Document>, Error> {
+        self.kuzzle()
+            .call(actions::mcreate(index, collection, documents)?, options)
+    }
+
+    /// Deletes multiple documents.
+    /// Items that could not be processed are listed in `MultiResult::errors`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::{KuzzleOptions, QueryOptions};
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let res = kuzzle.document().mdelete(
+    ///     "ferris_index",
+    ///     "ferris_collection",
+    ///     vec!["ferris".to_string(), "corro".to_string()],
+    ///     QueryOptions::new(),
+    /// );
+    ///
+    /// ```
+    ///
+    pub fn mdelete(
+        &self,
+        index: &str,
+        collection: &str,
+        ids: Vec<String>,
+        options: QueryOptions,
+    ) -> Result<MultiResult<String>, Error> {
+        self.kuzzle()
+            .call(actions::mdelete(index, collection, ids)?, options)
+    }
+
+    /// Updates multiple documents.
+    /// Each document is given as `{ "_id": ..., "body": { ... } }`, `body` holding the fields to update.
+    /// Items that could not be processed are listed in `MultiResult::errors`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::{KuzzleOptions, QueryOptions};
+    /// use serde_json::json;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let documents = vec![
+    ///     json!({ "_id": "ferris", "body": { "name": "Ferris" } }),
+    ///     json!({ "_id": "corro", "body": { "name": "Corro" } }),
+    /// ];
+    /// let res = kuzzle
+    ///     .document()
+    ///     .mupdate("ferris_index", "ferris_collection", &documents, QueryOptions::new());
+    ///
+    /// ```
+    ///
+    pub fn mupdate(
+        &self,
+        index: &str,
+        collection: &str,
+        documents: &[Value],
+        options: QueryOptions,
+    ) -> Result<MultiResult<Document>, Error> {
+        self.kuzzle()
+            .call(actions::mupdate(index, collection, documents)?, options)
+    }
+
+    /// Moves a search cursor forward.
+    /// A search cursor is created by a `search` query with a `scroll` value provided.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::{KuzzleOptions, QueryOptions};
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let res = kuzzle
+    ///     .document()
+    ///     .scroll("AAAAAAAAAAQSFnVmZktzX2tQ", "1m", QueryOptions::new());
+    ///
+    /// ```
+    ///
+    pub fn scroll(
+        &self,
+        scroll_id: &str,
+        scroll: &str,
+        options: QueryOptions,
+    ) -> Result<SearchResult, Error> {
+        self.kuzzle()
+            .call(actions::scroll(scroll_id, scroll)?, options)
+    }
+
+    /// Searches documents in the given collection, using Elasticsearch Query DSL.
+    ///
+    /// Results are paginated with `QueryOptions::set_from` and `QueryOptions::set_size`.
+    /// Providing a `QueryOptions::set_scroll` duration (e.g. `"1m"`) opens a search cursor,
+    /// whose identifier is available through `SearchResult::scroll_id`
+    /// and can be consumed with `scroll`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::{KuzzleOptions, QueryOptions};
+    /// use std::collections::HashMap;
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let res = kuzzle
+    ///     .document()
+    ///     .search(
+    ///         "ferris_index",
+    ///         "ferris_collection",
+    ///         &HashMap::new(),
+    ///         QueryOptions::new().set_from(0).set_size(10),
+    ///     );
+    ///
+    /// ```
+    ///
+    pub fn search(
+        &self,
+        index: &str,
+        collection: &str,
+        query: &HashMap<String, Value>,
+        options: QueryOptions,
+    ) -> Result<SearchResult, Error> {
+        self.kuzzle()
+            .call(actions::search(index, collection, query)?, options)
+    }
+
+    fn kuzzle(&self) -> &Kuzzle {
         self.0
     }
 }
+
+/// The calls of the `document` controller actions.
+pub(crate) mod actions {
+    use super::*;
+    use crate::controllers::Call;
+
+    pub(crate) fn mcreate(
+        index: &str,
+        collection: &str,
+        documents: &[Value],
+    ) -> Result<Call<MultiResult<Document>>, Error> {
+        if index.is_empty() || collection.is_empty() || documents.is_empty() {
+            return Err(Error::InvalidArgument(SdkError::new(
+                "DocumentController::mcreate",
+                "index, collection and documents arguments must not be empty.",
+            )));
+        }
+
+        let req: KuzzleRequest = KuzzleRequest::new("document", "mCreate")
+            .set_index(index)
+            .set_collection(collection)
+            .add_to_body("documents", serde_json::to_value(documents)?);
+        Ok(Call::new(req, |res| MultiResult::from_response(&res)))
+    }
+
+    pub(crate) fn mdelete(
+        index: &str,
+        collection: &str,
+        ids: Vec<String>,
+    ) -> Result<Call<MultiResult<String>>, Error> {
+        if index.is_empty() || collection.is_empty() || ids.is_empty() {
+            return Err(Error::InvalidArgument(SdkError::new(
+                "DocumentController::mdelete",
+                "index, collection and ids arguments must not be empty.",
+            )));
+        }
+
+        let req: KuzzleRequest = KuzzleRequest::new("document", "mDelete")
+            .set_index(index)
+            .set_collection(collection)
+            .add_to_body("ids", serde_json::to_value(ids)?);
+        Ok(Call::new(req, |res| MultiResult::ids_from_response(&res)))
+    }
+
+    pub(crate) fn mupdate(
+        index: &str,
+        collection: &str,
+        documents: &[Value],
+    ) -> Result<Call<MultiResult<Document>>, Error> {
+        if index.is_empty() || collection.is_empty() || documents.is_empty() {
+            return Err(Error::InvalidArgument(SdkError::new(
+                "DocumentController::mupdate",
+                "index, collection and documents arguments must not be empty.",
+            )));
+        }
+
+        let req: KuzzleRequest = KuzzleRequest::new("document", "mUpdate")
+            .set_index(index)
+            .set_collection(collection)
+            .add_to_body("documents", serde_json::to_value(documents)?);
+        Ok(Call::new(req, |res| MultiResult::from_response(&res)))
+    }
+
+    pub(crate) fn scroll(scroll_id: &str, scroll: &str) -> Result<Call<SearchResult>, Error> {
+        if scroll_id.is_empty() {
+            return Err(Error::InvalidArgument(SdkError::new(
+                "DocumentController::scroll",
+                "scroll_id argument must not be empty.",
+            )));
+        }
+
+        let mut req: KuzzleRequest = KuzzleRequest::new("document", "scroll")
+            .add_to_query_strings("scrollId", serde_json::to_value(scroll_id)?);
+
+        if !scroll.is_empty() {
+            req = req.add_to_query_strings("scroll", serde_json::to_value(scroll)?);
+        }
+        Ok(Call::new(req, |res| match &res.error() {
+            None => {
+                let result: SearchResult = serde_json::from_value(res.result().clone())?;
+                Ok(result)
+            }
+            Some(k_err) => Err(Error::from(k_err.clone())),
+        }))
+    }
+
+    pub(crate) fn search(
+        index: &str,
+        collection: &str,
+        query: &HashMap<String, Value>,
+    ) -> Result<Call<SearchResult>, Error> {
+        if index.is_empty() || collection.is_empty() {
+            return Err(Error::InvalidArgument(SdkError::new(
+                "DocumentController::search",
+                "index and collection arguments must not be empty.",
+            )));
+        }
+
+        let req: KuzzleRequest = KuzzleRequest::new("document", "search")
+            .set_index(index)
+            .set_collection(collection)
+            .set_body(query.clone());
+        Ok(Call::new(req, |res| match &res.error() {
+            None => {
+                let result: SearchResult = serde_json::from_value(res.result().clone())?;
+                Ok(result)
+            }
+            Some(k_err) => Err(Error::from(k_err.clone())),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocols::Http;
+    use crate::types::KuzzleOptions;
+    use mockito;
+    use serde_json::json;
+
+    #[test]
+    fn mcreate_ok() {
+        let _m = mockito::mock("POST", "/ferris_index/ferris_collection/_mCreate")
+            .match_body(
+                r#"{"documents":[{"_id":"ferris","body":{"name":"Ferris"}},{"_id":"corro","body":{"name":"Corro"}}]}"#,
+            )
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "document",
+                    "action": "mCreate",
+                    "collection": "ferris_collection",
+                    "index": "ferris_index",
+                    "volatile": null,
+                    "result": {
+                        "successes": [
+                            {
+                                "_id": "ferris",
+                                "_source": { "name": "Ferris" },
+                                "_version": 1,
+                                "created": true
+                            }
+                        ],
+                        "errors": [
+                            {
+                                "document": { "_id": "corro", "body": { "name": "Corro" } },
+                                "status": 409,
+                                "reason": "document already exists"
+                            }
+                        ]
+                    }
+                }"#,
+            )
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        k.connect().expect("Unable to connect to Kuzzle server");
+        let documents = vec![
+            json!({ "_id": "ferris", "body": { "name": "Ferris" } }),
+            json!({ "_id": "corro", "body": { "name": "Corro" } }),
+        ];
+        let res = k.document().mcreate(
+            "ferris_index",
+            "ferris_collection",
+            &documents,
+            QueryOptions::new(),
+        );
+
+        assert!(res.is_ok());
+        let result = res.unwrap();
+        assert!(result.has_errors());
+        assert_eq!(result.successes()[0].id(), "ferris".to_string());
+        assert_eq!(result.errors()[0].id(), "corro".to_string());
+        assert_eq!(result.errors()[0].status(), Some(409));
+        assert_eq!(result.errors()[0].reason(), "document already exists");
+    }
+
+    #[test]
+    fn mcreate_ok_partial_error() {
+        let _m = mockito::mock("POST", "/ferris_index/ferris_collection/_mCreate")
+            .with_status(206)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 206,
+                    "error": {
+                        "message": "Some document creations failed",
+                        "status": 206,
+                        "errors": [
+                            {
+                                "document": { "_id": "corro", "body": { "name": "Corro" } },
+                                "reason": "document already exists"
+                            }
+                        ],
+                        "count": 1
+                    },
+                    "controller": "document",
+                    "action": "mCreate",
+                    "collection": "ferris_collection",
+                    "index": "ferris_index",
+                    "volatile": null,
+                    "result": {
+                        "hits": [
+                            {
+                                "_id": "ferris",
+                                "_source": { "name": "Ferris" },
+                                "_version": 1,
+                                "result": "created",
+                                "status": 201
+                            }
+                        ],
+                        "total": 1
+                    }
+                }"#,
+            )
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        k.connect().expect("Unable to connect to Kuzzle server");
+        let documents = vec![
+            json!({ "_id": "ferris", "body": { "name": "Ferris" } }),
+            json!({ "_id": "corro", "body": { "name": "Corro" } }),
+        ];
+        let res = k.document().mcreate(
+            "ferris_index",
+            "ferris_collection",
+            &documents,
+            QueryOptions::new(),
+        );
+
+        assert!(res.is_ok());
+        let result = res.unwrap();
+        assert_eq!(result.successes().len(), 1);
+        assert_eq!(result.errors().len(), 1);
+        assert_eq!(result.errors()[0].id(), "corro".to_string());
+        assert_eq!(
+            result.errors()[0].document(),
+            json!({ "_id": "corro", "body": { "name": "Corro" } })
+        );
+    }
+
+    #[test]
+    fn mcreate_fail_error() {
+        let _m = mockito::mock("POST", "/ferris_index/ferris_collection/_mCreate")
+            .with_status(403)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 403,
+                    "error": {
+                        "message": "Forbidden action [ferris_index/ferris_collection/document/mCreate] for user -1",
+                        "status": 403,
+                        "stack": "ForbiddenError: Forbidden action [ferris_index/ferris_collection/document/mCreate] for user -1\n"
+                    },
+                    "controller": "document",
+                    "action": "mCreate",
+                    "collection": "ferris_collection",
+                    "index": "ferris_index",
+                    "volatile": null,
+                    "result": null
+                }"#,
+            )
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        k.connect().expect("Unable to connect to Kuzzle server");
+        let documents = vec![json!({ "_id": "ferris", "body": { "name": "Ferris" } })];
+        let res = k.document().mcreate(
+            "ferris_index",
+            "ferris_collection",
+            &documents,
+            QueryOptions::new(),
+        );
+
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn mcreate_fail_empty_args() {
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        k.connect().expect("Unable to connect to Kuzzle server");
+        let res = k.document().mcreate(
+            "ferris_index",
+            "ferris_collection",
+            &[],
+            QueryOptions::new(),
+        );
+
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn mdelete_ok() {
+        let _m = mockito::mock("DELETE", "/ferris_index/ferris_collection/_mDelete")
+            .match_body(r#"{"ids":["ferris","corro"]}"#)
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "document",
+                    "action": "mDelete",
+                    "collection": "ferris_collection",
+                    "index": "ferris_index",
+                    "volatile": null,
+                    "result": {
+                        "successes": [
+                            { "_id": "ferris", "_source": { "name": "Ferris" } }
+                        ],
+                        "errors": [
+                            { "id": "corro", "reason": "document not found" }
+                        ]
+                    }
+                }"#,
+            )
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        k.connect().expect("Unable to connect to Kuzzle server");
+        let res = k.document().mdelete(
+            "ferris_index",
+            "ferris_collection",
+            vec!["ferris".to_string(), "corro".to_string()],
+            QueryOptions::new(),
+        );
+
+        assert!(res.is_ok());
+        let result = res.unwrap();
+        assert_eq!(result.successes(), &vec!["ferris".to_string()]);
+        assert_eq!(result.errors()[0].id(), "corro".to_string());
+        assert_eq!(result.errors()[0].reason(), "document not found");
+    }
+
+    #[test]
+    fn mdelete_fail_empty_args() {
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        k.connect().expect("Unable to connect to Kuzzle server");
+        let res = k.document().mdelete(
+            "ferris_index",
+            "ferris_collection",
+            vec![],
+            QueryOptions::new(),
+        );
+
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn mupdate_ok() {
+        let _m = mockito::mock("PUT", "/ferris_index/ferris_collection/_mUpdate")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "document",
+                    "action": "mUpdate",
+                    "collection": "ferris_collection",
+                    "index": "ferris_index",
+                    "volatile": null,
+                    "result": {
+                        "successes": [
+                            { "_id": "ferris", "_version": 2 }
+                        ],
+                        "errors": []
+                    }
+                }"#,
+            )
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        k.connect().expect("Unable to connect to Kuzzle server");
+        let documents = vec![json!({ "_id": "ferris", "body": { "age": 4 } })];
+        let res = k.document().mupdate(
+            "ferris_index",
+            "ferris_collection",
+            &documents,
+            QueryOptions::new(),
+        );
+
+        assert!(res.is_ok());
+        let result = res.unwrap();
+        assert!(!result.has_errors());
+        assert_eq!(result.successes()[0].id(), "ferris".to_string());
+    }
+
+    #[test]
+    fn mupdate_fail_empty_args() {
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        k.connect().expect("Unable to connect to Kuzzle server");
+        let res = k
+            .document()
+            .mupdate("", "ferris_collection", &[json!({})], QueryOptions::new());
+
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn scroll_ok() {
+        let _m = mockito::mock(
+            "GET",
+            mockito::Matcher::Regex(r"^/_scroll/AAAAAAAAAAQSFnVmZktzX2tQ\?scroll=1m$".to_string()),
+        )
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            r#"{
+                "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                "status": 200,
+                "error": null,
+                "controller": "document",
+                "action": "scroll",
+                "collection": null,
+                "index": null,
+                "volatile": null,
+                "result": {
+                    "scrollId": "AAAAAAAAAAQSFnVmZktzX2tQ",
+                    "total": 3,
+                    "hits": [
+                        {
+                            "_id": "ferris",
+                            "_source": {
+                                "name": "Ferris",
+                                "species": "crab"
+                            }
+                        }
+                    ]
+                }
+            }"#,
+        )
+        .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        k.connect().expect("Unable to connect to Kuzzle server");
+        let res = k
+            .document()
+            .scroll("AAAAAAAAAAQSFnVmZktzX2tQ", "1m", QueryOptions::new());
+
+        assert!(res.is_ok());
+        let result = res.unwrap();
+        assert_eq!(result.total(), 3);
+        assert_eq!(result.scroll_id(), "AAAAAAAAAAQSFnVmZktzX2tQ".to_string());
+        assert_eq!(result.hits()[0].id(), "ferris".to_string());
+    }
+
+    #[test]
+    fn scroll_fail_empty_scroll_id() {
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        k.connect().expect("Unable to connect to Kuzzle server");
+        let res = k.document().scroll("", "1m", QueryOptions::new());
+
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn search_ok() {
+        let _m = mockito::mock(
+            "POST",
+            mockito::Matcher::Regex(
+                r"^/ferris_index/ferris_collection/_search\?.*size=10".to_string(),
+            ),
+        )
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            r#"{
+                "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                "status": 200,
+                "error": null,
+                "controller": "document",
+                "action": "search",
+                "collection": "ferris_collection",
+                "index": "ferris_index",
+                "volatile": null,
+                "result": {
+                    "total": 2,
+                    "hits": [
+                        {
+                            "_id": "ferris",
+                            "_source": {
+                                "name": "Ferris"
+                            }
+                        },
+                        {
+                            "_id": "corro",
+                            "_source": {
+                                "name": "Corro"
+                            }
+                        }
+                    ]
+                }
+            }"#,
+        )
+        .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        k.connect().expect("Unable to connect to Kuzzle server");
+        let res = k.document().search(
+            "ferris_index",
+            "ferris_collection",
+            &HashMap::new(),
+            QueryOptions::new().set_from(0).set_size(10),
+        );
+
+        assert!(res.is_ok());
+        let result = res.unwrap();
+        assert_eq!(result.total(), 2);
+        assert_eq!(result.hits().len(), 2);
+        assert_eq!(result.scroll_id(), String::new());
+        assert_eq!(
+            result.hits()[1]
+                .content()
+                .get("name")
+                .unwrap()
+                .as_str()
+                .unwrap(),
+            "Corro"
+        );
+    }
+
+    #[test]
+    fn search_fail_error() {
+        let _m = mockito::mock(
+            "POST",
+            mockito::Matcher::Regex(r"^/ferris_index/ferris_collection/_search\?".to_string()),
+        )
+        .with_status(404)
+        .with_header("content-type", "application/json")
+        .with_body(
+            r#"{
+                "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                "status": 404,
+                "error": {
+                    "message": "Collection \"ferris_index\":\"ferris_collection\" does not exist.",
+                    "status": 404,
+                    "stack": "NotFoundError: Collection \"ferris_index\":\"ferris_collection\" does not exist.\n"
+                },
+                "controller": "document",
+                "action": "search",
+                "collection": "ferris_collection",
+                "index": "ferris_index",
+                "volatile": null,
+                "result": null
+            }"#,
+        )
+        .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        k.connect().expect("Unable to connect to Kuzzle server");
+        let res = k.document().search(
+            "ferris_index",
+            "ferris_collection",
+            &HashMap::new(),
+            QueryOptions::new().set_from(0).set_size(10),
+        );
+
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn search_fail_empty_args() {
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        k.connect().expect("Unable to connect to Kuzzle server");
+        let res = k
+            .document()
+            .search("", "", &HashMap::new(), QueryOptions::new());
+
+        assert!(res.is_err());
+    }
+}
@@ -1,10 +1,8 @@
 use crate::kuzzle::Kuzzle;
-use crate::types::{KuzzleRequest, QueryOptions, SdkError};
+use crate::types::{Error, KuzzleRequest, MultiResult, QueryOptions, SdkError};
 use serde_json;
 
-use std::error::Error;
-
-pub struct IndexController<'a>(pub &'a mut Kuzzle);
+pub struct IndexController<'a>(pub &'a Kuzzle);
 
 impl<'a> IndexController<'a> {
     /// Create a new index in Kuzzl.
@@ -14,32 +12,20 @@ impl<'a> IndexController<'a> {
     /// ```
     /// use kuzzle_sdk::kuzzle::Kuzzle;
     /// use kuzzle_sdk::protocols::Http;
-    /// use kuzzle_sdk::types::KuzzleOptions;
+    /// use kuzzle_sdk::types::{KuzzleOptions, QueryOptions};
     ///
-    /// let mut kuzzle = Kuzzle::new(
+    /// let kuzzle = Kuzzle::new(
     ///     Http::new(
     ///         KuzzleOptions::new("localhost", 7512)
     ///     )
     /// );
     ///
-    /// let res = kuzzle.index().create("ferris_index");
+    /// let res = kuzzle.index().create("ferris_index", QueryOptions::new());
     ///
     /// ```
     ///
-    pub fn create(&'a mut self, index: &str) -> Result<(), Box<Error>> {
-        if index.is_empty() {
-            return Err(Box::new(SdkError::new(
-                "IndexController::create",
-                "index argument must not be empty.",
-            )));
-        }
-
-        let req: KuzzleRequest = KuzzleRequest::new("index", "create").set_index(index);
-        let res = self.kuzzle().query(req, QueryOptions::new())?;
-        match &res.error() {
-            None => Ok(()),
-            Some(k_err) => Err(Box::new(k_err.clone())),
-        }
+    pub fn create(&self, index: &str, options: QueryOptions) -> Result<(), Error> {
+        self.kuzzle().call(actions::create(index)?, options)
     }
 
     /// Delete an entire data index from Kuzzle.
@@ -49,32 +35,20 @@ impl<'a> IndexController<'a> {
     /// ```
     /// use kuzzle_sdk::kuzzle::Kuzzle;
     /// use kuzzle_sdk::protocols::Http;
-    /// use kuzzle_sdk::types::KuzzleOptions;
+    /// use kuzzle_sdk::types::{KuzzleOptions, QueryOptions};
     ///
-    /// let mut kuzzle = Kuzzle::new(
+    /// let kuzzle = Kuzzle::new(
     ///     Http::new(
     ///         KuzzleOptions::new("localhost", 7512)
     ///     )
     /// );
     ///
-    /// let res = kuzzle.index().delete("ferris_index");
+    /// let res = kuzzle.index().delete("ferris_index", QueryOptions::new());
     ///
     /// ```
     ///
-    pub fn delete(&'a mut self, index: &str) -> Result<(), Box<Error>> {
-        if index.is_empty() {
-            return Err(Box::new(SdkError::new(
-                "IndexController::delete",
-                "index argument must not be empty.",
-            )));
-        }
-
-        let req: KuzzleRequest = KuzzleRequest::new("index", "delete").set_index(index);
-        let res = self.kuzzle().query(req, QueryOptions::new())?;
-        match &res.error() {
-            None => Ok(()),
-            Some(k_err) => Err(Box::new(k_err.clone())),
-        }
+    pub fn delete(&self, index: &str, options: QueryOptions) -> Result<(), Error> {
+        self.kuzzle().call(actions::delete(index)?, options)
     }
 
     /// Checks if the given index exists in Kuzzle.
@@ -84,32 +58,20 @@ impl<'a> IndexController<'a> {
     /// ```
     /// use kuzzle_sdk::kuzzle::Kuzzle;
     /// use kuzzle_sdk::protocols::Http;
-    /// use kuzzle_sdk::types::KuzzleOptions;
+    /// use kuzzle_sdk::types::{KuzzleOptions, QueryOptions};
     ///
-    /// let mut kuzzle = Kuzzle::new(
+    /// let kuzzle = Kuzzle::new(
     ///     Http::new(
     ///         KuzzleOptions::new("localhost", 7512)
     ///     )
     /// );
     ///
-    /// let res = kuzzle.index().exists("ferris_index");
+    /// let res = kuzzle.index().exists("ferris_index", QueryOptions::new());
     ///
     /// ```
     ///
-    pub fn exists(&'a mut self, index: &str) -> Result<bool, Box<Error>> {
-        if index.is_empty() {
-            return Err(Box::new(SdkError::new(
-                "IndexController::exists",
-                "index argument must not be empty.",
-            )));
-        }
-
-        let req: KuzzleRequest = KuzzleRequest::new("index", "exists").set_index(index);
-        let res = self.kuzzle().query(req, QueryOptions::new())?;
-        match &res.error() {
-            None => Ok(res.result().as_bool().unwrap()),
-            Some(k_err) => Err(Box::new(k_err.clone())),
-        }
+    pub fn exists(&self, index: &str, options: QueryOptions) -> Result<bool, Error> {
+        self.kuzzle().call(actions::exists(index)?, options)
     }
 
     /// Return the current autorefresh status for the index.
@@ -122,32 +84,21 @@ impl<'a> IndexController<'a> {
     /// ```
     /// use kuzzle_sdk::kuzzle::Kuzzle;
     /// use kuzzle_sdk::protocols::Http;
-    /// use kuzzle_sdk::types::KuzzleOptions;
+    /// use kuzzle_sdk::types::{KuzzleOptions, QueryOptions};
     ///
-    /// let mut kuzzle = Kuzzle::new(
+    /// let kuzzle = Kuzzle::new(
     ///     Http::new(
     ///         KuzzleOptions::new("localhost", 7512)
     ///     )
     /// );
     ///
-    /// let res = kuzzle.index().get_auto_refresh("ferris_index");
+    /// let res = kuzzle.index().get_auto_refresh("ferris_index", QueryOptions::new());
     ///
     /// ```
     ///
-    pub fn get_auto_refresh(&'a mut self, index: &str) -> Result<bool, Box<Error>> {
-        if index.is_empty() {
-            return Err(Box::new(SdkError::new(
-                "IndexController::get_auto_refresh",
-                "index argument must not be empty.",
-            )));
-        }
-
-        let req: KuzzleRequest = KuzzleRequest::new("index", "getAutoRefresh").set_index(index);
-        let res = self.kuzzle().query(req, QueryOptions::new())?;
-        match &res.error() {
-            None => Ok(res.result().as_bool().unwrap()),
-            Some(k_err) => Err(Box::new(k_err.clone())),
-        }
+    pub fn get_auto_refresh(&self, index: &str, options: QueryOptions) -> Result<bool, Error> {
+        self.kuzzle()
+            .call(actions::get_auto_refresh(index)?, options)
     }
 
     /// Get the complete list of data indexes handled by Kuzzle.
@@ -157,81 +108,53 @@ impl<'a> IndexController<'a> {
     /// ```
     /// use kuzzle_sdk::kuzzle::Kuzzle;
     /// use kuzzle_sdk::protocols::Http;
-    /// use kuzzle_sdk::types::KuzzleOptions;
+    /// use kuzzle_sdk::types::{KuzzleOptions, QueryOptions};
     ///
-    /// let mut kuzzle = Kuzzle::new(
+    /// let kuzzle = Kuzzle::new(
     ///     Http::new(
     ///         KuzzleOptions::new("localhost", 7512)
     ///     )
     /// );
     ///
-    /// let res = kuzzle.index().list();
+    /// let res = kuzzle.index().list(QueryOptions::new());
     ///
     /// ```
     ///
-    pub fn list(&'a mut self) -> Result<Vec<String>, Box<Error>> {
-        let req: KuzzleRequest = KuzzleRequest::new("index", "list");
-        let res = self.kuzzle().query(req, QueryOptions::new())?;
-        match &res.error() {
-            None => Ok(res
-                .result()
-                .as_object()
-                .unwrap()
-                .get("indexes")
-                .unwrap()
-                .as_array()
-                .unwrap()
-                .iter()
-                .map(|x| x.as_str().unwrap().to_string())
-                .collect::<Vec<String>>()),
-            Some(k_err) => Err(Box::new(k_err.clone())),
-        }
+    pub fn list(&self, options: QueryOptions) -> Result<Vec<String>, Error> {
+        self.kuzzle().call(actions::list()?, options)
     }
 
     /// Deletes multiple indexes at once.
+    /// Indexes that could not be deleted are listed in `MultiResult::errors`.
     ///
     /// # Example
     ///
     /// ```
     /// use kuzzle_sdk::kuzzle::Kuzzle;
     /// use kuzzle_sdk::protocols::Http;
-    /// use kuzzle_sdk::types::KuzzleOptions;
+    /// use kuzzle_sdk::types::{KuzzleOptions, QueryOptions};
     ///
-    /// let mut kuzzle = Kuzzle::new(
+    /// let kuzzle = Kuzzle::new(
     ///     Http::new(
     ///         KuzzleOptions::new("localhost", 7512)
     ///     )
     /// );
     ///
-    /// let res = kuzzle.index().list();
+    /// let res = kuzzle
+    ///     .index()
+    ///     .mdelete(
+    ///         vec!["ferris_index".to_string(), "corro_index".to_string()],
+    ///         QueryOptions::new(),
+    ///     );
     ///
     /// ```
     ///
-    pub fn mdelete(&'a mut self, indexes: Vec<String>) -> Result<Vec<String>, Box<Error>> {
-        if indexes.is_empty() {
-            return Err(Box::new(SdkError::new(
-                "IndexController::mDelete",
-                "indexes argument must not be empty.",
-            )));
-        }
-
-        let req: KuzzleRequest = KuzzleRequest::new("index", "mDelete")
-            .add_to_body("indexes", serde_json::to_value(indexes)?);
-        let res = self.kuzzle().query(req, QueryOptions::new())?;
-        match &res.error() {
-            None => Ok(res
-                .result()
-                .as_object()
-                .unwrap()
-                .get("deleted")
-                .unwrap()
-                .as_array()
-                .unwrap()
-                .iter()
-                .map(|x| x.as_str().unwrap().to_string())
-                .collect::<Vec<String>>()),
-            Some(k_err) => Err(Box::new(k_err.clone())),
-        }
+    pub fn mdelete(
+        &self,
+        indexes: Vec<String>,
+        options: QueryOptions,
+    ) -> Result<MultiResult<String>, Error> {
+        self.kuzzle().call(actions::mdelete(indexes)?, options)
     }
 
     /// Forces an immediate reindexation of the provided index.
@@ -247,32 +170,20 @@ impl<'a> IndexController<'a> {
     /// ```
     /// use kuzzle_sdk::kuzzle::Kuzzle;
     /// use kuzzle_sdk::protocols::Http;
-    /// use kuzzle_sdk::types::KuzzleOptions;
+    /// use kuzzle_sdk::types::{KuzzleOptions, QueryOptions};
     ///
-    /// let mut kuzzle = Kuzzle::new(
+    /// let kuzzle = Kuzzle::new(
     ///     Http::new(
     ///         KuzzleOptions::new("localhost", 7512)
     ///     )
     /// );
     ///
-    /// let res = kuzzle.index().refresh("ferris_index");
+    /// let res = kuzzle.index().refresh("ferris_index", QueryOptions::new());
     ///
     /// ```
     ///
-    pub fn refresh(&'a mut self, index: &str) -> Result<(), Box<Error>> {
-        if index.is_empty() {
-            return Err(Box::new(SdkError::new(
-                "IndexController::refresh",
-                "index argument must not be empty.",
-            )));
-        }
-
-        let req: KuzzleRequest = KuzzleRequest::new("index", "refresh").set_index(index);
-        let res = self.kuzzle().query(req, QueryOptions::new())?;
-        match &res.error() {
-            None => Ok(()),
-            Some(k_err) => Err(Box::new(k_err.clone())),
-        }
+    pub fn refresh(&self, index: &str, options: QueryOptions) -> Result<(), Error> {
+        self.kuzzle().call(actions::refresh(index)?, options)
     }
 
     /// Forces an immediate reindexation of Kuzzle internal storage.
@@ -288,25 +199,20 @@ impl<'a> IndexController<'a> {
     /// ```
     /// use kuzzle_sdk::kuzzle::Kuzzle;
     /// use kuzzle_sdk::protocols::Http;
-    /// use kuzzle_sdk::types::KuzzleOptions;
+    /// use kuzzle_sdk::types::{KuzzleOptions, QueryOptions};
     ///
-    /// let mut kuzzle = Kuzzle::new(
+    /// let kuzzle = Kuzzle::new(
     ///     Http::new(
     ///         KuzzleOptions::new("localhost", 7512)
     ///     )
     /// );
     ///
-    /// let res = kuzzle.index().refresh_internal();
+    /// let res = kuzzle.index().refresh_internal(QueryOptions::new());
     ///
     /// ```
     ///
-    pub fn refresh_internal(&'a mut self) -> Result<(), Box<Error>> {
-        let req: KuzzleRequest = KuzzleRequest::new("index", "refreshInternal");
-        let res = self.kuzzle().query(req, QueryOptions::new())?;
-        match &res.error() {
-            None => Ok(()),
-            Some(k_err) => Err(Box::new(k_err.clone())),
-        }
+    pub fn refresh_internal(&self, options: QueryOptions) -> Result<(), Error> {
+        self.kuzzle().call(actions::refresh_internal()?, options)
     }
 
     /// Changes the autoRefresh configuration of an index.
@@ -323,25 +229,155 @@ impl<'a> IndexController<'a> {
     /// ```
     /// use kuzzle_sdk::kuzzle::Kuzzle;
     /// use kuzzle_sdk::protocols::Http;
-    /// use kuzzle_sdk::types::KuzzleOptions;
+    /// use kuzzle_sdk::types::{KuzzleOptions, QueryOptions};
     ///
-    /// let mut kuzzle = Kuzzle::new(
+    /// let kuzzle = Kuzzle::new(
     ///     Http::new(
     ///         KuzzleOptions::new("localhost", 7512)
     ///     )
     /// );
     ///
-    /// let res = kuzzle.index().set_auto_refresh("ferris_index", true);
+    /// let res = kuzzle.index().set_auto_refresh("ferris_index", true, QueryOptions::new());
     ///
     /// ```
     ///
     pub fn set_auto_refresh(
-        &'a mut self,
+        &self,
         index: &str,
         auto_refresh: bool,
-    ) -> Result<(), Box<Error>> {
+        options: QueryOptions,
+    ) -> Result<(), Error> {
+        self.kuzzle()
+            .call(actions::set_auto_refresh(index, auto_refresh)?, options)
+    }
+
+    fn kuzzle(&self) -> &Kuzzle {
+        self.0
+    }
+}
+
+/// The calls of the `index` controller actions.
+pub(crate) mod actions {
+    use super::*;
+    use crate::controllers::Call;
+
+    pub(crate) fn create(index: &str) -> Result<Call<()>, Error> {
+        if index.is_empty() {
+            return Err(Error::InvalidArgument(SdkError::new(
+                "IndexController::create",
+                "index argument must not be empty.",
+            )));
+        }
+
+        let req: KuzzleRequest = KuzzleRequest::new("index", "create").set_index(index);
+        Ok(Call::new(req, |res| match &res.error() {
+            None => Ok(()),
+            Some(k_err) => Err(Error::from(k_err.clone())),
+        }))
+    }
+
+    pub(crate) fn delete(index: &str) -> Result<Call<()>, Error> {
+        if index.is_empty() {
+            return Err(Error::InvalidArgument(SdkError::new(
+                "IndexController::delete",
+                "index argument must not be empty.",
+            )));
+        }
+
+        let req: KuzzleRequest = KuzzleRequest::new("index", "delete").set_index(index);
+        Ok(Call::new(req, |res| match &res.error() {
+            None => Ok(()),
+            Some(k_err) => Err(Error::from(k_err.clone())),
+        }))
+    }
+
+    pub(crate) fn exists(index: &str) -> Result<Call<bool>, Error> {
+        if index.is_empty() {
+            return Err(Error::InvalidArgument(SdkError::new(
+                "IndexController::exists",
+                "index argument must not be empty.",
+            )));
+        }
+
+        let req: KuzzleRequest = KuzzleRequest::new("index", "exists").set_index(index);
+        Ok(Call::new(req, |res| match &res.error() {
+            None => Ok(res.result().as_bool().unwrap()),
+            Some(k_err) => Err(Error::from(k_err.clone())),
+        }))
+    }
+
+    pub(crate) fn get_auto_refresh(index: &str) -> Result<Call<bool>, Error> {
+        if index.is_empty() {
+            return Err(Error::InvalidArgument(SdkError::new(
+                "IndexController::get_auto_refresh",
+                "index argument must not be empty.",
+            )));
+        }
+
+        let req: KuzzleRequest = KuzzleRequest::new("index", "getAutoRefresh").set_index(index);
+        Ok(Call::new(req, |res| match &res.error() {
+            None => Ok(res.result().as_bool().unwrap()),
+            Some(k_err) => Err(Error::from(k_err.clone())),
+        }))
+    }
+
+    pub(crate) fn list() -> Result<Call<Vec<String>>, Error> {
+        let req: KuzzleRequest = KuzzleRequest::new("index", "list");
+        Ok(Call::new(req, |res| match &res.error() {
+            None => Ok(res
+                .result()
+                .as_object()
+                .unwrap()
+                .get("indexes")
+                .unwrap()
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|x| x.as_str().unwrap().to_string())
+                .collect::<Vec<String>>()),
+            Some(k_err) => Err(Error::from(k_err.clone())),
+        }))
+    }
+
+    pub(crate) fn mdelete(indexes: Vec<String>) -> Result<Call<MultiResult<String>>, Error> {
+        if indexes.is_empty() {
+            return Err(Error::InvalidArgument(SdkError::new(
+                "IndexController::mDelete",
+                "indexes argument must not be empty.",
+            )));
+        }
+
+        let req: KuzzleRequest = KuzzleRequest::new("index", "mDelete")
+            .add_to_body("indexes", serde_json::to_value(indexes)?);
+        Ok(Call::new(req, |res| MultiResult::ids_from_response(&res)))
+    }
+
+    pub(crate) fn refresh(index: &str) -> Result<Call<()>, Error> {
         if index.is_empty() {
-            return Err(Box::new(SdkError::new(
+            return Err(Error::InvalidArgument(SdkError::new(
+                "IndexController::refresh",
+                "index argument must not be empty.",
+            )));
+        }
+
+        let req: KuzzleRequest = KuzzleRequest::new("index", "refresh").set_index(index);
+        Ok(Call::new(req, |res| match &res.error() {
+            None => Ok(()),
+            Some(k_err) => Err(Error::from(k_err.clone())),
+        }))
+    }
+
+    pub(crate) fn refresh_internal() -> Result<Call<()>, Error> {
+        let req: KuzzleRequest = KuzzleRequest::new("index", "refreshInternal");
+        Ok(Call::new(req, |res| match &res.error() {
+            None => Ok(()),
+            Some(k_err) => Err(Error::from(k_err.clone())),
+        }))
+    }
+
+    pub(crate) fn set_auto_refresh(index: &str, auto_refresh: bool) -> Result<Call<()>, Error> {
+        if index.is_empty() {
+            return Err(Error::InvalidArgument(SdkError::new(
                 "IndexController::set_auto_refresh",
                 "index argument must not be empty.",
             )));
@@ -350,15 +386,10 @@ impl<'a> IndexController<'a> {
         let req: KuzzleRequest = KuzzleRequest::new("index", "setAutoRefresh")
             .set_index(index)
             .add_to_body("autoRefresh", serde_json::to_value(auto_refresh)?);
-        let res = self.kuzzle().query(req, QueryOptions::new())?;
-        match &res.error() {
+        Ok(Call::new(req, |res| match &res.error() {
             None => Ok(()),
-            Some(k_err) => Err(Box::new(k_err.clone())),
-        }
-    }
-
-    fn kuzzle(&'a mut self) -> &'a mut Kuzzle {
-        self.0
+            Some(k_err) => Err(Error::from(k_err.clone())),
+        }))
     }
 }
 
@@ -392,9 +423,9 @@ mod tests {
             )
             .create();
 
-        let mut k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
         k.connect().expect("Unable to connect to Kuzzle server");
-        let res = k.index().create("ferris_index");
+        let res = k.index().create("ferris_index", QueryOptions::new());
 
         assert!(res.is_ok());
     }
@@ -422,18 +453,18 @@ mod tests {
                 }"#,
             ).create();
 
-        let mut k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
         k.connect().expect("Unable to connect to Kuzzle server");
-        let res = k.index().create("ferris_index");
+        let res = k.index().create("ferris_index", QueryOptions::new());
 
         assert!(res.is_err());
     }
 
     #[test]
     fn create_fail_empty_index_name() {
-        let mut k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
         k.connect().expect("Unable to connect to Kuzzle server");
-        let res = k.index().create("");
+        let res = k.index().create("", QueryOptions::new());
 
         assert!(res.is_err());
     }
@@ -458,9 +489,9 @@ mod tests {
             )
             .create();
 
-        let mut k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
         k.connect().expect("Unable to connect to Kuzzle server");
-        let res = k.index().delete("ferris_index");
+        let res = k.index().delete("ferris_index", QueryOptions::new());
 
         assert!(res.is_ok());
     }
@@ -486,18 +517,18 @@ mod tests {
                 }"#,
             ).create();
 
-        let mut k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
         k.connect().expect("Unable to connect to Kuzzle server");
-        let res = k.index().delete("ferris_index");
+        let res = k.index().delete("ferris_index", QueryOptions::new());
 
         assert!(res.is_err());
     }
 
     #[test]
     fn delete_fail_empty_index_name() {
-        let mut k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
         k.connect().expect("Unable to connect to Kuzzle server");
-        let res = k.index().delete("");
+        let res = k.index().delete("", QueryOptions::new());
 
         assert!(res.is_err());
     }
@@ -520,12 +551,12 @@ mod tests {
             )
             .create();
 
-        let mut k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
         k.connect().expect("Unable to connect to Kuzzle server");
-        let res = k.index().exists("ferris_index");
+        let res = k.index().exists("ferris_index", QueryOptions::new());
 
         assert!(res.is_ok());
-        assert_eq!(res.unwrap(), true);
+        assert!(res.unwrap());
     }
 
     #[test]
@@ -546,12 +577,12 @@ mod tests {
             )
             .create();
 
-        let mut k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
         k.connect().expect("Unable to connect to Kuzzle server");
-        let res = k.index().exists("ferris_index");
+        let res = k.index().exists("ferris_index", QueryOptions::new());
 
         assert!(res.is_ok());
-        assert_eq!(res.unwrap(), false);
+        assert!(!res.unwrap());
     }
 
     #[test]
@@ -575,18 +606,18 @@ mod tests {
                 }"#,
             ).create();
 
-        let mut k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
         k.connect().expect("Unable to connect to Kuzzle server");
-        let res = k.index().exists("ferris_index");
+        let res = k.index().exists("ferris_index", QueryOptions::new());
 
         assert!(res.is_err());
     }
 
     #[test]
     fn exists_fail_empty_index_name() {
-        let mut k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
         k.connect().expect("Unable to connect to Kuzzle server");
-        let res = k.index().exists("");
+        let res = k.index().exists("", QueryOptions::new());
 
         assert!(res.is_err());
     }
@@ -609,12 +640,14 @@ mod tests {
             )
             .create();
 
-        let mut k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
         k.connect().expect("Unable to connect to Kuzzle server");
-        let res = k.index().get_auto_refresh("ferris_index");
+        let res = k
+            .index()
+            .get_auto_refresh("ferris_index", QueryOptions::new());
 
         assert!(res.is_ok());
-        assert_eq!(res.unwrap(), true);
+        assert!(res.unwrap());
     }
 
     #[test]
@@ -635,12 +668,14 @@ mod tests {
             )
             .create();
 
-        let mut k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
         k.connect().expect("Unable to connect to Kuzzle server");
-        let res = k.index().get_auto_refresh("ferris_index");
+        let res = k
+            .index()
+            .get_auto_refresh("ferris_index", QueryOptions::new());
 
         assert!(res.is_ok());
-        assert_eq!(res.unwrap(), false);
+        assert!(!res.unwrap());
     }
 
     #[test]
@@ -664,18 +699,20 @@ mod tests {
                 }"#,
             ).create();
 
-        let mut k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
         k.connect().expect("Unable to connect to Kuzzle server");
-        let res = k.index().get_auto_refresh("ferris_index");
+        let res = k
+            .index()
+            .get_auto_refresh("ferris_index", QueryOptions::new());
 
         assert!(res.is_err());
     }
 
     #[test]
     fn get_auto_refresh_fail_empty_index_name() {
-        let mut k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
         k.connect().expect("Unable to connect to Kuzzle server");
-        let res = k.index().get_auto_refresh("");
+        let res = k.index().get_auto_refresh("", QueryOptions::new());
 
         assert!(res.is_err());
     }
@@ -705,9 +742,9 @@ mod tests {
             )
             .create();
 
-        let mut k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
         k.connect().expect("Unable to connect to Kuzzle server");
-        let res = k.index().list();
+        let res = k.index().list(QueryOptions::new());
 
         assert!(res.is_ok());
         assert_eq!(res.unwrap().len(), 2);
@@ -736,9 +773,9 @@ mod tests {
                 }"#,
             ).create();
 
-        let mut k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
         k.connect().expect("Unable to connect to Kuzzle server");
-        let res = k.index().list();
+        let res = k.index().list(QueryOptions::new());
 
         assert!(res.is_err());
     }
@@ -768,15 +805,20 @@ mod tests {
             )
             .create();
 
-        let mut k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
         k.connect().expect("Unable to connect to Kuzzle server");
-        let res = k.index().mdelete(vec![
-            "ferris_the_crab".to_string(),
-            "ferris_the_happy_crab".to_string(),
-        ]);
+        let res = k.index().mdelete(
+            vec![
+                "ferris_the_crab".to_string(),
+                "ferris_the_happy_crab".to_string(),
+            ],
+            QueryOptions::new(),
+        );
 
         assert!(res.is_ok());
-        assert_eq!(res.unwrap().len(), 2);
+        let result = res.unwrap();
+        assert_eq!(result.successes().len(), 2);
+        assert!(!result.has_errors());
     }
 
     #[test]
@@ -802,9 +844,11 @@ mod tests {
                 }"#,
             ).create();
 
-        let mut k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
         k.connect().expect("Unable to connect to Kuzzle server");
-        let res = k.index().mdelete(vec!["ferris_lair".to_string()]);
+        let res = k
+            .index()
+            .mdelete(vec!["ferris_lair".to_string()], QueryOptions::new());
 
         assert!(res.is_err());
     }
@@ -831,19 +875,21 @@ mod tests {
             )
             .create();
 
-        let mut k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
         k.connect().expect("Unable to connect to Kuzzle server");
-        let res = k.index().mdelete(vec!["ferris_not_found".to_string()]);
+        let res = k
+            .index()
+            .mdelete(vec!["ferris_not_found".to_string()], QueryOptions::new());
 
         assert!(res.is_ok());
-        assert_eq!(res.unwrap().len(), 0);
+        assert_eq!(res.unwrap().successes().len(), 0);
     }
 
     #[test]
     fn mdelete_fail_empty_indexes_array() {
-        let mut k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
         k.connect().expect("Unable to connect to Kuzzle server");
-        let res = k.index().mdelete(vec![]);
+        let res = k.index().mdelete(vec![], QueryOptions::new());
 
         assert!(res.is_err());
     }
@@ -872,9 +918,9 @@ mod tests {
             )
             .create();
 
-        let mut k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
         k.connect().expect("Unable to connect to Kuzzle server");
-        let res = k.index().refresh("ferris_index");
+        let res = k.index().refresh("ferris_index", QueryOptions::new());
 
         assert!(res.is_ok());
     }
@@ -900,18 +946,18 @@ mod tests {
                 }"#,
             ).create();
 
-        let mut k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
         k.connect().expect("Unable to connect to Kuzzle server");
-        let res = k.index().refresh("ferris_index");
+        let res = k.index().refresh("ferris_index", QueryOptions::new());
 
         assert!(res.is_err());
     }
 
     #[test]
     fn refresh_fail_empty_index_name() {
-        let mut k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
         k.connect().expect("Unable to connect to Kuzzle server");
-        let res = k.index().refresh("");
+        let res = k.index().refresh("", QueryOptions::new());
 
         assert!(res.is_err());
     }
@@ -936,9 +982,9 @@ mod tests {
             )
             .create();
 
-        let mut k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
         k.connect().expect("Unable to connect to Kuzzle server");
-        let res = k.index().refresh_internal();
+        let res = k.index().refresh_internal(QueryOptions::new());
 
         assert!(res.is_ok());
     }
@@ -964,9 +1010,9 @@ mod tests {
                 }"#,
             ).create();
 
-        let mut k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
         k.connect().expect("Unable to connect to Kuzzle server");
-        let res = k.index().refresh_internal();
+        let res = k.index().refresh_internal(QueryOptions::new());
 
         assert!(res.is_err());
     }
@@ -991,9 +1037,11 @@ mod tests {
             )
             .create();
 
-        let mut k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
         k.connect().expect("Unable to connect to Kuzzle server");
-        let res = k.index().set_auto_refresh("ferris_index", true);
+        let res = k
+            .index()
+            .set_auto_refresh("ferris_index", true, QueryOptions::new());
 
         assert!(res.is_ok());
     }
@@ -1019,18 +1067,20 @@ mod tests {
                 }"#,
             ).create();
 
-        let mut k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
         k.connect().expect("Unable to connect to Kuzzle server");
-        let res = k.index().set_auto_refresh("ferris_index", true);
+        let res = k
+            .index()
+            .set_auto_refresh("ferris_index", true, QueryOptions::new());
 
         assert!(res.is_err());
     }
 
     #[test]
     fn set_auto_refresh_fail_empty_index_name() {
-        let mut k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
         k.connect().expect("Unable to connect to Kuzzle server");
-        let res = k.index().set_auto_refresh("", true);
+        let res = k.index().set_auto_refresh("", true, QueryOptions::new());
 
         assert!(res.is_err());
     }
@@ -1,9 +1,9 @@
 use crate::kuzzle::Kuzzle;
 
-pub struct MemoryStorageController<'a>(pub &'a mut Kuzzle);
+pub struct MemoryStorageController<'a>(pub &'a Kuzzle);
 
 impl<'a> MemoryStorageController<'a> {
-    fn _kuzzle(&'a mut self) -> &'a mut Kuzzle {
+    fn _kuzzle(&self) -> &Kuzzle {
         self.0
     }
 }
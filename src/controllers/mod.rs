@@ -1,12 +1,16 @@
-mod auth;
-mod bulk;
-mod collection;
-mod document;
-mod index;
+use crate::types::{Error, KuzzleRequest, KuzzleResponse, SdkError};
+use serde_json::Value;
+use std::time::Duration;
+
+pub(crate) mod auth;
+pub(crate) mod bulk;
+pub(crate) mod collection;
+pub(crate) mod document;
+pub(crate) mod index;
 mod memory_storage;
 mod realtime;
-mod security;
-mod server;
+pub(crate) mod security;
+pub(crate) mod server;
 
 pub use self::auth::AuthController;
 pub use self::bulk::BulkController;
@@ -17,3 +21,55 @@ pub use self::memory_storage::MemoryStorageController;
 pub use self::realtime::RealtimeController;
 pub use self::security::SecurityController;
 pub use self::server::ServerController;
+
+/// A controller action, ready to be sent: the request to Kuzzle and how to
+/// read its response. Shared by the blocking and the async controllers.
+///
+/// Each controller module builds its calls in an `actions` submodule, one
+/// function per action: the blocking controllers send them with `Kuzzle::call`,
+/// and the async ones with `nonblocking::Kuzzle::call`, so that requests
+/// and responses are only described once.
+pub(crate) struct Call<T> {
+    pub(crate) request: KuzzleRequest,
+    pub(crate) parse: Box<dyn FnOnce(KuzzleResponse) -> Result<T, Error> + Send>,
+    login_required_by: Option<&'static str>,
+}
+
+impl<T> Call<T> {
+    pub(crate) fn new<F>(request: KuzzleRequest, parse: F) -> Call<T>
+    where
+        F: FnOnce(KuzzleResponse) -> Result<T, Error> + Send + 'static,
+    {
+        Call {
+            request,
+            parse: Box::new(parse),
+            login_required_by: None,
+        }
+    }
+
+    /// Makes the call fail before reaching Kuzzle if no JWT is set.
+    pub(crate) fn require_login(mut self, cause: &'static str) -> Call<T> {
+        self.login_required_by = Some(cause);
+        self
+    }
+
+    pub(crate) fn check_login(&self, jwt: &str) -> Result<(), Error> {
+        match self.login_required_by {
+            Some(cause) if jwt.is_empty() => Err(Error::InvalidArgument(SdkError::new(
+                cause,
+                "You need to be logged in to use this function.",
+            ))),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Sets the lifespan of the token created by the request, in milliseconds.
+pub(crate) fn with_expires_in(req: KuzzleRequest, expires_in: Option<Duration>) -> KuzzleRequest {
+    match expires_in {
+        Some(expires_in) => {
+            req.add_to_query_strings("expiresIn", Value::from(expires_in.as_millis() as u64))
+        }
+        None => req,
+    }
+}
@@ -1,15 +1,16 @@
 use crate::kuzzle::Kuzzle;
-use crate::types::{KuzzleRequest, QueryOptions};
+use crate::types::{Error, KuzzleRequest, QueryOptions};
 
-pub struct RealtimeController<'a>(pub &'a mut Kuzzle);
+pub struct RealtimeController<'a>(pub &'a Kuzzle);
 
 impl<'a> RealtimeController<'a> {
-    pub fn subscribe(&'a mut self, options: QueryOptions) {
+    pub fn subscribe(&self, options: QueryOptions) -> Result<(), Error> {
         let req: KuzzleRequest = KuzzleRequest::new("realtime", "subscribe");
-        self.kuzzle().query(req, options).is_ok();
+        self.kuzzle().query(req, options)?;
+        Ok(())
     }
 
-    fn kuzzle(&'a mut self) -> &'a mut Kuzzle {
+    fn kuzzle(&self) -> &Kuzzle {
         self.0
     }
 }
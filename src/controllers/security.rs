@@ -1,15 +1,539 @@
 use crate::kuzzle::Kuzzle;
-use crate::types::{KuzzleRequest, QueryOptions};
+use crate::types::{
+    ApiKey, ApiKeySearchResult, Error, KuzzleRequest, MultiResult, QueryOptions, SdkError,
+};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::time::Duration;
 
-pub struct SecurityController<'a>(pub &'a mut Kuzzle);
+pub struct SecurityController<'a>(pub &'a Kuzzle);
 
 impl<'a> SecurityController<'a> {
-    pub fn create_credentials(&'a mut self, options: QueryOptions) {
+    /// Creates an API key for the given user.
+    /// `expires_in` sets the lifespan of the key, which never expires if `None`.
+    /// The key token is only available from the returned `ApiKey`:
+    /// it can't be retrieved afterwards.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::{KuzzleOptions, QueryOptions};
+    /// use std::time::Duration;
+    ///
+    /// let kuzzle = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+    /// kuzzle.connect().unwrap();
+    /// kuzzle
+    ///     .auth()
+    ///     .login("local", "admin", "password", QueryOptions::new())
+    ///     .unwrap();
+    ///
+    /// let api_key = kuzzle
+    ///     .security()
+    ///     .create_api_key(
+    ///         "ci-runner",
+    ///         "Integration tests",
+    ///         Some(Duration::from_secs(30 * 24 * 3600)),
+    ///         QueryOptions::new(),
+    ///     )
+    ///     .unwrap();
+    /// println!("{}", api_key.token());
+    /// ```
+    pub fn create_api_key(
+        &self,
+        user_id: &str,
+        description: &str,
+        expires_in: Option<Duration>,
+        options: QueryOptions,
+    ) -> Result<ApiKey, Error> {
+        self.kuzzle().call(
+            actions::create_api_key(user_id, description, expires_in)?,
+            options,
+        )
+    }
+
+    pub fn create_credentials(&self, options: QueryOptions) -> Result<(), Error> {
         let req: KuzzleRequest = KuzzleRequest::new("security", "createCredentials");
-        self.kuzzle().query(req, options).is_ok();
+        self.kuzzle().query(req, options)?;
+        Ok(())
+    }
+
+    /// Deletes an API key of the given user, revoking its token.
+    pub fn delete_api_key(
+        &self,
+        user_id: &str,
+        id: &str,
+        options: QueryOptions,
+    ) -> Result<(), Error> {
+        self.kuzzle()
+            .call(actions::delete_api_key(user_id, id)?, options)
+    }
+
+    /// Deletes multiple profiles.
+    /// Profiles that could not be deleted are listed in `MultiResult::errors`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::{KuzzleOptions, QueryOptions};
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let res = kuzzle
+    ///     .security()
+    ///     .mdelete_profiles(
+    ///         vec!["ferris".to_string(), "corro".to_string()],
+    ///         QueryOptions::new(),
+    ///     );
+    ///
+    /// ```
+    ///
+    pub fn mdelete_profiles(
+        &self,
+        ids: Vec<String>,
+        options: QueryOptions,
+    ) -> Result<MultiResult<String>, Error> {
+        self.kuzzle().call(actions::mdelete_profiles(ids)?, options)
+    }
+
+    /// Deletes multiple roles.
+    /// Roles that could not be deleted are listed in `MultiResult::errors`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::{KuzzleOptions, QueryOptions};
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let res = kuzzle
+    ///     .security()
+    ///     .mdelete_roles(
+    ///         vec!["ferris".to_string(), "corro".to_string()],
+    ///         QueryOptions::new(),
+    ///     );
+    ///
+    /// ```
+    ///
+    pub fn mdelete_roles(
+        &self,
+        ids: Vec<String>,
+        options: QueryOptions,
+    ) -> Result<MultiResult<String>, Error> {
+        self.kuzzle().call(actions::mdelete_roles(ids)?, options)
     }
 
-    fn kuzzle(&'a mut self) -> &'a mut Kuzzle {
+    /// Deletes multiple users.
+    /// Users that could not be deleted are listed in `MultiResult::errors`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::{KuzzleOptions, QueryOptions};
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let res = kuzzle
+    ///     .security()
+    ///     .mdelete_users(
+    ///         vec!["ferris".to_string(), "corro".to_string()],
+    ///         QueryOptions::new(),
+    ///     );
+    ///
+    /// ```
+    ///
+    pub fn mdelete_users(
+        &self,
+        ids: Vec<String>,
+        options: QueryOptions,
+    ) -> Result<MultiResult<String>, Error> {
+        self.kuzzle().call(actions::mdelete_users(ids)?, options)
+    }
+
+    /// Searches the API keys of the given user.
+    /// Results are paginated with `QueryOptions::set_from` and `QueryOptions::set_size`.
+    pub fn search_api_keys(
+        &self,
+        user_id: &str,
+        query: &HashMap<String, Value>,
+        options: QueryOptions,
+    ) -> Result<ApiKeySearchResult, Error> {
+        self.kuzzle()
+            .call(actions::search_api_keys(user_id, query)?, options)
+    }
+
+    fn kuzzle(&self) -> &Kuzzle {
         self.0
     }
 }
+
+pub(crate) mod actions {
+    use super::*;
+    use crate::controllers::{with_expires_in, Call};
+
+    pub(crate) fn create_api_key(
+        user_id: &str,
+        description: &str,
+        expires_in: Option<Duration>,
+    ) -> Result<Call<ApiKey>, Error> {
+        if user_id.is_empty() || description.is_empty() {
+            return Err(Error::InvalidArgument(SdkError::new(
+                "SecurityController::create_api_key",
+                "user_id and description arguments must not be empty.",
+            )));
+        }
+
+        let req: KuzzleRequest = KuzzleRequest::new("security", "createApiKey")
+            .add_to_query_strings("userId", Value::from(user_id))
+            .add_to_body("description", Value::from(description));
+        Ok(Call::new(
+            with_expires_in(req, expires_in),
+            |res| match &res.error() {
+                None => Ok(serde_json::from_value(res.result().clone())?),
+                Some(k_err) => Err(Error::from(k_err.clone())),
+            },
+        ))
+    }
+
+    pub(crate) fn delete_api_key(user_id: &str, id: &str) -> Result<Call<()>, Error> {
+        if user_id.is_empty() || id.is_empty() {
+            return Err(Error::InvalidArgument(SdkError::new(
+                "SecurityController::delete_api_key",
+                "user_id and id arguments must not be empty.",
+            )));
+        }
+
+        let req: KuzzleRequest = KuzzleRequest::new("security", "deleteApiKey")
+            .add_to_query_strings("userId", Value::from(user_id))
+            .set_id(id);
+        Ok(Call::new(req, |res| match &res.error() {
+            None => Ok(()),
+            Some(k_err) => Err(Error::from(k_err.clone())),
+        }))
+    }
+
+    pub(crate) fn mdelete_profiles(ids: Vec<String>) -> Result<Call<MultiResult<String>>, Error> {
+        mdelete(
+            "mDeleteProfiles",
+            "SecurityController::mdelete_profiles",
+            ids,
+        )
+    }
+
+    pub(crate) fn mdelete_roles(ids: Vec<String>) -> Result<Call<MultiResult<String>>, Error> {
+        mdelete("mDeleteRoles", "SecurityController::mdelete_roles", ids)
+    }
+
+    pub(crate) fn mdelete_users(ids: Vec<String>) -> Result<Call<MultiResult<String>>, Error> {
+        mdelete("mDeleteUsers", "SecurityController::mdelete_users", ids)
+    }
+
+    pub(crate) fn search_api_keys(
+        user_id: &str,
+        query: &HashMap<String, Value>,
+    ) -> Result<Call<ApiKeySearchResult>, Error> {
+        if user_id.is_empty() {
+            return Err(Error::InvalidArgument(SdkError::new(
+                "SecurityController::search_api_keys",
+                "user_id argument must not be empty.",
+            )));
+        }
+
+        let req: KuzzleRequest = KuzzleRequest::new("security", "searchApiKeys")
+            .add_to_query_strings("userId", Value::from(user_id))
+            .set_body(query.clone());
+        Ok(Call::new(req, |res| match &res.error() {
+            None => Ok(serde_json::from_value(res.result().clone())?),
+            Some(k_err) => Err(Error::from(k_err.clone())),
+        }))
+    }
+
+    fn mdelete(
+        action: &str,
+        cause: &str,
+        ids: Vec<String>,
+    ) -> Result<Call<MultiResult<String>>, Error> {
+        if ids.is_empty() {
+            return Err(Error::InvalidArgument(SdkError::new(
+                cause,
+                "ids argument must not be empty.",
+            )));
+        }
+
+        let req: KuzzleRequest =
+            KuzzleRequest::new("security", action).add_to_body("ids", serde_json::to_value(ids)?);
+        Ok(Call::new(req, |res| MultiResult::ids_from_response(&res)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocols::Http;
+    use crate::types::KuzzleOptions;
+    use mockito;
+
+    #[test]
+    fn create_api_key_ok() {
+        let _m = mockito::mock("POST", "/users/ci-runner/api-keys/_create")
+            .match_header("authorization", "Bearer anApiKeyToken")
+            .match_body(r#"{"description":"Integration tests"}"#)
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "security",
+                    "action": "createApiKey",
+                    "volatile": null,
+                    "result": {
+                      "_id": "ci-key",
+                      "_source": {
+                        "userId": "ci-runner",
+                        "description": "Integration tests",
+                        "expiresAt": -1,
+                        "ttl": -1,
+                        "token": "aCiApiKeyToken"
+                      }
+                    }
+                }"#,
+            )
+            .create();
+
+        let k = Kuzzle::with_api_key(
+            Http::new(KuzzleOptions::new("localhost", 7512)),
+            "anApiKeyToken",
+        );
+        k.connect().expect("Unable to connect to Kuzzle server");
+        let res = k.security().create_api_key(
+            "ci-runner",
+            "Integration tests",
+            None,
+            QueryOptions::new(),
+        );
+
+        let api_key = res.unwrap();
+        assert_eq!(api_key.user_id(), "ci-runner");
+        assert_eq!(api_key.token(), "aCiApiKeyToken");
+    }
+
+    #[test]
+    fn create_api_key_fail_empty_args() {
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        k.connect().expect("Unable to connect to Kuzzle server");
+        let res = k
+            .security()
+            .create_api_key("", "Integration tests", None, QueryOptions::new());
+
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn delete_api_key_ok() {
+        let _m = mockito::mock("DELETE", "/users/ci-runner/api-keys/ci-key")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "security",
+                    "action": "deleteApiKey",
+                    "volatile": null,
+                    "result": {
+                      "_id": "ci-key"
+                    }
+                }"#,
+            )
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        k.connect().expect("Unable to connect to Kuzzle server");
+        let res = k
+            .security()
+            .delete_api_key("ci-runner", "ci-key", QueryOptions::new());
+
+        assert!(res.is_ok());
+    }
+
+    #[test]
+    fn search_api_keys_ok() {
+        let _m = mockito::mock("POST", "/users/ci-runner/api-keys/_search")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "security",
+                    "action": "searchApiKeys",
+                    "volatile": null,
+                    "result": {
+                      "total": 2,
+                      "hits": [
+                        {
+                          "_id": "ci-key",
+                          "_source": { "userId": "ci-runner", "description": "Integration tests" }
+                        },
+                        {
+                          "_id": "cron-key",
+                          "_source": { "userId": "ci-runner", "description": "Cron jobs" }
+                        }
+                      ]
+                    }
+                }"#,
+            )
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        k.connect().expect("Unable to connect to Kuzzle server");
+        let res = k
+            .security()
+            .search_api_keys("ci-runner", &HashMap::new(), QueryOptions::new());
+
+        let result = res.unwrap();
+        assert_eq!(result.total(), 2);
+        assert_eq!(result.hits()[1].id(), "cron-key");
+    }
+
+    #[test]
+    fn mdelete_users_ok() {
+        let _m = mockito::mock("POST", "/users/_mDelete")
+            .match_body(r#"{"ids":["ferris","corro"]}"#)
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "security",
+                    "action": "mDeleteUsers",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": ["ferris", "corro"]
+                }"#,
+            )
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        k.connect().expect("Unable to connect to Kuzzle server");
+        let res = k.security().mdelete_users(
+            vec!["ferris".to_string(), "corro".to_string()],
+            QueryOptions::new(),
+        );
+
+        assert!(res.is_ok());
+        let result = res.unwrap();
+        assert_eq!(result.successes().len(), 2);
+        assert!(!result.has_errors());
+    }
+
+    #[test]
+    fn mdelete_roles_ok_partial_error() {
+        let _m = mockito::mock("POST", "/roles/_mDelete")
+            .with_status(206)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 206,
+                    "error": {
+                        "message": "Some roles could not be deleted",
+                        "status": 206,
+                        "errors": ["corro"],
+                        "count": 1
+                    },
+                    "controller": "security",
+                    "action": "mDeleteRoles",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": ["ferris"]
+                }"#,
+            )
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        k.connect().expect("Unable to connect to Kuzzle server");
+        let res = k.security().mdelete_roles(
+            vec!["ferris".to_string(), "corro".to_string()],
+            QueryOptions::new(),
+        );
+
+        assert!(res.is_ok());
+        let result = res.unwrap();
+        assert_eq!(result.successes(), &vec!["ferris".to_string()]);
+        assert_eq!(result.errors()[0].id(), "corro".to_string());
+        assert_eq!(
+            result.errors()[0].reason(),
+            "Some roles could not be deleted"
+        );
+    }
+
+    #[test]
+    fn mdelete_profiles_fail_error() {
+        let _m = mockito::mock("POST", "/profiles/_mDelete")
+            .with_status(403)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 403,
+                    "error": {
+                        "message": "Forbidden action [null/null/security/mDeleteProfiles] for user -1",
+                        "status": 403,
+                        "stack": "ForbiddenError: Forbidden action [null/null/security/mDeleteProfiles] for user -1\n"
+                    },
+                    "controller": "security",
+                    "action": "mDeleteProfiles",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": null
+                }"#,
+            )
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        k.connect().expect("Unable to connect to Kuzzle server");
+        let res = k
+            .security()
+            .mdelete_profiles(vec!["ferris".to_string()], QueryOptions::new());
+
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn mdelete_users_fail_empty_args() {
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        k.connect().expect("Unable to connect to Kuzzle server");
+        let res = k.security().mdelete_users(vec![], QueryOptions::new());
+
+        assert!(res.is_err());
+    }
+}
@@ -1,9 +1,9 @@
 use crate::kuzzle::Kuzzle;
-use crate::types::{KuzzleRequest, QueryOptions, SdkError};
-use serde_json::{Map, Value};
-use std::error::Error;
+use crate::types::{
+    Error, HealthCheck, KuzzleRequest, QueryOptions, SdkError, ServerConfig, ServerInfo, StatsFrame,
+};
 
-pub struct ServerController<'a>(pub &'a mut Kuzzle);
+pub struct ServerController<'a>(pub &'a Kuzzle);
 
 impl<'a> ServerController<'a> {
     /// Checks that an administrator account exists.
@@ -13,32 +13,20 @@ impl<'a> ServerController<'a> {
     /// ```
     /// use kuzzle_sdk::kuzzle::Kuzzle;
     /// use kuzzle_sdk::protocols::Http;
-    /// use kuzzle_sdk::types::KuzzleOptions;
+    /// use kuzzle_sdk::types::{KuzzleOptions, QueryOptions};
     ///
-    /// let mut kuzzle = Kuzzle::new(
+    /// let kuzzle = Kuzzle::new(
     ///     Http::new(
     ///         KuzzleOptions::new("localhost", 7512)
     ///     )
     /// );
     ///
-    /// let res = kuzzle.server().admin_exists();
+    /// let res = kuzzle.server().admin_exists(QueryOptions::new());
     ///
     /// ```
     ///
-    pub fn admin_exists(&'a mut self) -> Result<bool, Box<Error>> {
-        let req: KuzzleRequest = KuzzleRequest::new("server", "adminExists");
-        let res = self.kuzzle().query(req, QueryOptions::new())?;
-        match &res.error() {
-            None => Ok(res
-                .result()
-                .as_object()
-                .unwrap()
-                .get("exists")
-                .unwrap()
-                .as_bool()
-                .unwrap()),
-            Some(k_err) => Err(Box::new(k_err.clone())),
-        }
+    pub fn admin_exists(&self, options: QueryOptions) -> Result<bool, Error> {
+        self.kuzzle().call(actions::admin_exists()?, options)
     }
 
     /// Gets all stored internal statistic snapshots.
@@ -48,25 +36,20 @@ impl<'a> ServerController<'a> {
     /// ```
     /// use kuzzle_sdk::kuzzle::Kuzzle;
     /// use kuzzle_sdk::protocols::Http;
-    /// use kuzzle_sdk::types::KuzzleOptions;
+    /// use kuzzle_sdk::types::{KuzzleOptions, QueryOptions};
     ///
-    /// let mut kuzzle = Kuzzle::new(
+    /// let kuzzle = Kuzzle::new(
     ///     Http::new(
     ///         KuzzleOptions::new("localhost", 7512)
     ///     )
     /// );
     ///
-    /// let res = kuzzle.server().get_all_stats();
+    /// let res = kuzzle.server().get_all_stats(QueryOptions::new());
     ///
     /// ```
     ///
-    pub fn get_all_stats(&'a mut self) -> Result<Map<String, Value>, Box<Error>> {
-        let req: KuzzleRequest = KuzzleRequest::new("server", "getAllStats");
-        let res = self.kuzzle().query(req, QueryOptions::new())?;
-        match &res.error() {
-            None => Ok(res.result().as_object().unwrap().clone()),
-            Some(k_err) => Err(Box::new(k_err.clone())),
-        }
+    pub fn get_all_stats(&self, options: QueryOptions) -> Result<Vec<StatsFrame>, Error> {
+        self.kuzzle().call(actions::get_all_stats()?, options)
     }
 
     /// Returns the current Kuzzle configuration.
@@ -79,25 +62,20 @@ impl<'a> ServerController<'a> {
     /// ```
     /// use kuzzle_sdk::kuzzle::Kuzzle;
     /// use kuzzle_sdk::protocols::Http;
-    /// use kuzzle_sdk::types::KuzzleOptions;
+    /// use kuzzle_sdk::types::{KuzzleOptions, QueryOptions};
     ///
-    /// let mut kuzzle = Kuzzle::new(
+    /// let kuzzle = Kuzzle::new(
     ///     Http::new(
     ///         KuzzleOptions::new("localhost", 7512)
     ///     )
     /// );
     ///
-    /// let res = kuzzle.server().get_config();
+    /// let res = kuzzle.server().get_config(QueryOptions::new());
     ///
     /// ```
     ///
-    pub fn get_config(&'a mut self) -> Result<Map<String, Value>, Box<Error>> {
-        let req: KuzzleRequest = KuzzleRequest::new("server", "getConfig");
-        let res = self.kuzzle().query(req, QueryOptions::new())?;
-        match &res.error() {
-            None => Ok(res.result().as_object().unwrap().clone()),
-            Some(k_err) => Err(Box::new(k_err.clone())),
-        }
+    pub fn get_config(&self, options: QueryOptions) -> Result<ServerConfig, Error> {
+        self.kuzzle().call(actions::get_config()?, options)
     }
 
     /// Returns the most recent statistics snapshot.
@@ -107,25 +85,20 @@ impl<'a> ServerController<'a> {
     /// ```
     /// use kuzzle_sdk::kuzzle::Kuzzle;
     /// use kuzzle_sdk::protocols::Http;
-    /// use kuzzle_sdk::types::KuzzleOptions;
+    /// use kuzzle_sdk::types::{KuzzleOptions, QueryOptions};
     ///
-    /// let mut kuzzle = Kuzzle::new(
+    /// let kuzzle = Kuzzle::new(
     ///     Http::new(
     ///         KuzzleOptions::new("localhost", 7512)
     ///     )
     /// );
     ///
-    /// let res = kuzzle.server().get_last_stats();
+    /// let res = kuzzle.server().get_last_stats(QueryOptions::new());
     ///
     /// ```
     ///
-    pub fn get_last_stats(&'a mut self) -> Result<Map<String, Value>, Box<Error>> {
-        let req: KuzzleRequest = KuzzleRequest::new("server", "getLastStats");
-        let res = self.kuzzle().query(req, QueryOptions::new())?;
-        match &res.error() {
-            None => Ok(res.result().as_object().unwrap().clone()),
-            Some(k_err) => Err(Box::new(k_err.clone())),
-        }
+    pub fn get_last_stats(&self, options: QueryOptions) -> Result<StatsFrame, Error> {
+        self.kuzzle().call(actions::get_last_stats()?, options)
     }
 
     /// Returns statistics snapshots within a provided Epoch millis timestamp range.
@@ -135,34 +108,51 @@ impl<'a> ServerController<'a> {
     /// ```
     /// use kuzzle_sdk::kuzzle::Kuzzle;
     /// use kuzzle_sdk::protocols::Http;
-    /// use kuzzle_sdk::types::KuzzleOptions;
+    /// use kuzzle_sdk::types::{KuzzleOptions, QueryOptions};
     ///
-    /// let mut kuzzle = Kuzzle::new(
+    /// let kuzzle = Kuzzle::new(
     ///     Http::new(
     ///         KuzzleOptions::new("localhost", 7512)
     ///     )
     /// );
     ///
-    /// let res = kuzzle.server().get_stats(1550444792010, 1550444805453);
+    /// let res = kuzzle.server().get_stats(1550444792010, 1550444805453, QueryOptions::new());
     ///
     /// ```
     ///
-    pub fn get_stats(&'a mut self, from: i64, to: i64) -> Result<Map<String, Value>, Box<Error>> {
-        if from.to_string().len() != 13 || to.to_string().len() != 13 {
-            return Err(Box::new(SdkError::new(
-                "ServerController::get_stats",
-                "`form` and `to` arguments need to be millis Epoch timestamps (13 digits).",
-            )));
-        }
+    pub fn get_stats(
+        &self,
+        from: i64,
+        to: i64,
+        options: QueryOptions,
+    ) -> Result<Vec<StatsFrame>, Error> {
+        self.kuzzle().call(actions::get_stats(from, to)?, options)
+    }
 
-        let req: KuzzleRequest = KuzzleRequest::new("server", "getStats")
-            .add_to_query_strings("startTime", serde_json::to_value(from)?)
-            .add_to_query_strings("stopTime", serde_json::to_value(to)?);
-        let res = self.kuzzle().query(req, QueryOptions::new())?;
-        match &res.error() {
-            None => Ok(res.result().as_object().unwrap().clone()),
-            Some(k_err) => Err(Box::new(k_err.clone())),
-        }
+    /// Returns the status of Kuzzle and of its external services (Elasticsearch, Redis...).
+    ///
+    /// A Kuzzle node answers with a `red` status, instead of an error, when one of
+    /// its services is down.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::{KuzzleOptions, QueryOptions};
+    ///
+    /// let kuzzle = Kuzzle::new(
+    ///     Http::new(
+    ///         KuzzleOptions::new("localhost", 7512)
+    ///     )
+    /// );
+    ///
+    /// let res = kuzzle.server().health_check(QueryOptions::new());
+    ///
+    /// ```
+    ///
+    pub fn health_check(&self, options: QueryOptions) -> Result<HealthCheck, Error> {
+        self.kuzzle().call(actions::health_check()?, options)
     }
 
     /// Returns information about Kuzzle: available API (base + extended), plugins,
@@ -173,25 +163,20 @@ impl<'a> ServerController<'a> {
     /// ```
     /// use kuzzle_sdk::kuzzle::Kuzzle;
     /// use kuzzle_sdk::protocols::Http;
-    /// use kuzzle_sdk::types::KuzzleOptions;
+    /// use kuzzle_sdk::types::{KuzzleOptions, QueryOptions};
     ///
-    /// let mut kuzzle = Kuzzle::new(
+    /// let kuzzle = Kuzzle::new(
     ///     Http::new(
     ///         KuzzleOptions::new("localhost", 7512)
     ///     )
     /// );
     ///
-    /// let res = kuzzle.server().info();
+    /// let res = kuzzle.server().info(QueryOptions::new());
     ///
     /// ```
     ///
-    pub fn info(&'a mut self) -> Result<Map<String, Value>, Box<Error>> {
-        let req: KuzzleRequest = KuzzleRequest::new("server", "info");
-        let res = self.kuzzle().query(req, QueryOptions::new())?;
-        match &res.error() {
-            None => Ok(res.result().as_object().unwrap().clone()),
-            Some(k_err) => Err(Box::new(k_err.clone())),
-        }
+    pub fn info(&self, options: QueryOptions) -> Result<ServerInfo, Error> {
+        self.kuzzle().call(actions::info()?, options)
     }
 
     /// Returns the current server timestamp, in Epoch-millis format.
@@ -201,37 +186,139 @@ impl<'a> ServerController<'a> {
     /// ```
     /// use kuzzle_sdk::kuzzle::Kuzzle;
     /// use kuzzle_sdk::protocols::Http;
-    /// use kuzzle_sdk::types::KuzzleOptions;
+    /// use kuzzle_sdk::types::{KuzzleOptions, QueryOptions};
     ///
-    /// let mut kuzzle = Kuzzle::new(
+    /// let kuzzle = Kuzzle::new(
     ///     Http::new(
     ///         KuzzleOptions::new("localhost", 7512)
     ///     )
     /// );
     ///
-    /// let res = kuzzle.server().info();
+    /// let res = kuzzle.server().info(QueryOptions::new());
     ///
     /// ```
     ///
-    pub fn now(&'a mut self) -> Result<u64, Box<Error>> {
-        let req: KuzzleRequest = KuzzleRequest::new("server", "now");
-        let res = self.kuzzle().query(req, QueryOptions::new())?;
-        match &res.error() {
+    pub fn now(&self, options: QueryOptions) -> Result<u64, Error> {
+        self.kuzzle().call(actions::now()?, options)
+    }
+
+    fn kuzzle(&self) -> &Kuzzle {
+        self.0
+    }
+}
+
+/// The calls of the `server` controller actions.
+pub(crate) mod actions {
+    use super::*;
+    use crate::controllers::Call;
+
+    pub(crate) fn admin_exists() -> Result<Call<bool>, Error> {
+        let req: KuzzleRequest = KuzzleRequest::new("server", "adminExists");
+        Ok(Call::new(req, |res| match &res.error() {
             None => Ok(res
                 .result()
                 .as_object()
                 .unwrap()
-                .get("now")
-                .unwrap()
-                .as_u64()
+                .get("exists")
                 .unwrap()
-                .clone()),
-            Some(k_err) => Err(Box::new(k_err.clone())),
+                .as_bool()
+                .unwrap()),
+            Some(k_err) => Err(Error::from(k_err.clone())),
+        }))
+    }
+
+    pub(crate) fn get_all_stats() -> Result<Call<Vec<StatsFrame>>, Error> {
+        let req: KuzzleRequest = KuzzleRequest::new("server", "getAllStats");
+        Ok(Call::new(req, |res| match &res.error() {
+            None => {
+                let frames: Vec<StatsFrame> = serde_json::from_value(res.result()["hits"].clone())?;
+                Ok(frames)
+            }
+            Some(k_err) => Err(Error::from(k_err.clone())),
+        }))
+    }
+
+    pub(crate) fn get_config() -> Result<Call<ServerConfig>, Error> {
+        let req: KuzzleRequest = KuzzleRequest::new("server", "getConfig");
+        Ok(Call::new(req, |res| match &res.error() {
+            None => {
+                let config: ServerConfig = serde_json::from_value(res.result().clone())?;
+                Ok(config)
+            }
+            Some(k_err) => Err(Error::from(k_err.clone())),
+        }))
+    }
+
+    pub(crate) fn get_last_stats() -> Result<Call<StatsFrame>, Error> {
+        let req: KuzzleRequest = KuzzleRequest::new("server", "getLastStats");
+        Ok(Call::new(req, |res| match &res.error() {
+            None => {
+                let frame: StatsFrame = serde_json::from_value(res.result().clone())?;
+                Ok(frame)
+            }
+            Some(k_err) => Err(Error::from(k_err.clone())),
+        }))
+    }
+
+    pub(crate) fn get_stats(from: i64, to: i64) -> Result<Call<Vec<StatsFrame>>, Error> {
+        if from.to_string().len() != 13 || to.to_string().len() != 13 {
+            return Err(Error::InvalidArgument(SdkError::new(
+                "ServerController::get_stats",
+                "`form` and `to` arguments need to be millis Epoch timestamps (13 digits).",
+            )));
         }
+
+        let req: KuzzleRequest = KuzzleRequest::new("server", "getStats")
+            .add_to_query_strings("startTime", serde_json::to_value(from)?)
+            .add_to_query_strings("stopTime", serde_json::to_value(to)?);
+        Ok(Call::new(req, |res| match &res.error() {
+            None => {
+                let frames: Vec<StatsFrame> = serde_json::from_value(res.result()["hits"].clone())?;
+                Ok(frames)
+            }
+            Some(k_err) => Err(Error::from(k_err.clone())),
+        }))
     }
 
-    fn kuzzle(&'a mut self) -> &'a mut Kuzzle {
-        self.0
+    pub(crate) fn health_check() -> Result<Call<HealthCheck>, Error> {
+        let req: KuzzleRequest = KuzzleRequest::new("server", "healthCheck");
+        Ok(Call::new(req, |res| match &res.error() {
+            None => {
+                let health: HealthCheck = serde_json::from_value(res.result().clone())?;
+                Ok(health)
+            }
+            // A red node answers with a 503 status, along with its health report
+            Some(k_err) => match serde_json::from_value::<HealthCheck>(res.result().clone()) {
+                Ok(health) if *res.status() == 503 => Ok(health),
+                _ => Err(Error::from(k_err.clone())),
+            },
+        }))
+    }
+
+    pub(crate) fn info() -> Result<Call<ServerInfo>, Error> {
+        let req: KuzzleRequest = KuzzleRequest::new("server", "info");
+        Ok(Call::new(req, |res| match &res.error() {
+            None => {
+                let info: ServerInfo = serde_json::from_value(res.result()["serverInfo"].clone())?;
+                Ok(info)
+            }
+            Some(k_err) => Err(Error::from(k_err.clone())),
+        }))
+    }
+
+    pub(crate) fn now() -> Result<Call<u64>, Error> {
+        let req: KuzzleRequest = KuzzleRequest::new("server", "now");
+        Ok(Call::new(req, |res| match &res.error() {
+            None => Ok(res
+                .result()
+                .as_object()
+                .unwrap()
+                .get("now")
+                .unwrap()
+                .as_u64()
+                .unwrap()),
+            Some(k_err) => Err(Error::from(k_err.clone())),
+        }))
     }
 }
 
@@ -239,7 +326,7 @@ impl<'a> ServerController<'a> {
 mod tests {
     use super::*;
     use crate::protocols::Http;
-    use crate::types::KuzzleOptions;
+    use crate::types::{HealthStatus, KuzzleOptions};
     use mockito;
 
     #[test]
@@ -264,9 +351,9 @@ mod tests {
             )
             .create();
 
-        let mut k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
         k.connect().expect("Unable to connect to Kuzzle server");
-        let res = k.server().admin_exists();
+        let res = k.server().admin_exists(QueryOptions::new());
 
         assert!(res.is_ok());
         assert!(res.unwrap());
@@ -296,9 +383,9 @@ mod tests {
             )
             .create();
 
-        let mut k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
         k.connect().expect("Unable to connect to Kuzzle server");
-        let res = k.server().admin_exists();
+        let res = k.server().admin_exists(QueryOptions::new());
 
         assert!(res.is_err());
     }
@@ -345,32 +432,16 @@ mod tests {
             )
             .create();
 
-        let mut k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
         k.connect().expect("Unable to connect to Kuzzle server");
-        let res = k.server().get_all_stats();
+        let res = k.server().get_all_stats(QueryOptions::new());
 
         assert!(res.is_ok());
-        let stats = res.unwrap();
-        assert_eq!(stats.get("total").unwrap().as_u64().unwrap(), 1);
-        let hits = stats.get("hits").unwrap().as_array().unwrap()[0]
-            .as_object()
-            .unwrap()
-            .clone();
-        assert_eq!(
-            hits.get("timestamp").unwrap().as_u64().unwrap(),
-            1453110641308
-        );
-        assert_eq!(
-            hits.get("failedRequests")
-                .unwrap()
-                .as_object()
-                .unwrap()
-                .get("websocket")
-                .unwrap()
-                .as_u64()
-                .unwrap(),
-            3
-        );
+        let frames = res.unwrap();
+        assert_eq!(frames.len(), 1);
+        assert_eq!(frames[0].timestamp(), 1453110641308);
+        assert_eq!(frames[0].failed_requests().get("websocket"), Some(&3));
+        assert_eq!(frames[0].completed_requests().get("mqtt"), Some(&78));
     }
 
     #[test]
@@ -397,9 +468,9 @@ mod tests {
             )
             .create();
 
-        let mut k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
         k.connect().expect("Unable to connect to Kuzzle server");
-        let res = k.server().get_all_stats();
+        let res = k.server().get_all_stats(QueryOptions::new());
 
         assert!(res.is_err());
     }
@@ -445,25 +516,18 @@ mod tests {
             )
             .create();
 
-        let mut k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
         k.connect().expect("Unable to connect to Kuzzle server");
-        let res = k.server().get_config();
+        let res = k.server().get_config(QueryOptions::new());
 
         assert!(res.is_ok());
         let config = res.unwrap();
-        assert_eq!(config.get("version").unwrap().as_str().unwrap(), "1.5.1");
-        assert_eq!(
-            config
-                .get("limits")
-                .unwrap()
-                .as_object()
-                .unwrap()
-                .get("concurrentRequests")
-                .unwrap()
-                .as_u64()
-                .unwrap(),
-            100
-        );
+        assert_eq!(config.version(), "1.5.1");
+        assert_eq!(config.limits().concurrent_requests(), Some(100));
+        assert_eq!(config.limits().subscription_document_ttl(), Some(259200));
+        assert_eq!(config.limits().logins_per_second(), None);
+        assert!(config.plugins().contains_key("common"));
+        assert!(config.others().is_empty());
     }
 
     #[test]
@@ -490,9 +554,9 @@ mod tests {
             )
             .create();
 
-        let mut k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
         k.connect().expect("Unable to connect to Kuzzle server");
-        let res = k.server().get_config();
+        let res = k.server().get_config(QueryOptions::new());
 
         assert!(res.is_err());
     }
@@ -534,27 +598,15 @@ mod tests {
             )
             .create();
 
-        let mut k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
         k.connect().expect("Unable to connect to Kuzzle server");
-        let res = k.server().get_last_stats();
+        let res = k.server().get_last_stats(QueryOptions::new());
         assert!(res.is_ok());
         let last_stats = res.unwrap();
-        assert_eq!(
-            last_stats.get("timestamp").unwrap().as_u64().unwrap(),
-            1453110641308
-        );
-        assert_eq!(
-            last_stats
-                .get("failedRequests")
-                .unwrap()
-                .as_object()
-                .unwrap()
-                .get("websocket")
-                .unwrap()
-                .as_u64()
-                .unwrap(),
-            3
-        );
+        assert_eq!(last_stats.timestamp(), 1453110641308);
+        assert_eq!(last_stats.failed_requests().get("websocket"), Some(&3));
+        assert_eq!(last_stats.connections().get("websocket"), Some(&13));
+        assert_eq!(last_stats.ongoing_requests().get("http"), Some(&2));
     }
 
     #[test]
@@ -581,9 +633,9 @@ mod tests {
             )
             .create();
 
-        let mut k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
         k.connect().expect("Unable to connect to Kuzzle server");
-        let res = k.server().get_last_stats();
+        let res = k.server().get_last_stats(QueryOptions::new());
 
         assert!(res.is_err());
     }
@@ -607,48 +659,55 @@ mod tests {
 		    "index": null,
 		    "volatile": null,
                     "result": {
-                      "completedRequests": {
-                        "websocket": 148,
-                        "http": 24,
-                        "mqtt": 78
-                      },
-                      "failedRequests": {
-                        "websocket": 3
-                      },
-                      "ongoingRequests": {
-                        "mqtt": 8,
-                        "http": 2
-                      },
-                      "connections": {
-                        "websocket": 13
-                      },
-                      "timestamp": 1453110641308
+                      "total": 2,
+                      "hits": [
+                        {
+                          "completedRequests": {
+                            "websocket": 148,
+                            "http": 24,
+                            "mqtt": 78
+                          },
+                          "failedRequests": {
+                            "websocket": 3
+                          },
+                          "ongoingRequests": {
+                            "mqtt": 8,
+                            "http": 2
+                          },
+                          "connections": {
+                            "websocket": 13
+                          },
+                          "timestamp": 1453110641308
+                        },
+                        {
+                          "completedRequests": {
+                            "websocket": 152
+                          },
+                          "failedRequests": {},
+                          "ongoingRequests": {},
+                          "connections": {
+                            "websocket": 12
+                          },
+                          "timestamp": 1453110651308
+                        }
+                      ]
                     }
                 }"#,
             )
             .create();
 
-        let mut k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
         k.connect().expect("Unable to connect to Kuzzle server");
-        let res = k.server().get_stats(1550439618398, 1550436918273);
+        let res = k
+            .server()
+            .get_stats(1550439618398, 1550436918273, QueryOptions::new());
         assert!(res.is_ok());
         let stats = res.unwrap();
-        assert_eq!(
-            stats.get("timestamp").unwrap().as_u64().unwrap(),
-            1453110641308
-        );
-        assert_eq!(
-            stats
-                .get("failedRequests")
-                .unwrap()
-                .as_object()
-                .unwrap()
-                .get("websocket")
-                .unwrap()
-                .as_u64()
-                .unwrap(),
-            3
-        );
+        assert_eq!(stats.len(), 2);
+        assert_eq!(stats[0].timestamp(), 1453110641308);
+        assert_eq!(stats[0].failed_requests().get("websocket"), Some(&3));
+        assert_eq!(stats[1].completed_requests().get("websocket"), Some(&152));
+        assert!(stats[1].failed_requests().is_empty());
     }
 
     #[test]
@@ -678,27 +737,142 @@ mod tests {
             )
             .create();
 
-        let mut k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
         k.connect().expect("Unable to connect to Kuzzle server");
-        let res = k.server().get_stats(1550439618398, 1550436918273);
+        let res = k
+            .server()
+            .get_stats(1550439618398, 1550436918273, QueryOptions::new());
 
         assert!(res.is_err());
     }
 
     #[test]
     fn get_stats_fail_all_bad_timestamp_format() {
-        let mut k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
         k.connect().expect("Unable to connect to Kuzzle server");
-        let res = k.server().get_stats(1550439618, 150436918273);
+        let res = k
+            .server()
+            .get_stats(1550439618, 150436918273, QueryOptions::new());
 
         assert!(res.is_err());
     }
 
     #[test]
     fn get_stats_fail_one_bad_timestamp_format() {
-        let mut k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
         k.connect().expect("Unable to connect to Kuzzle server");
-        let res = k.server().get_stats(155043961845, 150436918273);
+        let res = k
+            .server()
+            .get_stats(155043961845, 150436918273, QueryOptions::new());
+
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn health_check_ok() {
+        let _m = mockito::mock("GET", "/_healthCheck")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "server",
+                    "action": "healthCheck",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": {
+                      "status": "green",
+                      "services": {
+                        "internalCache": "green",
+                        "memoryStorage": "green",
+                        "storageEngine": "green"
+                      }
+                    }
+                }"#,
+            )
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        k.connect().expect("Unable to connect to Kuzzle server");
+        let res = k.server().health_check(QueryOptions::new());
+
+        assert!(res.is_ok());
+        let health = res.unwrap();
+        assert!(health.is_healthy());
+        assert_eq!(health.services().len(), 3);
+    }
+
+    #[test]
+    fn health_check_ok_red() {
+        let _m = mockito::mock("GET", "/_healthCheck")
+            .with_status(503)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 503,
+                    "error": null,
+                    "controller": "server",
+                    "action": "healthCheck",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": {
+                      "status": "red",
+                      "services": {
+                        "internalCache": "green",
+                        "memoryStorage": "green",
+                        "storageEngine": "red"
+                      }
+                    }
+                }"#,
+            )
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        k.connect().expect("Unable to connect to Kuzzle server");
+        let res = k.server().health_check(QueryOptions::new());
+
+        assert!(res.is_ok());
+        let health = res.unwrap();
+        assert!(!health.is_healthy());
+        assert_eq!(health.status(), &HealthStatus::Red);
+        assert_eq!(
+            health.services().get("storageEngine"),
+            Some(&HealthStatus::Red)
+        );
+    }
+
+    #[test]
+    fn health_check_fail_error() {
+        let _m = mockito::mock("GET", "/_healthCheck")
+            .with_status(403)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 403,
+                    "error": {
+                      "message": "Forbidden action [null/null/server/healthCheck] for user -1",
+                      "status": 403,
+                      "stack": "ForbiddenError: Forbidden action [null/null/server/healthCheck] for user -1\n"
+                    },
+                    "controller": "server",
+                    "action": "healthCheck",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": null
+                }"#,
+            )
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        k.connect().expect("Unable to connect to Kuzzle server");
+        let res = k.server().health_check(QueryOptions::new());
 
         assert!(res.is_err());
     }
@@ -764,26 +938,18 @@ mod tests {
             )
             .create();
 
-        let mut k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
         k.connect().expect("Unable to connect to Kuzzle server");
-        let res = k.server().info();
+        let res = k.server().info(QueryOptions::new());
         assert!(res.is_ok());
         let info = res.unwrap();
-        assert_eq!(
-            info.get("serverInfo")
-                .unwrap()
-                .as_object()
-                .unwrap()
-                .get("kuzzle")
-                .unwrap()
-                .as_object()
-                .unwrap()
-                .get("memoryUsed")
-                .unwrap()
-                .as_u64()
-                .unwrap(),
-            115036160
-        );
+        assert_eq!(info.memory_used(), 115036160);
+        assert_eq!(info.node_version(), "v8.9.0");
+        assert!(info
+            .plugins()
+            .contains_key("kuzzle-plugin-auth-passport-local"));
+        assert!(info.api().contains_key("routes"));
+        assert!(info.services().is_empty());
     }
 
     #[test]
@@ -810,9 +976,9 @@ mod tests {
             )
             .create();
 
-        let mut k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
         k.connect().expect("Unable to connect to Kuzzle server");
-        let res = k.server().info();
+        let res = k.server().info(QueryOptions::new());
 
         assert!(res.is_err());
     }
@@ -839,9 +1005,9 @@ mod tests {
             )
             .create();
 
-        let mut k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
         k.connect().expect("Unable to connect to Kuzzle server");
-        let res = k.server().now();
+        let res = k.server().now(QueryOptions::new());
 
         assert!(res.is_ok());
         assert_eq!(res.unwrap().to_string().len(), 13);
@@ -871,9 +1037,9 @@ mod tests {
             )
             .create();
 
-        let mut k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
         k.connect().expect("Unable to connect to Kuzzle server");
-        let res = k.server().now();
+        let res = k.server().now(QueryOptions::new());
 
         assert!(res.is_err());
     }
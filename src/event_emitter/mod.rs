@@ -1,3 +1,4 @@
+#[allow(clippy::module_inception)]
 mod event_emitter;
 
 pub use self::event_emitter::EventEmitter;
@@ -0,0 +1,1277 @@
+//! The Kuzzle API subset served by the fake server, kept in memory.
+
+use crate::fake_server::query;
+
+use chrono::Utc;
+use serde_json::{json, Map, Value};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::hash::{Hash, Hasher};
+use std::sync::mpsc::Sender;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+/// Lifespan of the tokens returned by `auth:login`, in milliseconds, when none is asked for.
+const DEFAULT_TOKEN_TTL: i64 = 3_600_000;
+
+/// Number of documents returned by `document:search` when no `size` is given.
+const DEFAULT_SEARCH_SIZE: usize = 10;
+
+/// Identifier of a WebSocket connection, to which realtime notifications are sent.
+pub(crate) type ConnectionId = u64;
+
+/// An error sent back by the API, with the status and id Kuzzle uses for it.
+#[derive(Debug)]
+pub(crate) struct ApiError {
+    status: u16,
+    id: &'static str,
+    message: String,
+}
+
+type ApiResult = Result<Value, ApiError>;
+
+/// A document identifier and its source.
+type Hit = (String, Map<String, Value>);
+
+fn error<M: Into<String>>(status: u16, id: &'static str, message: M) -> ApiError {
+    ApiError {
+        status,
+        id,
+        message: message.into(),
+    }
+}
+
+fn missing_argument(name: &str) -> ApiError {
+    error(
+        400,
+        "api.assert.missing_argument",
+        format!("Missing argument \"{}\".", name),
+    )
+}
+
+/// The state of the fake server: indexes, memory storage, users, tokens and subscriptions.
+#[derive(Default)]
+pub(crate) struct Backend {
+    _state: Mutex<State>,
+}
+
+#[derive(Default)]
+struct State {
+    indexes: BTreeMap<String, BTreeMap<String, Collection>>,
+    memory: BTreeMap<String, Entry>,
+    /// Passwords, by username. Usernames are also used as user identifiers (kuid).
+    users: BTreeMap<String, String>,
+    tokens: HashMap<String, Token>,
+    /// Subscriptions, by channel.
+    channels: BTreeMap<String, Subscription>,
+    connections: HashMap<ConnectionId, Sender<String>>,
+    counter: u64,
+}
+
+#[derive(Default)]
+struct Collection {
+    documents: BTreeMap<String, StoredDocument>,
+    mapping: Map<String, Value>,
+}
+
+#[derive(Clone)]
+struct StoredDocument {
+    source: Map<String, Value>,
+    version: u64,
+}
+
+/// A memory storage value.
+enum Entry {
+    String(String),
+    Hash(BTreeMap<String, String>),
+}
+
+struct Token {
+    kuid: String,
+    expires_at: i64,
+}
+
+struct Subscription {
+    room_id: String,
+    index: String,
+    collection: String,
+    filters: Value,
+    scope: String,
+    connections: BTreeSet<ConnectionId>,
+}
+
+/// A request, as the JSON payload sent through WebSocket: arguments are top level fields.
+struct Request<'a> {
+    payload: &'a Map<String, Value>,
+    connection: Option<ConnectionId>,
+    /// Identifier of the authenticated user, if any.
+    kuid: Option<String>,
+}
+
+impl<'a> Request<'a> {
+    fn arg(&self, name: &str) -> Option<&Value> {
+        self.payload.get(name).filter(|value| !value.is_null())
+    }
+
+    fn string(&self, name: &str) -> Result<String, ApiError> {
+        match self.arg(name) {
+            Some(Value::String(value)) if !value.is_empty() => Ok(value.clone()),
+            Some(Value::Number(value)) => Ok(value.to_string()),
+            _ => Err(missing_argument(name)),
+        }
+    }
+
+    fn controller(&self) -> &str {
+        self.payload
+            .get("controller")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+    }
+
+    fn action(&self) -> &str {
+        self.payload
+            .get("action")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+    }
+
+    fn index(&self) -> Result<String, ApiError> {
+        self.string("index")
+    }
+
+    fn collection(&self) -> Result<String, ApiError> {
+        self.string("collection")
+    }
+
+    fn id(&self) -> Result<String, ApiError> {
+        self.string("_id")
+    }
+
+    fn body(&self) -> Map<String, Value> {
+        match self.arg("body") {
+            Some(Value::Object(body)) => body.clone(),
+            _ => Map::new(),
+        }
+    }
+
+    /// Returns a body field, or the argument of the same name: GET requests have no body.
+    fn body_arg(&self, name: &str) -> Option<Value> {
+        self.body()
+            .get(name)
+            .cloned()
+            .or_else(|| self.arg(name).cloned())
+            .filter(|value| !value.is_null())
+    }
+
+    fn required_body_arg(&self, name: &str) -> Result<Value, ApiError> {
+        self.body_arg(name).ok_or_else(|| missing_argument(name))
+    }
+
+    /// Returns a list argument, given either as an array or as a comma-separated string.
+    fn list(&self, name: &str) -> Result<Vec<String>, ApiError> {
+        match self.required_body_arg(name)? {
+            Value::Array(items) => Ok(items.iter().map(string_value).collect()),
+            Value::String(items) => Ok(items.split(',').map(String::from).collect()),
+            value => Ok(vec![string_value(&value)]),
+        }
+    }
+
+    fn number(&self, name: &str) -> Option<i64> {
+        self.body_arg(name).and_then(|value| match value {
+            Value::Number(number) => number.as_i64(),
+            Value::String(number) => number.parse().ok(),
+            _ => None,
+        })
+    }
+}
+
+/// Converts a memory storage argument to the string stored.
+fn string_value(value: &Value) -> String {
+    match value {
+        Value::String(value) => value.clone(),
+        value => value.to_string(),
+    }
+}
+
+impl Backend {
+    /// Executes the request `payload` and returns the response payload.
+    /// Requests coming through WebSocket give their `connection`.
+    pub(crate) fn handle(&self, payload: &Value, connection: Option<ConnectionId>) -> Value {
+        let empty = Map::new();
+        let mut req = Request {
+            payload: payload.as_object().unwrap_or(&empty),
+            connection,
+            kuid: None,
+        };
+
+        let mut state = self.state();
+        let result = state.authenticate(&req).and_then(|kuid| {
+            req.kuid = kuid;
+            state.dispatch(&req)
+        });
+
+        let (status, error, result) = match result {
+            Ok(result) => (200, Value::Null, result),
+            Err(err) => (
+                err.status,
+                json!({ "status": err.status, "id": err.id, "message": err.message }),
+                Value::Null,
+            ),
+        };
+
+        json!({
+            "requestId": req.arg("requestId").cloned().unwrap_or_else(|| json!("")),
+            "status": status,
+            "error": error,
+            "controller": req.controller(),
+            "action": req.action(),
+            "index": req.arg("index"),
+            "collection": req.arg("collection"),
+            "volatile": req.arg("volatile").cloned().unwrap_or_else(|| json!({})),
+            "result": result
+        })
+    }
+
+    /// Registers a WebSocket connection, to which notifications are sent with `notifications`.
+    pub(crate) fn connect(&self, notifications: Sender<String>) -> ConnectionId {
+        let mut state = self.state();
+        let connection = state.next_counter();
+        state.connections.insert(connection, notifications);
+        connection
+    }
+
+    /// Forgets a WebSocket connection and its subscriptions.
+    pub(crate) fn disconnect(&self, connection: ConnectionId) {
+        let mut state = self.state();
+        state.connections.remove(&connection);
+        state.unsubscribe_all(connection);
+    }
+
+    pub(crate) fn add_user(&self, username: &str, password: &str) {
+        self.state()
+            .users
+            .insert(username.to_string(), password.to_string());
+    }
+
+    pub(crate) fn add_collection(&self, index: &str, collection: &str) {
+        self.state()
+            .indexes
+            .entry(index.to_string())
+            .or_default()
+            .entry(collection.to_string())
+            .or_default();
+    }
+
+    fn state(&self) -> MutexGuard<'_, State> {
+        self._state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl State {
+    fn next_counter(&mut self) -> u64 {
+        self.counter += 1;
+        self.counter
+    }
+
+    /// Returns the user the request token belongs to, if any:
+    /// requests without a token are anonymous, as allowed by a default Kuzzle setup.
+    fn authenticate(&mut self, req: &Request) -> Result<Option<String>, ApiError> {
+        let jwt = match req.arg("jwt").and_then(Value::as_str) {
+            Some(jwt) if !jwt.is_empty() => jwt,
+            _ => return Ok(None),
+        };
+        if req.controller() == "auth" && ["login", "checkToken"].contains(&req.action()) {
+            return Ok(None);
+        }
+
+        self.check_token(jwt).map(Some)
+    }
+
+    fn check_token(&mut self, jwt: &str) -> Result<String, ApiError> {
+        match self.tokens.get(jwt) {
+            Some(token) if token.expires_at > Utc::now().timestamp_millis() => {
+                Ok(token.kuid.clone())
+            }
+            Some(_) => {
+                self.tokens.remove(jwt);
+                Err(error(401, "security.token.expired", "Token expired"))
+            }
+            None => Err(error(401, "security.token.invalid", "Invalid token")),
+        }
+    }
+
+    fn dispatch(&mut self, req: &Request) -> ApiResult {
+        let controller = match req.controller() {
+            "memoryStorage" => "ms",
+            controller => controller,
+        };
+
+        match (controller, req.action()) {
+            ("server", "now") => Ok(json!({ "now": Utc::now().timestamp_millis() })),
+            ("server", "healthCheck") => Ok(json!({
+                "status": "green",
+                "services": {
+                    "internalCache": "green",
+                    "memoryStorage": "green",
+                    "storageEngine": "green"
+                }
+            })),
+
+            ("auth", "login") => self.login(req),
+            ("auth", "logout") => self.logout(req),
+            ("auth", "checkToken") => self.check_token_action(req),
+            ("auth", "refreshToken") => self.refresh_token(req),
+            ("auth", "getCurrentUser") => self.get_current_user(req),
+            ("auth", "getStrategies") => Ok(json!(["local"])),
+
+            ("index", "create") => self.create_index(req),
+            ("index", "delete") => self.delete_index(req),
+            ("index", "mDelete") => self.mdelete_indexes(req),
+            ("index", "exists") => Ok(json!(self.indexes.contains_key(&req.index()?))),
+            ("index", "list") => Ok(json!({ "indexes": self.indexes.keys().collect::<Vec<_>>() })),
+            ("index", "refresh") => self.collections(&req.index()?).map(|_| json!(null)),
+
+            ("collection", "create") => self.create_collection(req),
+            ("collection", "delete") => self.delete_collection(req),
+            ("collection", "exists") => self.collection_exists(req),
+            ("collection", "list") => self.list_collections(req),
+            ("collection", "truncate") => self.truncate_collection(req),
+            ("collection", "getMapping") => self.get_mapping(req),
+            ("collection", "updateMapping") => self.update_mapping(req),
+
+            ("document", "create") => self.write_document(req, Write::Create, req.arg("_id")),
+            ("document", "createOrReplace") => {
+                self.write_document(req, Write::CreateOrReplace, Some(&json!(req.id()?)))
+            }
+            ("document", "replace") => {
+                self.write_document(req, Write::Replace, Some(&json!(req.id()?)))
+            }
+            ("document", "update") => {
+                self.write_document(req, Write::Update, Some(&json!(req.id()?)))
+            }
+            ("document", "get") => self.get_document(req),
+            ("document", "exists") => self.document_exists(req),
+            ("document", "delete") => self.delete_document(req),
+            ("document", "count") => self.count_documents(req),
+            ("document", "search") => self.search_documents(req),
+            ("document", "mGet") => self.mget_documents(req),
+            ("document", "mCreate") => self.mwrite_documents(req, Write::Create),
+            ("document", "mCreateOrReplace") => self.mwrite_documents(req, Write::CreateOrReplace),
+            ("document", "mReplace") => self.mwrite_documents(req, Write::Replace),
+            ("document", "mUpdate") => self.mwrite_documents(req, Write::Update),
+            ("document", "mDelete") => self.mdelete_documents(req),
+
+            ("ms", action) => self.memory_storage(req, action),
+
+            ("realtime", "subscribe") => self.subscribe(req),
+            ("realtime", "unsubscribe") => self.unsubscribe(req),
+            ("realtime", "publish") => self.publish(req),
+            ("realtime", "count") => self.count_subscribers(req),
+
+            (controller, action) => Err(error(
+                400,
+                "api.process.action_not_found",
+                format!(
+                    "API action \"{}\":\"{}\" not found, or not supported by the fake server.",
+                    controller, action
+                ),
+            )),
+        }
+    }
+
+    // auth
+
+    fn login(&mut self, req: &Request) -> ApiResult {
+        let strategy = req.string("strategy")?;
+        if strategy != "local" {
+            return Err(error(
+                400,
+                "security.credentials.unknown_strategy",
+                format!("Unknown authentication strategy \"{}\".", strategy),
+            ));
+        }
+
+        let body = req.body();
+        let username = body.get("username").and_then(Value::as_str);
+        let password = body.get("password").and_then(Value::as_str);
+        match (username, password) {
+            (Some(username), Some(password))
+                if self.users.get(username).map(String::as_str) == Some(password) =>
+            {
+                let ttl = req.number("expiresIn").unwrap_or(DEFAULT_TOKEN_TTL);
+                Ok(self.new_token(username.to_string(), ttl))
+            }
+            _ => Err(error(
+                401,
+                "security.auth.invalid_credentials",
+                "Wrong login or password.",
+            )),
+        }
+    }
+
+    fn new_token(&mut self, kuid: String, ttl: i64) -> Value {
+        let jwt = format!("fake-jwt-{}-{}", kuid, self.next_counter());
+        let expires_at = Utc::now().timestamp_millis() + ttl;
+        self.tokens.insert(
+            jwt.clone(),
+            Token {
+                kuid: kuid.clone(),
+                expires_at,
+            },
+        );
+
+        json!({ "_id": kuid, "jwt": jwt, "expiresAt": expires_at, "ttl": ttl })
+    }
+
+    fn logout(&mut self, req: &Request) -> ApiResult {
+        if let Some(jwt) = req.arg("jwt").and_then(Value::as_str) {
+            self.tokens.remove(jwt);
+        }
+        Ok(Value::Null)
+    }
+
+    fn check_token_action(&mut self, req: &Request) -> ApiResult {
+        let token = string_value(&req.required_body_arg("token")?);
+        match self.check_token(&token) {
+            Ok(_) => Ok(json!({
+                "valid": true,
+                "expiresAt": self.tokens[&token].expires_at
+            })),
+            Err(err) => Ok(json!({ "valid": false, "state": err.message })),
+        }
+    }
+
+    fn refresh_token(&mut self, req: &Request) -> ApiResult {
+        let kuid = self.logged_user(req)?;
+        if let Some(jwt) = req.arg("jwt").and_then(Value::as_str) {
+            self.tokens.remove(jwt);
+        }
+        let ttl = req.number("expiresIn").unwrap_or(DEFAULT_TOKEN_TTL);
+        Ok(self.new_token(kuid, ttl))
+    }
+
+    fn get_current_user(&mut self, req: &Request) -> ApiResult {
+        let kuid = self.logged_user(req)?;
+        Ok(json!({
+            "_id": kuid,
+            "_source": { "profileIds": ["default"] },
+            "strategies": ["local"]
+        }))
+    }
+
+    fn logged_user(&self, req: &Request) -> Result<String, ApiError> {
+        req.kuid.clone().ok_or_else(|| {
+            error(
+                401,
+                "security.rights.unauthorized",
+                "Unauthorized action for anonymous user.",
+            )
+        })
+    }
+
+    // index and collection
+
+    fn collections(&mut self, index: &str) -> Result<&mut BTreeMap<String, Collection>, ApiError> {
+        self.indexes.get_mut(index).ok_or_else(|| {
+            error(
+                404,
+                "services.storage.unknown_index",
+                format!("Index \"{}\" does not exist.", index),
+            )
+        })
+    }
+
+    fn collection(&mut self, index: &str, collection: &str) -> Result<&mut Collection, ApiError> {
+        self.collections(index)?.get_mut(collection).ok_or_else(|| {
+            error(
+                404,
+                "services.storage.unknown_collection",
+                format!(
+                    "Collection \"{}\" does not exist in index \"{}\".",
+                    collection, index
+                ),
+            )
+        })
+    }
+
+    fn create_index(&mut self, req: &Request) -> ApiResult {
+        let index = req.index()?;
+        if self.indexes.contains_key(&index) {
+            return Err(error(
+                412,
+                "services.storage.index_already_exists",
+                format!("Index \"{}\" already exists.", index),
+            ));
+        }
+
+        self.indexes.insert(index, BTreeMap::new());
+        Ok(json!({ "acknowledged": true }))
+    }
+
+    fn delete_index(&mut self, req: &Request) -> ApiResult {
+        let index = req.index()?;
+        self.collections(&index)?;
+        self.indexes.remove(&index);
+        Ok(json!({ "acknowledged": true }))
+    }
+
+    fn mdelete_indexes(&mut self, req: &Request) -> ApiResult {
+        let deleted: Vec<String> = req
+            .list("indexes")?
+            .into_iter()
+            .filter(|index| self.indexes.remove(index).is_some())
+            .collect();
+        Ok(json!({ "deleted": deleted }))
+    }
+
+    fn create_collection(&mut self, req: &Request) -> ApiResult {
+        let (index, collection) = (req.index()?, req.collection()?);
+        let mapping = req.body();
+        let collection = self.collections(&index)?.entry(collection).or_default();
+
+        if !mapping.is_empty() {
+            collection.mapping = mapping;
+        }
+        Ok(json!({ "acknowledged": true }))
+    }
+
+    fn delete_collection(&mut self, req: &Request) -> ApiResult {
+        let (index, collection) = (req.index()?, req.collection()?);
+        self.collection(&index, &collection)?;
+        self.collections(&index)?.remove(&collection);
+        Ok(Value::Null)
+    }
+
+    fn collection_exists(&mut self, req: &Request) -> ApiResult {
+        let (index, collection) = (req.index()?, req.collection()?);
+        Ok(json!(self.indexes.get(&index).is_some_and(|collections| {
+            collections.contains_key(&collection)
+        })))
+    }
+
+    fn list_collections(&mut self, req: &Request) -> ApiResult {
+        let collections: Vec<Value> = self
+            .collections(&req.index()?)?
+            .keys()
+            .map(|name| json!({ "name": name, "type": "stored" }))
+            .collect();
+        Ok(json!({ "type": "all", "collections": collections }))
+    }
+
+    fn truncate_collection(&mut self, req: &Request) -> ApiResult {
+        let (index, collection) = (req.index()?, req.collection()?);
+        self.collection(&index, &collection)?.documents.clear();
+        Ok(json!({ "acknowledged": true }))
+    }
+
+    fn get_mapping(&mut self, req: &Request) -> ApiResult {
+        let (index, collection) = (req.index()?, req.collection()?);
+        let mapping = &self.collection(&index, &collection)?.mapping;
+
+        let mut result = mapping.clone();
+        result.entry("properties").or_insert_with(|| json!({}));
+        Ok(Value::Object(result))
+    }
+
+    fn update_mapping(&mut self, req: &Request) -> ApiResult {
+        let (index, collection) = (req.index()?, req.collection()?);
+        let mapping = &mut self.collection(&index, &collection)?.mapping;
+
+        merge(mapping, req.body());
+        Ok(Value::Object(mapping.clone()))
+    }
+
+    // document
+
+    fn write_document(&mut self, req: &Request, write: Write, id: Option<&Value>) -> ApiResult {
+        let (index, collection) = (req.index()?, req.collection()?);
+        let (document, before) = self.write(
+            req,
+            &index,
+            &collection,
+            write,
+            id.map(string_value),
+            req.body(),
+        )?;
+
+        let result = document_json(&document.0, &document.1);
+        self.notify(
+            req,
+            &index,
+            &collection,
+            &document.0,
+            before,
+            Some(document.1.source),
+        );
+        Ok(result)
+    }
+
+    /// Writes a document and returns it along with its previous content.
+    #[allow(clippy::type_complexity)]
+    fn write(
+        &mut self,
+        req: &Request,
+        index: &str,
+        collection: &str,
+        write: Write,
+        id: Option<String>,
+        content: Map<String, Value>,
+    ) -> Result<((String, StoredDocument), Option<Map<String, Value>>), ApiError> {
+        let id = match id {
+            Some(id) if !id.is_empty() => id,
+            _ if write == Write::Create => self.new_id(),
+            _ => return Err(missing_argument("_id")),
+        };
+        let author = req.kuid.clone().map_or(Value::from("-1"), Value::from);
+        let now = Utc::now().timestamp_millis();
+
+        let documents = &mut self.collection(index, collection)?.documents;
+        let existing = documents.get(&id).cloned();
+
+        let document = match (write, existing.clone()) {
+            (Write::Create, Some(_)) => {
+                return Err(error(
+                    412,
+                    "services.storage.document_already_exists",
+                    format!("Document \"{}\" already exists.", id),
+                ))
+            }
+            (Write::Replace, None) | (Write::Update, None) => {
+                return Err(error(
+                    404,
+                    "services.storage.not_found",
+                    format!("Document \"{}\" not found.", id),
+                ))
+            }
+            (Write::Update, Some(mut document)) => {
+                merge(&mut document.source, content);
+                set_updated(&mut document.source, author, now);
+                document.version += 1;
+                document
+            }
+            (_, existing) => {
+                let mut source = content;
+                source.remove("_kuzzle_info");
+                match &existing {
+                    Some(existing) => {
+                        let created = existing.source.get("_kuzzle_info").cloned();
+                        source.insert("_kuzzle_info".to_string(), created.unwrap_or_default());
+                        set_updated(&mut source, author, now);
+                    }
+                    None => {
+                        source.insert(
+                            "_kuzzle_info".to_string(),
+                            json!({
+                                "author": author,
+                                "createdAt": now,
+                                "updatedAt": null,
+                                "updater": null
+                            }),
+                        );
+                    }
+                }
+                StoredDocument {
+                    source,
+                    version: existing.map_or(1, |existing| existing.version + 1),
+                }
+            }
+        };
+
+        documents.insert(id.clone(), document.clone());
+        Ok(((id, document), existing.map(|existing| existing.source)))
+    }
+
+    fn new_id(&mut self) -> String {
+        format!(
+            "{:x}{:06x}",
+            Utc::now().timestamp_millis(),
+            self.next_counter()
+        )
+    }
+
+    fn mwrite_documents(&mut self, req: &Request, write: Write) -> ApiResult {
+        let (index, collection) = (req.index()?, req.collection()?);
+        self.collection(&index, &collection)?;
+        let documents = match req.required_body_arg("documents")? {
+            Value::Array(documents) => documents,
+            _ => return Err(missing_argument("documents")),
+        };
+
+        let (mut successes, mut errors) = (Vec::new(), Vec::new());
+        for item in documents {
+            let id = item.get("_id").map(string_value);
+            let content = match item.get("body") {
+                Some(Value::Object(content)) => content.clone(),
+                _ => Map::new(),
+            };
+
+            match self.write(req, &index, &collection, write, id, content) {
+                Ok(((id, document), before)) => {
+                    successes.push(document_json(&id, &document));
+                    self.notify(req, &index, &collection, &id, before, Some(document.source));
+                }
+                Err(err) => errors.push(json!({
+                    "document": item,
+                    "status": err.status,
+                    "reason": err.message
+                })),
+            }
+        }
+
+        Ok(json!({ "successes": successes, "errors": errors }))
+    }
+
+    fn get_document(&mut self, req: &Request) -> ApiResult {
+        let (index, collection, id) = (req.index()?, req.collection()?, req.id()?);
+        match self.collection(&index, &collection)?.documents.get(&id) {
+            Some(document) => Ok(document_json(&id, document)),
+            None => Err(error(
+                404,
+                "services.storage.not_found",
+                format!("Document \"{}\" not found.", id),
+            )),
+        }
+    }
+
+    fn document_exists(&mut self, req: &Request) -> ApiResult {
+        let (index, collection, id) = (req.index()?, req.collection()?, req.id()?);
+        let documents = &self.collection(&index, &collection)?.documents;
+        Ok(json!(documents.contains_key(&id)))
+    }
+
+    fn mget_documents(&mut self, req: &Request) -> ApiResult {
+        let (index, collection) = (req.index()?, req.collection()?);
+        let ids = req.list("ids")?;
+        let documents = &self.collection(&index, &collection)?.documents;
+
+        let (mut successes, mut errors) = (Vec::new(), Vec::new());
+        for id in ids {
+            match documents.get(&id) {
+                Some(document) => successes.push(document_json(&id, document)),
+                None => errors.push(json!(id)),
+            }
+        }
+        Ok(json!({ "successes": successes, "errors": errors }))
+    }
+
+    fn delete_document(&mut self, req: &Request) -> ApiResult {
+        let (index, collection, id) = (req.index()?, req.collection()?, req.id()?);
+        match self.collection(&index, &collection)?.documents.remove(&id) {
+            Some(document) => {
+                self.notify(req, &index, &collection, &id, Some(document.source), None);
+                Ok(json!({ "_id": id }))
+            }
+            None => Err(error(
+                404,
+                "services.storage.not_found",
+                format!("Document \"{}\" not found.", id),
+            )),
+        }
+    }
+
+    fn mdelete_documents(&mut self, req: &Request) -> ApiResult {
+        let (index, collection) = (req.index()?, req.collection()?);
+        let ids = req.list("ids")?;
+
+        let (mut successes, mut errors) = (Vec::new(), Vec::new());
+        for id in ids {
+            match self.collection(&index, &collection)?.documents.remove(&id) {
+                Some(document) => {
+                    self.notify(req, &index, &collection, &id, Some(document.source), None);
+                    successes.push(json!({ "_id": id }));
+                }
+                None => errors.push(json!({
+                    "_id": id,
+                    "status": 404,
+                    "reason": "Document not found"
+                })),
+            }
+        }
+        Ok(json!({ "successes": successes, "errors": errors }))
+    }
+
+    /// Returns the documents of the collection matching the request query.
+    fn matching_documents(&mut self, req: &Request) -> Result<Vec<Hit>, ApiError> {
+        let (index, collection) = (req.index()?, req.collection()?);
+        let query = req.body().get("query").cloned().unwrap_or(Value::Null);
+        let documents = &self.collection(&index, &collection)?.documents;
+
+        let mut matching = Vec::new();
+        for (id, document) in documents {
+            let matches = query::matches_query(id, &document.source, &query)
+                .map_err(|message| error(400, "services.storage.invalid_query", message))?;
+            if matches {
+                matching.push((id.clone(), document.source.clone()));
+            }
+        }
+        Ok(matching)
+    }
+
+    fn count_documents(&mut self, req: &Request) -> ApiResult {
+        Ok(json!({ "count": self.matching_documents(req)?.len() }))
+    }
+
+    fn search_documents(&mut self, req: &Request) -> ApiResult {
+        let mut hits = self.matching_documents(req)?;
+        if let Some(sort) = req.body().get("sort") {
+            query::sort(&mut hits, sort);
+        }
+
+        let from = req.number("from").unwrap_or(0).max(0) as usize;
+        let size = req
+            .number("size")
+            .map_or(DEFAULT_SEARCH_SIZE, |size| size.max(0) as usize);
+        let total = hits.len();
+        let hits: Vec<Value> = hits
+            .into_iter()
+            .skip(from)
+            .take(size)
+            .map(|(id, source)| json!({ "_id": id, "_score": 1, "_source": source }))
+            .collect();
+
+        Ok(json!({ "total": total, "hits": hits }))
+    }
+
+    // memory storage
+
+    fn memory_storage(&mut self, req: &Request, action: &str) -> ApiResult {
+        match action {
+            "set" => {
+                let (key, value) = (req.id()?, req.required_body_arg("value")?);
+                let exists = self.memory.contains_key(&key);
+                if (exists && req.body_arg("nx") == Some(json!(true)))
+                    || (!exists && req.body_arg("xx") == Some(json!(true)))
+                {
+                    return Ok(Value::Null);
+                }
+                self.memory.insert(key, Entry::String(string_value(&value)));
+                Ok(json!("OK"))
+            }
+            "get" => match self.memory.get(&req.id()?) {
+                Some(Entry::String(value)) => Ok(json!(value)),
+                Some(Entry::Hash(_)) => Err(wrong_type()),
+                None => Ok(Value::Null),
+            },
+            "del" => {
+                let keys = req.list("keys")?;
+                let deleted = keys
+                    .iter()
+                    .filter(|key| self.memory.remove(*key).is_some())
+                    .count();
+                Ok(json!(deleted))
+            }
+            "exists" => {
+                let keys = req.list("keys")?;
+                Ok(json!(keys
+                    .iter()
+                    .filter(|key| self.memory.contains_key(*key))
+                    .count()))
+            }
+            "keys" => {
+                let pattern = req.string("pattern")?;
+                let keys: Vec<&String> = self
+                    .memory
+                    .keys()
+                    .filter(|key| glob_matches(&pattern, key))
+                    .collect();
+                Ok(json!(keys))
+            }
+            "mset" => {
+                for entry in entries(req)? {
+                    self.memory.insert(entry.0, Entry::String(entry.1));
+                }
+                Ok(json!("OK"))
+            }
+            "mget" => {
+                let values: Vec<Value> = req
+                    .list("keys")?
+                    .iter()
+                    .map(|key| match self.memory.get(key) {
+                        Some(Entry::String(value)) => json!(value),
+                        _ => Value::Null,
+                    })
+                    .collect();
+                Ok(json!(values))
+            }
+            "incr" | "incrby" | "decr" | "decrby" => {
+                let value = match action {
+                    "incr" => 1,
+                    "decr" => -1,
+                    _ => {
+                        let value = req
+                            .number("value")
+                            .ok_or_else(|| missing_argument("value"))?;
+                        if action == "decrby" {
+                            -value
+                        } else {
+                            value
+                        }
+                    }
+                };
+                self.increment(req.id()?, value)
+            }
+            "append" => {
+                let (key, value) = (req.id()?, req.required_body_arg("value")?);
+                let current = self.string_entry(&key)?;
+                let appended = format!("{}{}", current, string_value(&value));
+                let length = appended.len();
+                self.memory.insert(key, Entry::String(appended));
+                Ok(json!(length))
+            }
+            "strlen" => Ok(json!(self.string_entry(&req.id()?)?.len())),
+
+            "hset" | "hsetnx" => {
+                let (key, field) = (req.id()?, req.required_body_arg("field")?);
+                let value = string_value(&req.required_body_arg("value")?);
+                let hash = self.hash_entry(&key)?;
+                let field = string_value(&field);
+                if action == "hsetnx" && hash.contains_key(&field) {
+                    return Ok(json!(0));
+                }
+                let created = hash.insert(field, value).is_none();
+                Ok(json!(created as u8))
+            }
+            "hmset" => {
+                let key = req.id()?;
+                let entries = entries(req)?;
+                let hash = self.hash_entry(&key)?;
+                hash.extend(entries);
+                Ok(json!("OK"))
+            }
+            "hget" => {
+                let (key, field) = (req.id()?, req.string("field")?);
+                Ok(json!(self.hash(&key)?.and_then(|hash| hash.get(&field))))
+            }
+            "hgetall" => Ok(json!(self.hash(&req.id()?)?.cloned().unwrap_or_default())),
+            "hexists" => {
+                let (key, field) = (req.id()?, req.string("field")?);
+                let exists = self
+                    .hash(&key)?
+                    .is_some_and(|hash| hash.contains_key(&field));
+                Ok(json!(exists as u8))
+            }
+            "hdel" => {
+                let (key, fields) = (req.id()?, req.list("fields")?);
+                let hash = self.hash_entry(&key)?;
+                let deleted = fields
+                    .iter()
+                    .filter(|field| hash.remove(*field).is_some())
+                    .count();
+                Ok(json!(deleted))
+            }
+            "hkeys" => Ok(json!(self
+                .hash(&req.id()?)?
+                .map_or(Vec::new(), |hash| hash.keys().cloned().collect()))),
+            "hvals" => Ok(json!(self
+                .hash(&req.id()?)?
+                .map_or(Vec::new(), |hash| hash.values().cloned().collect()))),
+            "hlen" => Ok(json!(self.hash(&req.id()?)?.map_or(0, BTreeMap::len))),
+            "hincrby" => {
+                let (key, field) = (req.id()?, string_value(&req.required_body_arg("field")?));
+                let value = req
+                    .number("value")
+                    .ok_or_else(|| missing_argument("value"))?;
+                let hash = self.hash_entry(&key)?;
+                let current = hash.get(&field).map_or(Ok(0), |current| {
+                    current.parse::<i64>().map_err(|_| not_an_integer())
+                })?;
+                hash.insert(field, (current + value).to_string());
+                Ok(json!(current + value))
+            }
+
+            "flushdb" => {
+                self.memory.clear();
+                Ok(json!("OK"))
+            }
+            "dbsize" => Ok(json!(self.memory.len())),
+            "ping" => Ok(json!("PONG")),
+            action => Err(error(
+                400,
+                "api.process.action_not_found",
+                format!(
+                    "API action \"ms\":\"{}\" not found, or not supported by the fake server.",
+                    action
+                ),
+            )),
+        }
+    }
+
+    fn string_entry(&self, key: &str) -> Result<String, ApiError> {
+        match self.memory.get(key) {
+            Some(Entry::String(value)) => Ok(value.clone()),
+            Some(Entry::Hash(_)) => Err(wrong_type()),
+            None => Ok(String::new()),
+        }
+    }
+
+    fn increment(&mut self, key: String, value: i64) -> ApiResult {
+        let current = match self.memory.get(&key) {
+            Some(Entry::String(current)) => current.parse::<i64>().map_err(|_| not_an_integer())?,
+            Some(Entry::Hash(_)) => return Err(wrong_type()),
+            None => 0,
+        };
+        self.memory
+            .insert(key, Entry::String((current + value).to_string()));
+        Ok(json!(current + value))
+    }
+
+    fn hash(&self, key: &str) -> Result<Option<&BTreeMap<String, String>>, ApiError> {
+        match self.memory.get(key) {
+            Some(Entry::Hash(hash)) => Ok(Some(hash)),
+            Some(Entry::String(_)) => Err(wrong_type()),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns the hash stored at `key`, created if missing.
+    fn hash_entry(&mut self, key: &str) -> Result<&mut BTreeMap<String, String>, ApiError> {
+        match self
+            .memory
+            .entry(key.to_string())
+            .or_insert_with(|| Entry::Hash(BTreeMap::new()))
+        {
+            Entry::Hash(hash) => Ok(hash),
+            Entry::String(_) => Err(wrong_type()),
+        }
+    }
+
+    // realtime
+
+    fn subscribe(&mut self, req: &Request) -> ApiResult {
+        let connection = req.connection.ok_or_else(|| {
+            error(
+                400,
+                "api.assert.unsupported_protocol",
+                "Realtime subscriptions require a WebSocket connection.",
+            )
+        })?;
+        let (index, collection) = (req.index()?, req.collection()?);
+        let filters = Value::Object(req.body());
+        let scope = req
+            .arg("scope")
+            .and_then(Value::as_str)
+            .unwrap_or("all")
+            .to_string();
+
+        // Filters are checked now rather than when a document changes
+        query::matches_filters("", &Map::new(), &filters)
+            .map_err(|message| error(400, "core.realtime.invalid_filters", message))?;
+
+        let mut hasher = DefaultHasher::new();
+        (&index, &collection, filters.to_string()).hash(&mut hasher);
+        let room_id = format!("{:016x}", hasher.finish());
+        let channel = format!("{}-{}", room_id, scope);
+
+        self.channels
+            .entry(channel.clone())
+            .or_insert_with(|| Subscription {
+                room_id: room_id.clone(),
+                index,
+                collection,
+                filters,
+                scope,
+                connections: BTreeSet::new(),
+            })
+            .connections
+            .insert(connection);
+
+        Ok(json!({ "roomId": room_id, "channel": channel }))
+    }
+
+    fn unsubscribe(&mut self, req: &Request) -> ApiResult {
+        let room_id = string_value(&req.required_body_arg("roomId")?);
+        let connection = req.connection.unwrap_or_default();
+
+        let mut subscribed = false;
+        for subscription in self.channels.values_mut() {
+            if subscription.room_id == room_id {
+                subscribed |= subscription.connections.remove(&connection);
+            }
+        }
+        self.channels
+            .retain(|_, subscription| !subscription.connections.is_empty());
+
+        if !subscribed {
+            return Err(error(
+                412,
+                "core.realtime.not_subscribed",
+                format!("Not subscribed to \"{}\".", room_id),
+            ));
+        }
+        Ok(json!({ "roomId": room_id }))
+    }
+
+    fn unsubscribe_all(&mut self, connection: ConnectionId) {
+        for subscription in self.channels.values_mut() {
+            subscription.connections.remove(&connection);
+        }
+        self.channels
+            .retain(|_, subscription| !subscription.connections.is_empty());
+    }
+
+    fn count_subscribers(&mut self, req: &Request) -> ApiResult {
+        let room_id = string_value(&req.required_body_arg("roomId")?);
+        let connections: BTreeSet<&ConnectionId> = self
+            .channels
+            .values()
+            .filter(|subscription| subscription.room_id == room_id)
+            .flat_map(|subscription| subscription.connections.iter())
+            .collect();
+        Ok(json!({ "count": connections.len() }))
+    }
+
+    fn publish(&mut self, req: &Request) -> ApiResult {
+        let (index, collection) = (req.index()?, req.collection()?);
+        self.notify(req, &index, &collection, "", None, Some(req.body()));
+        Ok(json!({ "published": true }))
+    }
+
+    /// Notifies subscribers that a document of `index`/`collection` changed from `before` to `after`.
+    /// Documents entering the scope of a subscription are notified with the `in` scope,
+    /// and those leaving it with the `out` scope.
+    fn notify(
+        &mut self,
+        req: &Request,
+        index: &str,
+        collection: &str,
+        id: &str,
+        before: Option<Map<String, Value>>,
+        after: Option<Map<String, Value>>,
+    ) {
+        let matches = |subscription: &Subscription, source: &Option<Map<String, Value>>| {
+            source.as_ref().is_some_and(|source| {
+                query::matches_filters(id, source, &subscription.filters).unwrap_or(false)
+            })
+        };
+
+        for (channel, subscription) in &self.channels {
+            if subscription.index != index || subscription.collection != collection {
+                continue;
+            }
+
+            let scope = if matches(subscription, &after) {
+                "in"
+            } else if matches(subscription, &before) {
+                "out"
+            } else {
+                continue;
+            };
+            if subscription.scope != "all" && subscription.scope != scope {
+                continue;
+            }
+
+            let notification = json!({
+                "status": 200,
+                "requestId": req.arg("requestId").cloned().unwrap_or_else(|| json!("")),
+                "timestamp": Utc::now().timestamp_millis(),
+                "volatile": req.arg("volatile").cloned().unwrap_or_else(|| json!({})),
+                "index": index,
+                "collection": collection,
+                "controller": req.controller(),
+                "action": req.action(),
+                "protocol": if req.connection.is_some() { "websocket" } else { "http" },
+                "scope": scope,
+                "type": "document",
+                "room": channel,
+                "result": {
+                    "_id": if id.is_empty() { Value::Null } else { json!(id) },
+                    "_source": after.as_ref().or(before.as_ref())
+                }
+            })
+            .to_string();
+
+            for connection in &subscription.connections {
+                if let Some(sender) = self.connections.get(connection) {
+                    let _ = sender.send(notification.clone());
+                }
+            }
+        }
+    }
+}
+
+/// The kind of write made by a document action.
+#[derive(Clone, Copy, PartialEq)]
+enum Write {
+    Create,
+    CreateOrReplace,
+    Replace,
+    Update,
+}
+
+fn document_json(id: &str, document: &StoredDocument) -> Value {
+    json!({ "_id": id, "_version": document.version, "_source": document.source })
+}
+
+fn set_updated(source: &mut Map<String, Value>, updater: Value, now: i64) {
+    if let Some(Value::Object(info)) = source.get_mut("_kuzzle_info") {
+        info.insert("updatedAt".to_string(), json!(now));
+        info.insert("updater".to_string(), updater);
+    }
+}
+
+/// Merges `changes` into `target`, recursively for objects.
+fn merge(target: &mut Map<String, Value>, changes: Map<String, Value>) {
+    for (key, change) in changes {
+        match (target.get_mut(&key), change) {
+            (Some(Value::Object(target)), Value::Object(change)) => merge(target, change),
+            (_, change) => {
+                target.insert(key, change);
+            }
+        }
+    }
+}
+
+/// Returns the `entries` argument of `mset` and `hmset`: `[{ "key" | "field": ..., "value": ... }]`.
+fn entries(req: &Request) -> Result<Vec<(String, String)>, ApiError> {
+    match req.required_body_arg("entries")? {
+        Value::Array(entries) => Ok(entries
+            .iter()
+            .filter_map(|entry| {
+                let name = entry.get("key").or_else(|| entry.get("field"))?;
+                Some((string_value(name), string_value(entry.get("value")?)))
+            })
+            .collect()),
+        _ => Err(missing_argument("entries")),
+    }
+}
+
+/// Matches a memory storage key against a pattern, where `*` matches any characters
+/// and `?` a single one.
+fn glob_matches(pattern: &str, key: &str) -> bool {
+    let (pattern, key): (Vec<char>, Vec<char>) = (pattern.chars().collect(), key.chars().collect());
+    let (mut p, mut k) = (0, 0);
+    let mut star: Option<(usize, usize)> = None;
+
+    while k < key.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == key[k]) {
+            p += 1;
+            k += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            star = Some((p, k));
+            p += 1;
+        } else if let Some((star_p, star_k)) = star {
+            p = star_p + 1;
+            k = star_k + 1;
+            star = Some((star_p, star_k + 1));
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+fn wrong_type() -> ApiError {
+    error(
+        400,
+        "services.memory_storage.wrong_type",
+        "Operation against a key holding the wrong kind of value.",
+    )
+}
+
+fn not_an_integer() -> ApiError {
+    error(
+        400,
+        "services.memory_storage.not_an_integer",
+        "Value is not an integer or out of range.",
+    )
+}
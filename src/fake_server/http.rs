@@ -0,0 +1,299 @@
+//! HTTP requests to the fake server, converted to the payloads sent through WebSocket.
+
+use crate::protocols::Http;
+
+use serde_json::{json, Map, Value};
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::TcpStream;
+
+/// A route of the Kuzzle HTTP API, split into path segments.
+pub(crate) struct HttpRoute {
+    verb: String,
+    segments: Vec<String>,
+    controller: String,
+    action: String,
+}
+
+/// Returns the routes of the API, as bundled with the SDK.
+pub(crate) fn routes() -> Vec<HttpRoute> {
+    let mut routes: Vec<HttpRoute> = Http::bundled_routes()
+        .into_iter()
+        // Same routes as the `ms` controller
+        .filter(|(controller, _)| controller != "memoryStorage")
+        .flat_map(|(controller, actions)| {
+            actions.into_iter().map(move |(action, route)| HttpRoute {
+                verb: route.verb.to_uppercase(),
+                segments: route
+                    .url
+                    .split('/')
+                    .filter(|segment| !segment.is_empty())
+                    .map(String::from)
+                    .collect(),
+                controller: controller.clone(),
+                action,
+            })
+        })
+        .collect();
+
+    // Routes are matched in a deterministic order
+    routes.sort_by(|a, b| (&a.controller, &a.action).cmp(&(&b.controller, &b.action)));
+    routes
+}
+
+/// Reads an HTTP request from `stream` and returns its payload.
+/// Routes which don't exist are answered with a `network.http.url_not_found` error.
+pub(crate) fn read_request(
+    stream: &TcpStream,
+    routes: &[HttpRoute],
+) -> Result<Result<Value, Value>, io::Error> {
+    let mut reader = BufReader::new(stream);
+
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    let mut parts = request_line.split_whitespace();
+    let (method, target) = match (parts.next(), parts.next()) {
+        (Some(method), Some(target)) => (method.to_uppercase(), target.to_string()),
+        _ => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "Invalid request line",
+            ))
+        }
+    };
+
+    let mut headers = Map::new();
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 || line.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            headers.insert(name.trim().to_lowercase(), json!(value.trim()));
+        }
+    }
+
+    let length = headers
+        .get("content-length")
+        .and_then(Value::as_str)
+        .and_then(|length| length.parse().ok())
+        .unwrap_or(0);
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body)?;
+
+    let (path, query) = target.split_once('?').unwrap_or((&target, ""));
+    let segments: Vec<String> = path
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .map(decode)
+        .collect();
+
+    let route = match find_route(routes, &method, &segments) {
+        Some(route) => route,
+        None => {
+            return Ok(Err(json!({
+                "status": 404,
+                "id": "network.http.url_not_found",
+                "message": format!("API URL not found: {} {}.", method, path)
+            })))
+        }
+    };
+
+    let mut payload = Map::new();
+    for (name, value) in query.split('&').filter_map(|pair| pair.split_once('=')) {
+        payload.insert(decode(name), query_value(&decode(value)));
+    }
+    for (segment, value) in route.segments.iter().zip(segments) {
+        if let Some(name) = segment.strip_prefix(':') {
+            payload.insert(name.to_string(), json!(value));
+        }
+    }
+    payload.insert("controller".to_string(), json!(route.controller));
+    payload.insert("action".to_string(), json!(route.action));
+
+    if !body.is_empty() {
+        match serde_json::from_slice(&body) {
+            Ok(body) => {
+                payload.insert("body".to_string(), body);
+            }
+            Err(err) => {
+                return Ok(Err(json!({
+                    "status": 400,
+                    "id": "api.assert.body_required",
+                    "message": format!("Invalid request body: {}", err)
+                })))
+            }
+        }
+    }
+
+    let header = |name: &str| headers.get(name).and_then(Value::as_str);
+    if let Some(jwt) = header("authorization").and_then(|value| value.strip_prefix("Bearer ")) {
+        payload.insert("jwt".to_string(), json!(jwt));
+    }
+    if let Some(request_id) = header("x-kuzzle-request-id") {
+        payload.insert("requestId".to_string(), json!(request_id));
+    }
+    if let Some(volatile) =
+        header("x-kuzzle-volatile").and_then(|value| serde_json::from_str(value).ok())
+    {
+        payload.insert("volatile".to_string(), volatile);
+    }
+
+    Ok(Ok(Value::Object(payload)))
+}
+
+/// Writes `response` to `stream`, with its `status` as the HTTP status.
+pub(crate) fn write_response(mut stream: &TcpStream, response: &Value) -> io::Result<()> {
+    let body = response.to_string();
+    let status = response["status"].as_u64().unwrap_or(500);
+
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        reason(status),
+        body.len(),
+        body
+    )?;
+    stream.flush()
+}
+
+/// Returns the route matching `segments`: routes whose path has the most fixed segments
+/// win, so that `/users/_me` is preferred over `/users/:_id`.
+fn find_route<'a>(
+    routes: &'a [HttpRoute],
+    method: &str,
+    segments: &[String],
+) -> Option<&'a HttpRoute> {
+    let mut best: Option<(&HttpRoute, usize)> = None;
+
+    for route in routes {
+        if route.verb != method || route.segments.len() != segments.len() {
+            continue;
+        }
+
+        let mut fixed = 0;
+        let matches = route
+            .segments
+            .iter()
+            .zip(segments)
+            .all(|(expected, segment)| {
+                if expected.starts_with(':') {
+                    true
+                } else {
+                    fixed += 1;
+                    expected == segment
+                }
+            });
+
+        if matches && best.is_none_or(|(_, best_fixed)| fixed > best_fixed) {
+            best = Some((route, fixed));
+        }
+    }
+
+    best.map(|(route, _)| route)
+}
+
+/// Parses a query string value the way Kuzzle does: numbers, booleans and
+/// JSON-encoded objects are converted, anything else is a string.
+fn query_value(value: &str) -> Value {
+    match serde_json::from_str(value) {
+        Ok(Value::String(_)) | Ok(Value::Null) | Err(_) => json!(value),
+        Ok(value) => value,
+    }
+}
+
+/// Decodes a percent-encoded URL component.
+fn decode(component: &str) -> String {
+    let bytes = component.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let escaped = bytes.get(i + 1..i + 3).and_then(|hex| {
+            std::str::from_utf8(hex)
+                .ok()
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+        });
+        match (bytes[i], escaped) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            (b'+', _) => {
+                decoded.push(b' ');
+                i += 1;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+fn reason(status: u64) -> &'static str {
+    match status {
+        200 => "OK",
+        206 => "Partial Content",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        412 => "Precondition Failed",
+        _ => "Internal Server Error",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn find_route_ok() {
+        let routes = routes();
+        let route = |method: &str, path: &str| {
+            let segments: Vec<String> = path
+                .split('/')
+                .filter(|segment| !segment.is_empty())
+                .map(String::from)
+                .collect();
+            find_route(&routes, method, &segments)
+                .map(|route| format!("{}:{}", route.controller, route.action))
+        };
+
+        assert_eq!(
+            route("GET", "/users/_me"),
+            Some("auth:getCurrentUser".to_string())
+        );
+        assert_eq!(
+            route("GET", "/users/ferris"),
+            Some("security:getUser".to_string())
+        );
+        assert_eq!(
+            route("POST", "/nyc-open-data/yellow-taxi/_create"),
+            Some("document:create".to_string())
+        );
+        assert_eq!(
+            route("GET", "/nyc-open-data/yellow-taxi/_mapping"),
+            Some("collection:getMapping".to_string())
+        );
+        assert_eq!(route("GET", "/nowhere/to/be/found"), None);
+    }
+
+    #[test]
+    fn query_value_ok() {
+        assert_eq!(query_value("10"), json!(10));
+        assert_eq!(query_value("true"), json!(true));
+        assert_eq!(query_value("wait_for"), json!("wait_for"));
+        assert_eq!(query_value("null"), json!("null"));
+        assert_eq!(query_value(r#"{"a":1}"#), json!({ "a": 1 }));
+    }
+
+    #[test]
+    fn decode_ok() {
+        assert_eq!(decode("caf%C3%A9+cr%C3%A8me"), "café crème");
+        assert_eq!(decode("100%"), "100%");
+    }
+}
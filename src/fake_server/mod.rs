@@ -0,0 +1,464 @@
+//! An in-process fake Kuzzle server, enabled by the `fake-server` cargo feature.
+//!
+//! `FakeKuzzle` serves a subset of the Kuzzle API over HTTP and WebSocket on a local port,
+//! keeping everything in memory, so that code using `Http` or `Websocket` can be tested
+//! without a Kuzzle server:
+//!
+//! - `index` and `collection` lifecycle, and collection mappings,
+//! - document CRUD with versions, `mCreate`/`mGet`/`mDelete`..., `count` and `search`
+//!   with simple queries (see the `query` module below),
+//! - `ms` strings and hashes,
+//! - `auth:login` with the `local` strategy for users added with `add_user`,
+//!   `checkToken`, `refreshToken`, `getCurrentUser` and `logout`,
+//! - `realtime:subscribe` through WebSocket, with notifications on document changes.
+//!
+//! Requests without a token are allowed, as with a default Kuzzle setup.
+//! Other actions are answered with an `api.process.action_not_found` error.
+//!
+//! # Example
+//!
+//! ```
+//! use kuzzle_sdk::fake_server::FakeKuzzle;
+//! use kuzzle_sdk::kuzzle::Kuzzle;
+//! use kuzzle_sdk::protocols::Websocket;
+//! use kuzzle_sdk::types::QueryOptions;
+//!
+//! let server = FakeKuzzle::new()
+//!     .add_user("ferris", "password")
+//!     .start()
+//!     .unwrap();
+//!
+//! let kuzzle = Kuzzle::new(Websocket::new(server.options()));
+//! kuzzle.connect().unwrap();
+//! kuzzle
+//!     .auth()
+//!     .login("local", "ferris", "password", QueryOptions::new())
+//!     .unwrap();
+//!
+//! assert!(kuzzle.index().list(QueryOptions::new()).unwrap().is_empty());
+//! ```
+
+mod api;
+mod http;
+mod query;
+
+use crate::fake_server::api::Backend;
+use crate::fake_server::http::HttpRoute;
+use crate::types::{Error, KuzzleOptions};
+
+use serde_json::Value;
+use std::io::{self, ErrorKind};
+use std::net::{TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+use tungstenite::Message;
+
+/// How often the server checks for new connections, messages to send and whether it must stop.
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// How long the server waits for the rest of an HTTP request.
+const HTTP_READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Builds a fake Kuzzle server.
+#[derive(Default)]
+pub struct FakeKuzzle {
+    _users: Vec<(String, String)>,
+    _collections: Vec<(String, String)>,
+}
+
+impl FakeKuzzle {
+    pub fn new() -> FakeKuzzle {
+        FakeKuzzle::default()
+    }
+
+    /// Adds a user able to log in with the `local` strategy.
+    /// Its username is also its unique identifier (kuid).
+    pub fn add_user(mut self, username: &str, password: &str) -> Self {
+        self._users
+            .push((username.to_string(), password.to_string()));
+        self
+    }
+
+    /// Creates a collection, and its index if needed, when the server starts.
+    pub fn add_collection(mut self, index: &str, collection: &str) -> Self {
+        self._collections
+            .push((index.to_string(), collection.to_string()));
+        self
+    }
+
+    /// Starts serving HTTP and WebSocket requests on a free local port,
+    /// until the returned handle is stopped or dropped.
+    pub fn start(self) -> Result<FakeKuzzleHandle, Error> {
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        listener.set_nonblocking(true)?;
+        let port = listener.local_addr()?.port();
+
+        let backend = Arc::new(Backend::default());
+        for (username, password) in &self._users {
+            backend.add_user(username, password);
+        }
+        for (index, collection) in &self._collections {
+            backend.add_collection(index, collection);
+        }
+
+        let stop = Arc::new(AtomicBool::new(false));
+        let server = Server {
+            backend,
+            routes: Arc::new(http::routes()),
+            stop: stop.clone(),
+        };
+        let thread = thread::spawn(move || server.accept(listener));
+
+        Ok(FakeKuzzleHandle {
+            _port: port,
+            _stop: stop,
+            _thread: Some(thread),
+        })
+    }
+}
+
+/// A running fake Kuzzle server, stopped when dropped.
+pub struct FakeKuzzleHandle {
+    _port: u16,
+    _stop: Arc<AtomicBool>,
+    _thread: Option<JoinHandle<()>>,
+}
+
+impl FakeKuzzleHandle {
+    pub fn port(&self) -> u16 {
+        self._port
+    }
+
+    /// Returns the options to give to `Http` or `Websocket` to reach the server.
+    pub fn options(&self) -> KuzzleOptions {
+        KuzzleOptions::new("127.0.0.1", self._port as u32)
+    }
+
+    /// Stops the server and closes its connections.
+    pub fn stop(mut self) {
+        self.shutdown();
+    }
+
+    fn shutdown(&mut self) {
+        self._stop.store(true, Ordering::SeqCst);
+        if let Some(thread) = self._thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+impl Drop for FakeKuzzleHandle {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+#[derive(Clone)]
+struct Server {
+    backend: Arc<Backend>,
+    routes: Arc<Vec<HttpRoute>>,
+    stop: Arc<AtomicBool>,
+}
+
+impl Server {
+    fn accept(self, listener: TcpListener) {
+        let mut connections = Vec::new();
+
+        while !self.stop.load(Ordering::SeqCst) {
+            match listener.accept() {
+                Ok((stream, _)) => {
+                    let server = self.clone();
+                    connections.push(thread::spawn(move || {
+                        // The client went away: there is nobody to report the error to
+                        let _ = server.serve(stream);
+                    }));
+                }
+                Err(err) if err.kind() == ErrorKind::WouldBlock => thread::sleep(POLL_INTERVAL),
+                Err(_) => thread::sleep(POLL_INTERVAL),
+            }
+            connections.retain(|connection: &JoinHandle<()>| !connection.is_finished());
+        }
+
+        for connection in connections {
+            let _ = connection.join();
+        }
+    }
+
+    fn serve(&self, stream: TcpStream) -> io::Result<()> {
+        stream.set_nonblocking(false)?;
+        stream.set_read_timeout(Some(HTTP_READ_TIMEOUT))?;
+
+        if is_websocket(&stream)? {
+            self.serve_websocket(stream)
+        } else {
+            self.serve_http(stream)
+        }
+    }
+
+    fn serve_http(&self, stream: TcpStream) -> io::Result<()> {
+        let response = match http::read_request(&stream, &self.routes)? {
+            Ok(payload) => self.backend.handle(&payload, None),
+            Err(error) => serde_json::json!({
+                "requestId": "",
+                "status": error["status"],
+                "error": error,
+                "result": null
+            }),
+        };
+
+        http::write_response(&stream, &response)
+    }
+
+    fn serve_websocket(&self, stream: TcpStream) -> io::Result<()> {
+        let mut socket = tungstenite::accept(stream)
+            .map_err(|err| io::Error::new(ErrorKind::InvalidData, err.to_string()))?;
+        socket.get_ref().set_read_timeout(Some(POLL_INTERVAL))?;
+
+        let (notifier, notifications) = mpsc::channel();
+        let connection = self.backend.connect(notifier);
+        let res = self.websocket_loop(&mut socket, &notifications, connection);
+        self.backend.disconnect(connection);
+
+        res
+    }
+
+    fn websocket_loop(
+        &self,
+        socket: &mut tungstenite::WebSocket<TcpStream>,
+        notifications: &mpsc::Receiver<String>,
+        connection: api::ConnectionId,
+    ) -> io::Result<()> {
+        let to_io = |err: tungstenite::Error| io::Error::other(err.to_string());
+
+        while !self.stop.load(Ordering::SeqCst) {
+            match socket.read() {
+                Ok(Message::Text(text)) => {
+                    let payload: Value = serde_json::from_str(&text).unwrap_or(Value::Null);
+                    let response = self.backend.handle(&payload, Some(connection));
+                    socket
+                        .send(Message::Text(response.to_string()))
+                        .map_err(to_io)?;
+                }
+                Ok(Message::Close(_)) => return Ok(()),
+                Ok(_) => (),
+                Err(tungstenite::Error::Io(err))
+                    if err.kind() == ErrorKind::WouldBlock || err.kind() == ErrorKind::TimedOut => {
+                }
+                Err(err) => return Err(to_io(err)),
+            }
+
+            while let Ok(notification) = notifications.try_recv() {
+                socket.send(Message::Text(notification)).map_err(to_io)?;
+            }
+        }
+
+        let _ = socket.close(None);
+        Ok(())
+    }
+}
+
+/// Returns true if the request waiting on `stream` is a WebSocket handshake.
+fn is_websocket(stream: &TcpStream) -> io::Result<bool> {
+    let mut buffer = [0; 4096];
+    let started = Instant::now();
+
+    loop {
+        let read = stream.peek(&mut buffer)?;
+        let head = String::from_utf8_lossy(&buffer[..read]).to_lowercase();
+        if read == 0 || read == buffer.len() || head.contains("\r\n\r\n") {
+            return Ok(head.contains("upgrade: websocket"));
+        }
+        if started.elapsed() > HTTP_READ_TIMEOUT {
+            return Err(io::Error::new(
+                ErrorKind::TimedOut,
+                "Incomplete request headers",
+            ));
+        }
+        thread::sleep(Duration::from_millis(1));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kuzzle::Kuzzle;
+    use crate::protocols::Websocket;
+    use crate::types::{KuzzleRequest, QueryOptions};
+    use serde_json::json;
+
+    fn connect(server: &FakeKuzzleHandle) -> Kuzzle {
+        let kuzzle = Kuzzle::new(Websocket::new(server.options()));
+        kuzzle.connect().unwrap();
+        kuzzle
+    }
+
+    fn query(kuzzle: &Kuzzle, req: KuzzleRequest) -> Value {
+        let res = kuzzle.query(req, QueryOptions::new()).unwrap();
+        assert_eq!(res.error(), &None);
+        res.result().clone()
+    }
+
+    #[test]
+    fn websocket_document_lifecycle_ok() {
+        let server = FakeKuzzle::new()
+            .add_user("ferris", "password")
+            .add_collection("nyc-open-data", "yellow-taxi")
+            .start()
+            .unwrap();
+        let kuzzle = connect(&server);
+
+        kuzzle
+            .auth()
+            .login("local", "ferris", "password", QueryOptions::new())
+            .unwrap();
+        assert_eq!(
+            kuzzle.index().list(QueryOptions::new()).unwrap(),
+            vec!["nyc-open-data"]
+        );
+
+        let created = query(
+            &kuzzle,
+            KuzzleRequest::new("document", "create")
+                .set_index("nyc-open-data")
+                .set_collection("yellow-taxi")
+                .set_id("taxi-1")
+                .add_to_body("licence", json!("B")),
+        );
+        assert_eq!(created["_version"], json!(1));
+        assert_eq!(
+            created["_source"]["_kuzzle_info"]["author"],
+            json!("ferris")
+        );
+
+        let updated = query(
+            &kuzzle,
+            KuzzleRequest::new("document", "update")
+                .set_index("nyc-open-data")
+                .set_collection("yellow-taxi")
+                .set_id("taxi-1")
+                .add_to_body("seats", json!(4)),
+        );
+        assert_eq!(updated["_version"], json!(2));
+
+        let found = query(
+            &kuzzle,
+            KuzzleRequest::new("document", "search")
+                .set_index("nyc-open-data")
+                .set_collection("yellow-taxi")
+                .add_to_body("query", json!({ "range": { "seats": { "gte": 4 } } })),
+        );
+        assert_eq!(found["total"], json!(1));
+        assert_eq!(found["hits"][0]["_source"]["licence"], json!("B"));
+
+        query(
+            &kuzzle,
+            KuzzleRequest::new("document", "delete")
+                .set_index("nyc-open-data")
+                .set_collection("yellow-taxi")
+                .set_id("taxi-1"),
+        );
+        let res = kuzzle
+            .query(
+                KuzzleRequest::new("document", "get")
+                    .set_index("nyc-open-data")
+                    .set_collection("yellow-taxi")
+                    .set_id("taxi-1"),
+                QueryOptions::new(),
+            )
+            .unwrap();
+        assert_eq!(res.status(), &404);
+        assert_eq!(
+            res.error().as_ref().unwrap().id(),
+            "services.storage.not_found"
+        );
+    }
+
+    #[test]
+    fn websocket_memory_storage_ok() {
+        let server = FakeKuzzle::new().start().unwrap();
+        let kuzzle = connect(&server);
+
+        query(
+            &kuzzle,
+            KuzzleRequest::new("ms", "set")
+                .set_id("counter")
+                .add_to_body("value", json!(41)),
+        );
+        assert_eq!(
+            query(&kuzzle, KuzzleRequest::new("ms", "incr").set_id("counter")),
+            json!(42)
+        );
+        assert_eq!(
+            query(&kuzzle, KuzzleRequest::new("ms", "get").set_id("counter")),
+            json!("42")
+        );
+    }
+
+    #[test]
+    fn http_request_ok() {
+        let server = FakeKuzzle::new()
+            .add_user("ferris", "password")
+            .start()
+            .unwrap();
+        let url = format!("http://127.0.0.1:{}", server.port());
+        let client = reqwest::blocking::Client::new();
+
+        let res: Value = client
+            .post(format!("{}/_login/local", url))
+            .json(&json!({ "username": "ferris", "password": "password" }))
+            .send()
+            .unwrap()
+            .json()
+            .unwrap();
+        let jwt = res["result"]["jwt"].as_str().unwrap().to_string();
+
+        let res: Value = client
+            .get(format!("{}/users/_me", url))
+            .bearer_auth(&jwt)
+            .send()
+            .unwrap()
+            .json()
+            .unwrap();
+        assert_eq!(res["result"]["_id"], json!("ferris"));
+
+        let res = client.get(format!("{}/nowhere", url)).send().unwrap();
+        assert_eq!(res.status().as_u16(), 404);
+    }
+
+    #[test]
+    fn realtime_notification_ok() {
+        let server = FakeKuzzle::new()
+            .add_collection("nyc-open-data", "yellow-taxi")
+            .start()
+            .unwrap();
+        let kuzzle = connect(&server);
+
+        let ws = Websocket::new(server.options());
+        let notifications = ws.notifications();
+        let subscriber = Kuzzle::new(ws);
+        subscriber.connect().unwrap();
+        let subscribed = query(
+            &subscriber,
+            KuzzleRequest::new("realtime", "subscribe")
+                .set_index("nyc-open-data")
+                .set_collection("yellow-taxi")
+                .add_to_body("equals", json!({ "licence": "B" })),
+        );
+
+        query(
+            &kuzzle,
+            KuzzleRequest::new("document", "create")
+                .set_index("nyc-open-data")
+                .set_collection("yellow-taxi")
+                .set_id("taxi-1")
+                .add_to_body("licence", json!("B")),
+        );
+
+        let notification = notifications.recv_timeout(Duration::from_secs(1)).unwrap();
+        assert_eq!(notification.room_id(), subscribed["channel"]);
+        assert_eq!(notification.action(), "create");
+        assert_eq!(notification.result()["_id"], json!("taxi-1"));
+    }
+}
@@ -0,0 +1,413 @@
+//! Matching of documents against search queries and realtime filters.
+//!
+//! Only the most common clauses are supported: search queries can use
+//! `match_all`, `ids`, `term`, `terms`, `match`, `range`, `exists` and `bool`,
+//! realtime filters `equals`, `in`, `ids`, `range`, `exists`, `and`, `or`, `not` and `bool`.
+
+use serde_json::{Map, Value};
+use std::cmp::Ordering;
+
+/// Returns the value at the dotted `path` of `source`.
+pub(crate) fn field<'a>(source: &'a Map<String, Value>, path: &str) -> Option<&'a Value> {
+    let mut keys = path.split('.');
+    let first = source.get(keys.next()?)?;
+    keys.try_fold(first, |value, key| value.get(key))
+}
+
+/// Returns true if the document matches the search `query`.
+pub(crate) fn matches_query(
+    id: &str,
+    source: &Map<String, Value>,
+    query: &Value,
+) -> Result<bool, String> {
+    let (kind, clause) = match single_key(query)? {
+        Some(clause) => clause,
+        None => return Ok(true),
+    };
+
+    match kind {
+        "match_all" => Ok(true),
+        "bool" => matches_bool(id, source, clause, &matches_query),
+        "ids" => Ok(ids(clause)?.iter().any(|value| value == id)),
+        "exists" => Ok(exists(source, clause)?),
+        "term" => {
+            let (path, expected) = field_clause(clause, "value")?;
+            Ok(values(source, path).any(|value| value == expected))
+        }
+        "terms" => {
+            let (path, expected) = field_clause(clause, "value")?;
+            let expected = expected
+                .as_array()
+                .ok_or("terms expects an array of values")?;
+            Ok(values(source, path).any(|value| expected.contains(value)))
+        }
+        "match" => {
+            let (path, expected) = field_clause(clause, "query")?;
+            Ok(values(source, path).any(|value| text_matches(value, expected)))
+        }
+        "range" => matches_range(source, clause),
+        kind => Err(format!("Unsupported query clause \"{}\"", kind)),
+    }
+}
+
+/// Returns true if the document matches the realtime `filters`.
+pub(crate) fn matches_filters(
+    id: &str,
+    source: &Map<String, Value>,
+    filters: &Value,
+) -> Result<bool, String> {
+    let (kind, clause) = match single_key(filters)? {
+        Some(clause) => clause,
+        None => return Ok(true),
+    };
+
+    match kind {
+        "bool" => matches_bool(id, source, clause, &matches_filters),
+        "and" => all(id, source, clause, &matches_filters),
+        "or" => any(id, source, clause, &matches_filters),
+        "not" => Ok(!matches_filters(id, source, clause)?),
+        "ids" => Ok(ids(clause)?.iter().any(|value| value == id)),
+        "exists" => Ok(exists(source, clause)?),
+        "equals" => {
+            let (path, expected) = field_clause(clause, "value")?;
+            Ok(values(source, path).any(|value| value == expected))
+        }
+        "in" => {
+            let (path, expected) = field_clause(clause, "value")?;
+            let expected = expected.as_array().ok_or("in expects an array of values")?;
+            Ok(values(source, path).any(|value| expected.contains(value)))
+        }
+        "range" => matches_range(source, clause),
+        kind => Err(format!("Unsupported filter \"{}\"", kind)),
+    }
+}
+
+/// Sorts documents with an Elasticsearch `sort`: a field name, or a list of
+/// field names and `{ "<field>": "asc" | "desc" }` objects.
+pub(crate) fn sort(hits: &mut [(String, Map<String, Value>)], sort: &Value) {
+    let mut criteria: Vec<(String, bool)> = Vec::new();
+    let items = match sort {
+        Value::Array(items) => items.clone(),
+        Value::Null => Vec::new(),
+        sort => vec![sort.clone()],
+    };
+
+    for item in items {
+        match item {
+            Value::String(path) => criteria.push((path, false)),
+            Value::Object(fields) => {
+                for (path, order) in fields {
+                    let order = order.get("order").unwrap_or(&order).clone();
+                    criteria.push((path, order == "desc"));
+                }
+            }
+            _ => (),
+        }
+    }
+
+    hits.sort_by(|(a_id, a), (b_id, b)| {
+        for (path, descending) in &criteria {
+            let ordering = if path == "_id" {
+                order(a_id.cmp(b_id), *descending)
+            } else {
+                compare_fields(field(a, path), field(b, path), *descending)
+            };
+            if ordering != Ordering::Equal {
+                return ordering;
+            }
+        }
+        Ordering::Equal
+    });
+}
+
+type Matcher = dyn Fn(&str, &Map<String, Value>, &Value) -> Result<bool, String>;
+
+/// Returns the only key of `clause` and its value, or `None` for an empty clause.
+fn single_key(clause: &Value) -> Result<Option<(&str, &Value)>, String> {
+    match clause {
+        Value::Null => Ok(None),
+        Value::Object(clause) if clause.is_empty() => Ok(None),
+        Value::Object(clause) if clause.len() == 1 => Ok(clause
+            .iter()
+            .next()
+            .map(|(kind, clause)| (kind.as_str(), clause))),
+        clause => Err(format!("Invalid clause {}", clause)),
+    }
+}
+
+/// Splits `{ "<field>": <value> }` or `{ "<field>": { "<key>": <value> } }`.
+fn field_clause<'a>(clause: &'a Value, key: &str) -> Result<(&'a str, &'a Value), String> {
+    match single_key(clause)? {
+        Some((path, Value::Object(options))) if options.contains_key(key) => {
+            Ok((path, &options[key]))
+        }
+        Some((path, value)) => Ok((path, value)),
+        None => Err("Missing field name".to_string()),
+    }
+}
+
+/// Returns the values of a field, each item of an array field being a value.
+fn values<'a>(
+    source: &'a Map<String, Value>,
+    path: &str,
+) -> Box<dyn Iterator<Item = &'a Value> + 'a> {
+    match field(source, path) {
+        Some(Value::Array(items)) => Box::new(items.iter()),
+        Some(value) => Box::new(std::iter::once(value)),
+        None => Box::new(std::iter::empty()),
+    }
+}
+
+fn ids(clause: &Value) -> Result<&Vec<Value>, String> {
+    clause
+        .get("values")
+        .and_then(Value::as_array)
+        .ok_or_else(|| "ids expects a \"values\" array".to_string())
+}
+
+fn exists(source: &Map<String, Value>, clause: &Value) -> Result<bool, String> {
+    let path = clause
+        .get("field")
+        .unwrap_or(clause)
+        .as_str()
+        .ok_or("exists expects a field name")?;
+    Ok(field(source, path).is_some_and(|value| !value.is_null()))
+}
+
+/// Full text matching, reduced to every word of `expected` being a word of `value`.
+fn text_matches(value: &Value, expected: &Value) -> bool {
+    match (value, expected) {
+        (Value::String(value), Value::String(expected)) => {
+            let words: Vec<String> = value.split_whitespace().map(str::to_lowercase).collect();
+            expected
+                .split_whitespace()
+                .all(|word| words.contains(&word.to_lowercase()))
+        }
+        (value, expected) => value == expected,
+    }
+}
+
+fn matches_range(source: &Map<String, Value>, clause: &Value) -> Result<bool, String> {
+    let (path, bounds) = match single_key(clause)? {
+        Some((path, Value::Object(bounds))) => (path, bounds),
+        _ => return Err("range expects bounds for a field".to_string()),
+    };
+
+    Ok(values(source, path).any(|value| {
+        bounds.iter().all(|(bound, limit)| {
+            let ordering = compare(value, limit);
+            match bound.as_str() {
+                "gt" => ordering == Some(Ordering::Greater),
+                "gte" => ordering.is_some_and(|ordering| ordering != Ordering::Less),
+                "lt" => ordering == Some(Ordering::Less),
+                "lte" => ordering.is_some_and(|ordering| ordering != Ordering::Greater),
+                _ => true,
+            }
+        })
+    }))
+}
+
+fn matches_bool(
+    id: &str,
+    source: &Map<String, Value>,
+    clause: &Value,
+    matches: &Matcher,
+) -> Result<bool, String> {
+    let clauses = |name: &str| clause.get(name).cloned().unwrap_or(Value::Null);
+
+    if !all(id, source, &clauses("must"), matches)?
+        || !all(id, source, &clauses("filter"), matches)?
+        || any(id, source, &clauses("must_not"), matches)?
+    {
+        return Ok(false);
+    }
+
+    match clauses("should") {
+        Value::Null => Ok(true),
+        Value::Array(should) if should.is_empty() => Ok(true),
+        should => any(id, source, &should, matches),
+    }
+}
+
+/// Returns true if the document matches every clause: `clauses` is either a clause or a list of them.
+fn all(
+    id: &str,
+    source: &Map<String, Value>,
+    clauses: &Value,
+    matches: &Matcher,
+) -> Result<bool, String> {
+    match clauses {
+        Value::Null => Ok(true),
+        Value::Array(clauses) => {
+            for clause in clauses {
+                if !matches(id, source, clause)? {
+                    return Ok(false);
+                }
+            }
+            Ok(true)
+        }
+        clause => matches(id, source, clause),
+    }
+}
+
+/// Returns true if the document matches at least one clause.
+fn any(
+    id: &str,
+    source: &Map<String, Value>,
+    clauses: &Value,
+    matches: &Matcher,
+) -> Result<bool, String> {
+    match clauses {
+        Value::Null => Ok(false),
+        Value::Array(clauses) => {
+            for clause in clauses {
+                if matches(id, source, clause)? {
+                    return Ok(true);
+                }
+            }
+            Ok(false)
+        }
+        clause => matches(id, source, clause),
+    }
+}
+
+/// Compares numbers with numbers and strings with strings.
+fn compare(a: &Value, b: &Value) -> Option<Ordering> {
+    match (a, b) {
+        (Value::Number(a), Value::Number(b)) => a.as_f64()?.partial_cmp(&b.as_f64()?),
+        (Value::String(a), Value::String(b)) => Some(a.cmp(b)),
+        (Value::Bool(a), Value::Bool(b)) => Some(a.cmp(b)),
+        _ => None,
+    }
+}
+
+fn order(ordering: Ordering, descending: bool) -> Ordering {
+    if descending {
+        ordering.reverse()
+    } else {
+        ordering
+    }
+}
+
+/// Orders missing values last, whatever the sort order.
+fn compare_fields(a: Option<&Value>, b: Option<&Value>, descending: bool) -> Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) => order(compare(a, b).unwrap_or(Ordering::Equal), descending),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn source() -> Map<String, Value> {
+        json!({
+            "name": "Ferris the crab",
+            "age": 7,
+            "tags": ["rust", "crab"],
+            "address": { "city": "Montpellier" }
+        })
+        .as_object()
+        .unwrap()
+        .clone()
+    }
+
+    #[test]
+    fn matches_query_ok() {
+        let source = source();
+        let matches = |query: Value| matches_query("ferris", &source, &query).unwrap();
+
+        assert!(matches(json!({})));
+        assert!(matches(json!({ "match_all": {} })));
+        assert!(matches(json!({ "ids": { "values": ["ferris"] } })));
+        assert!(matches(
+            json!({ "term": { "address.city": "Montpellier" } })
+        ));
+        assert!(matches(json!({ "term": { "tags": { "value": "rust" } } })));
+        assert!(matches(json!({ "terms": { "age": [6, 7] } })));
+        assert!(matches(json!({ "match": { "name": "the FERRIS" } })));
+        assert!(matches(
+            json!({ "range": { "age": { "gte": 7, "lt": 10 } } })
+        ));
+        assert!(matches(json!({ "exists": { "field": "address.city" } })));
+        assert!(matches(json!({
+            "bool": {
+                "must": { "term": { "tags": "crab" } },
+                "must_not": [{ "term": { "age": 8 } }],
+                "should": [{ "term": { "age": 1 } }, { "match": { "name": "crab" } }]
+            }
+        })));
+
+        assert!(!matches(json!({ "term": { "name": "Ferris" } })));
+        assert!(!matches(json!({ "range": { "age": { "gt": 7 } } })));
+        assert!(!matches(json!({ "exists": { "field": "email" } })));
+        assert!(!matches(
+            json!({ "bool": { "should": [{ "term": { "age": 1 } }] } })
+        ));
+    }
+
+    #[test]
+    fn matches_query_fail_unsupported() {
+        let res = matches_query(
+            "ferris",
+            &source(),
+            &json!({ "wildcard": { "name": "F*" } }),
+        );
+
+        assert!(res.unwrap_err().contains("wildcard"));
+    }
+
+    #[test]
+    fn matches_filters_ok() {
+        let source = source();
+        let matches = |filters: Value| matches_filters("ferris", &source, &filters).unwrap();
+
+        assert!(matches(json!({})));
+        assert!(matches(
+            json!({ "equals": { "address.city": "Montpellier" } })
+        ));
+        assert!(matches(json!({ "in": { "tags": ["go", "rust"] } })));
+        assert!(matches(json!({ "exists": "name" })));
+        assert!(matches(json!({
+            "and": [
+                { "range": { "age": { "lte": 7 } } },
+                { "not": { "equals": { "name": "Corro" } } }
+            ]
+        })));
+        assert!(matches(
+            json!({ "or": [{ "equals": { "age": 1 } }, { "ids": { "values": ["ferris"] } }] })
+        ));
+
+        assert!(!matches(json!({ "equals": { "age": "7" } })));
+        assert!(!matches(json!({ "not": { "exists": { "field": "age" } } })));
+    }
+
+    #[test]
+    fn sort_ok() {
+        let document = |id: &str, age: Option<u64>| {
+            let mut source = Map::new();
+            if let Some(age) = age {
+                source.insert("age".to_string(), json!(age));
+            }
+            (id.to_string(), source)
+        };
+        let mut hits = vec![
+            document("a", Some(3)),
+            document("b", None),
+            document("c", Some(5)),
+            document("d", Some(3)),
+        ];
+
+        sort(
+            &mut hits,
+            &json!([{ "age": { "order": "desc" } }, { "_id": "desc" }]),
+        );
+
+        let ids: Vec<&str> = hits.iter().map(|(id, _)| id.as_str()).collect();
+        assert_eq!(ids, vec!["c", "d", "a", "b"]);
+    }
+}
@@ -1,12 +1,21 @@
 use crate::controllers::*;
+use crate::middleware::{Middleware, Next};
 use crate::protocols::Protocol;
-use crate::types::{KuzzleRequest, KuzzleResponse, QueryOptions};
-use std::error::Error;
+use crate::token_store::{stored_session, TokenStore};
+use crate::types::{Error, KuzzleRequest, KuzzleResponse, QueryOptions, Session};
+
+use std::sync::{Arc, PoisonError, RwLock, RwLockReadGuard};
 
 /// Kuzzle is the Kuzzle SDK client used to dial with the Kuzzle server.
+///
+/// Clones are cheap and share the same connection and JWT:
+/// a client can be cloned to send concurrent requests from several threads.
+#[derive(Clone)]
 pub struct Kuzzle {
-    _protocol: Box<Protocol>,
-    _jwt: Option<String>,
+    _protocol: Arc<RwLock<Box<dyn Protocol>>>,
+    _session: Arc<RwLock<Option<Session>>>,
+    _token_store: Option<Arc<dyn TokenStore>>,
+    _middlewares: Arc<Vec<Arc<dyn Middleware>>>,
 }
 
 impl Kuzzle {
@@ -30,87 +39,280 @@ impl Kuzzle {
         P: 'static + Protocol,
     {
         Kuzzle {
-            _protocol: Box::new(protocol),
-            _jwt: None,
+            _protocol: Arc::new(RwLock::new(Box::new(protocol))),
+            _session: Arc::new(RwLock::new(None)),
+            _token_store: None,
+            _middlewares: Arc::new(Vec::new()),
         }
     }
 
-    pub fn connect(&mut self) -> Result<(), Box<Error>> {
-        if self._protocol.is_ready() {
+    /// Returns a builder of clients using `protocol`, to set up their token store,
+    /// API key or middlewares.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::token_store::MemoryTokenStore;
+    /// use kuzzle_sdk::types::KuzzleOptions;
+    ///
+    /// let _kuzzle = Kuzzle::builder(Http::new(KuzzleOptions::new("localhost", 7512)))
+    ///     .token_store(MemoryTokenStore::new())
+    ///     .build();
+    /// ```
+    pub fn builder<P>(protocol: P) -> KuzzleBuilder
+    where
+        P: 'static + Protocol,
+    {
+        KuzzleBuilder {
+            _kuzzle: Kuzzle::new(protocol),
+            _middlewares: Vec::new(),
+        }
+    }
+
+    /// Returns a client authenticated with an API key, for services which
+    /// must not store a password. API keys are created with
+    /// `auth().create_api_key` or `security().create_api_key`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::KuzzleOptions;
+    ///
+    /// let _kuzzle = Kuzzle::with_api_key(
+    ///     Http::new(KuzzleOptions::new("localhost", 7512)),
+    ///     "anApiKeyToken",
+    /// );
+    /// ```
+    pub fn with_api_key<P>(protocol: P, api_key: &str) -> Kuzzle
+    where
+        P: 'static + Protocol,
+    {
+        Kuzzle::builder(protocol).api_key(api_key).build()
+    }
+
+    /// Returns a client saving its authentication session to `store`
+    /// whenever it changes. A session saved by a previous run of the
+    /// program is restored with `restore_session`.
+    ///
+    /// Failing to save the session doesn't fail the login or logout
+    /// which changed it: the session is just not restored next time.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::token_store::MemoryTokenStore;
+    /// use kuzzle_sdk::types::KuzzleOptions;
+    ///
+    /// let _kuzzle = Kuzzle::with_token_store(
+    ///     Http::new(KuzzleOptions::new("localhost", 7512)),
+    ///     MemoryTokenStore::new(),
+    /// );
+    /// ```
+    pub fn with_token_store<P, S>(protocol: P, store: S) -> Kuzzle
+    where
+        P: 'static + Protocol,
+        S: 'static + TokenStore,
+    {
+        Kuzzle::builder(protocol).token_store(store).build()
+    }
+
+    pub fn connect(&self) -> Result<(), Error> {
+        let mut protocol = self
+            ._protocol
+            .write()
+            .unwrap_or_else(PoisonError::into_inner);
+        if protocol.is_ready() {
             return Ok(());
         }
 
-        self._protocol.connect()
+        protocol.connect()
     }
 
     /// Execute the given KuzzleRequest and returns a `Result` which contains
     /// `KuzzleResponse` if execute was ok or a `KuzzleError` else.
+    ///
+    /// The request goes through the client middlewares before being sent.
     pub fn query(
         &self,
         req: KuzzleRequest,
         options: QueryOptions,
-    ) -> Result<KuzzleResponse, Box<Error>> {
-        let request = match &self._jwt {
-            Some(jwt) => req.clone().set_jwt(jwt.to_string()),
-            None => req.clone(),
+    ) -> Result<KuzzleResponse, Error> {
+        let request = match options.request_jwt(self.jwt()) {
+            jwt if jwt.is_empty() => req,
+            jwt => req.set_jwt(jwt),
         };
+        let request = options.apply(request);
 
-        self._protocol.send(request, options)
+        let protocol = self.protocol();
+        Next::new(protocol.as_ref(), &self._middlewares).run(request, options)
+    }
 
+    fn protocol(&self) -> RwLockReadGuard<'_, Box<dyn Protocol>> {
+        self._protocol
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Sends a controller action and reads its response.
+    pub(crate) fn call<T>(&self, call: Call<T>, options: QueryOptions) -> Result<T, Error> {
+        call.check_login(&options.request_jwt(self.jwt()))?;
+        (call.parse)(self.query(call.request, options)?)
     }
 
     /// Kuzzle JWT getter
     pub fn jwt(&self) -> String {
-        self._jwt.clone().unwrap_or(String::new())
+        self.session()
+            .map(|session| session.jwt())
+            .unwrap_or_default()
     }
 
-    /// Kuzzle JWT setter
-    pub fn set_jwt(&mut self, jwt: String) {
-        self._jwt = Some(jwt);
+    /// Kuzzle JWT setter, shared by all clones of this client.
+    /// The token expiration is unknown: use `set_session` to keep track of it.
+    pub fn set_jwt(&self, jwt: String) {
+        self.set_session(Some(Session::new(&jwt)));
+    }
+
+    /// Returns the current authentication session, if any.
+    pub fn session(&self) -> Option<Session> {
+        self._session
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Replaces the authentication session, shared by all clones of this client,
+    /// and saves it to the token store if any.
+    pub fn set_session(&self, session: Option<Session>) {
+        if let Some(store) = &self._token_store {
+            let _ = store.save(session.as_ref());
+        }
+
+        self.replace_session(session);
+    }
+
+    /// Replaces the authentication session without saving it to the token store.
+    fn replace_session(&self, session: Option<Session>) {
+        *self
+            ._session
+            .write()
+            .unwrap_or_else(PoisonError::into_inner) = session;
+    }
+
+    /// Restores the session saved to the token store, if its token is still
+    /// valid according to `auth:checkToken`. Returns false if there is no such
+    /// session, in which case the user must log in again.
+    ///
+    /// Sessions whose token is no longer valid are removed from the store.
+    pub fn restore_session(&self, options: QueryOptions) -> Result<bool, Error> {
+        let store = match &self._token_store {
+            Some(store) => store,
+            None => return Ok(false),
+        };
+        let session = match stored_session(store.as_ref())? {
+            Some(session) => session,
+            None => return Ok(false),
+        };
+
+        let validity = self.auth().check_token(&session.jwt(), options)?;
+        if !validity.valid() {
+            store.save(None)?;
+            return Ok(false);
+        }
+
+        self.set_session(Some(session));
+        Ok(true)
     }
 
     /// Kuzzle AuthController's getter
-    pub fn auth(&mut self) -> AuthController {
+    pub fn auth(&self) -> AuthController<'_> {
         AuthController(self)
     }
 
     /// Kuzzle BulkController's getter
-    pub fn bulk(&mut self) -> BulkController {
+    pub fn bulk(&self) -> BulkController<'_> {
         BulkController(self)
     }
 
     /// Kuzzle CollectionController's getter
-    pub fn collection(&mut self) -> CollectionController {
+    pub fn collection(&self) -> CollectionController<'_> {
         CollectionController(self)
     }
 
     /// Kuzzle DocumentController's getter
-    pub fn document(&mut self) -> DocumentController {
+    pub fn document(&self) -> DocumentController<'_> {
         DocumentController(self)
     }
 
     /// Kuzzle IndexController's getter
-    pub fn index(&mut self) -> IndexController {
+    pub fn index(&self) -> IndexController<'_> {
         IndexController(self)
     }
 
     /// Kuzzle MemoryStorageController's getter
-    pub fn ms(&mut self) -> MemoryStorageController {
+    pub fn ms(&self) -> MemoryStorageController<'_> {
         MemoryStorageController(self)
     }
 
     /// Kuzzle RealtimeController's getter
-    pub fn realtime(&mut self) -> RealtimeController {
+    pub fn realtime(&self) -> RealtimeController<'_> {
         RealtimeController(self)
     }
 
     /// Kuzzle SecurityController's getter
-    pub fn security(&mut self) -> SecurityController {
+    pub fn security(&self) -> SecurityController<'_> {
         SecurityController(self)
     }
 
     /// Kuzzle ServerController's getter
-    pub fn server(&mut self) -> ServerController {
+    pub fn server(&self) -> ServerController<'_> {
         ServerController(self)
     }
 }
+
+/// Builds a `Kuzzle` client, see `Kuzzle::builder`.
+pub struct KuzzleBuilder {
+    _kuzzle: Kuzzle,
+    _middlewares: Vec<Arc<dyn Middleware>>,
+}
+
+impl KuzzleBuilder {
+    /// Authenticates the client with an API key, see `Kuzzle::with_api_key`.
+    /// The key is never saved to the token store.
+    pub fn api_key(self, api_key: &str) -> Self {
+        self._kuzzle.replace_session(Some(Session::new(api_key)));
+        self
+    }
+
+    /// Saves the client session to `store`, see `Kuzzle::with_token_store`.
+    pub fn token_store<S>(mut self, store: S) -> Self
+    where
+        S: 'static + TokenStore,
+    {
+        self._kuzzle._token_store = Some(Arc::new(store));
+        self
+    }
+
+    /// Adds a middleware around the requests sent by the client.
+    /// Middlewares see requests in the order they are added,
+    /// and responses in the reverse order.
+    pub fn middleware<M>(mut self, middleware: M) -> Self
+    where
+        M: 'static + Middleware,
+    {
+        self._middlewares.push(Arc::new(middleware));
+        self
+    }
+
+    pub fn build(self) -> Kuzzle {
+        Kuzzle {
+            _middlewares: Arc::new(self._middlewares),
+            ..self._kuzzle
+        }
+    }
+}
@@ -6,6 +6,12 @@
 //!
 //! If you're just starting with Kuzzle, check the product [website](https://kuzzle.io)
 //! and [documentation](https://docs.kuzzle.io)
+//!
+//! ## Cargo features
+//!
+//! - `async`: adds the `nonblocking` module, an async API running on tokio.
+//! - `fake-server`: adds the `fake_server` module, an in-process fake Kuzzle server for tests.
+//! - `prometheus`: exports Kuzzle server statistics in the Prometheus text format.
 
 #[macro_use]
 extern crate serde_derive;
@@ -13,6 +19,18 @@ extern crate serde_json;
 
 pub mod controllers;
 pub mod event_emitter;
+#[cfg(feature = "fake-server")]
+pub mod fake_server;
 pub mod kuzzle;
+pub mod middleware;
+#[cfg(feature = "async")]
+pub mod nonblocking;
+#[cfg(feature = "prometheus")]
+pub mod prometheus;
 pub mod protocols;
+pub mod token_refresher;
+pub mod token_store;
+pub mod transfer;
 pub mod types;
+
+pub use crate::types::Error;
@@ -0,0 +1,214 @@
+//! Hooks around the requests sent by a `Kuzzle` client.
+//!
+//! A `Middleware` receives each request before it reaches the protocol, and the
+//! response or error coming back: it can add volatile data or tracing identifiers
+//! to requests, record metrics, retry failed requests or map errors, whatever
+//! the protocol used. Middlewares are registered with `KuzzleBuilder::middleware`
+//! and run in registration order, each one calling the next with `Next::run`.
+//!
+//! # Example
+//!
+//! ```
+//! use kuzzle_sdk::kuzzle::Kuzzle;
+//! use kuzzle_sdk::middleware::{Middleware, Next};
+//! use kuzzle_sdk::protocols::Http;
+//! use kuzzle_sdk::types::{Error, KuzzleOptions, KuzzleRequest, KuzzleResponse, QueryOptions};
+//! use serde_json::json;
+//!
+//! /// Tells Kuzzle which application sent each request.
+//! struct Tag(&'static str);
+//!
+//! impl Middleware for Tag {
+//!     fn handle(
+//!         &self,
+//!         req: KuzzleRequest,
+//!         options: QueryOptions,
+//!         next: &Next<'_>,
+//!     ) -> Result<KuzzleResponse, Error> {
+//!         next.run(req.add_to_volatile("application", json!(self.0)), options)
+//!     }
+//! }
+//!
+//! let _kuzzle = Kuzzle::builder(Http::new(KuzzleOptions::new("localhost", 7512)))
+//!     .middleware(Tag("billing"))
+//!     .build();
+//! ```
+
+use crate::protocols::Protocol;
+use crate::types::{Error, KuzzleRequest, KuzzleResponse, QueryOptions};
+
+use std::sync::Arc;
+
+/// A hook around the requests sent by a `Kuzzle` client.
+pub trait Middleware: Send + Sync {
+    /// Handles `req`, usually by passing it, modified or not, to `next.run`
+    /// and returning the result, modified or not.
+    ///
+    /// `next.run` may be called several times, to retry a request,
+    /// or not at all, to answer it without sending it.
+    fn handle(
+        &self,
+        req: KuzzleRequest,
+        options: QueryOptions,
+        next: &Next<'_>,
+    ) -> Result<KuzzleResponse, Error>;
+}
+
+impl<M: Middleware + ?Sized> Middleware for Arc<M> {
+    fn handle(
+        &self,
+        req: KuzzleRequest,
+        options: QueryOptions,
+        next: &Next<'_>,
+    ) -> Result<KuzzleResponse, Error> {
+        (**self).handle(req, options, next)
+    }
+}
+
+/// The rest of the middleware chain, ending with the protocol.
+#[derive(Clone, Copy)]
+pub struct Next<'a> {
+    _protocol: &'a dyn Protocol,
+    _middlewares: &'a [Arc<dyn Middleware>],
+}
+
+impl<'a> Next<'a> {
+    pub(crate) fn new(protocol: &'a dyn Protocol, middlewares: &'a [Arc<dyn Middleware>]) -> Self {
+        Next {
+            _protocol: protocol,
+            _middlewares: middlewares,
+        }
+    }
+
+    /// Passes `req` to the next middleware, or sends it if there is none left.
+    pub fn run(&self, req: KuzzleRequest, options: QueryOptions) -> Result<KuzzleResponse, Error> {
+        match self._middlewares.split_first() {
+            Some((middleware, rest)) => {
+                middleware.handle(req, options, &Next::new(self._protocol, rest))
+            }
+            None => self._protocol.send(req, options),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kuzzle::Kuzzle;
+    use crate::protocols::Mock;
+    use serde_json::json;
+    use std::sync::Mutex;
+
+    /// Records the middlewares a request went through in its volatile data.
+    struct Trace(&'static str);
+
+    impl Middleware for Trace {
+        fn handle(
+            &self,
+            req: KuzzleRequest,
+            options: QueryOptions,
+            next: &Next<'_>,
+        ) -> Result<KuzzleResponse, Error> {
+            let mut trace = req
+                .volatile()
+                .get("trace")
+                .cloned()
+                .unwrap_or_else(|| json!([]));
+            trace.as_array_mut().unwrap().push(json!(self.0));
+            next.run(req.add_to_volatile("trace", trace), options)
+        }
+    }
+
+    /// Retries requests failing with a network error.
+    struct Retry {
+        attempts: usize,
+        errors: Mutex<Vec<String>>,
+    }
+
+    impl Middleware for Retry {
+        fn handle(
+            &self,
+            req: KuzzleRequest,
+            options: QueryOptions,
+            next: &Next<'_>,
+        ) -> Result<KuzzleResponse, Error> {
+            let mut attempt = 1;
+            loop {
+                match next.run(req.clone(), options.clone()) {
+                    Err(Error::Network(err)) if attempt < self.attempts => {
+                        self.errors.lock().unwrap().push(err.to_string());
+                        attempt += 1;
+                    }
+                    res => return res,
+                }
+            }
+        }
+    }
+
+    /// Answers every request without sending it.
+    struct Offline;
+
+    impl Middleware for Offline {
+        fn handle(
+            &self,
+            _req: KuzzleRequest,
+            _options: QueryOptions,
+            _next: &Next<'_>,
+        ) -> Result<KuzzleResponse, Error> {
+            Err(Error::NotConnected)
+        }
+    }
+
+    #[test]
+    fn middlewares_ok_in_order() {
+        let mock = Mock::new();
+        mock.when("server", "now").respond(json!({ "now": 1 }));
+
+        let kuzzle = Kuzzle::builder(mock.clone())
+            .middleware(Trace("first"))
+            .middleware(Trace("second"))
+            .build();
+        kuzzle.connect().unwrap();
+        kuzzle.server().now(QueryOptions::new()).unwrap();
+
+        let calls = mock.calls();
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].volatile()["trace"], json!(["first", "second"]));
+    }
+
+    #[test]
+    fn middleware_ok_retry() {
+        let mock = Mock::new();
+        mock.when("server", "now").respond(json!({ "now": 1 }));
+        mock.when("server", "now")
+            .once()
+            .fail(|| Error::Network("Connection reset".into()));
+
+        let retry = Arc::new(Retry {
+            attempts: 2,
+            errors: Mutex::new(Vec::new()),
+        });
+        let kuzzle = Kuzzle::builder(mock.clone())
+            .middleware(retry.clone())
+            .build();
+        kuzzle.connect().unwrap();
+
+        assert_eq!(kuzzle.server().now(QueryOptions::new()).unwrap(), 1);
+        assert_eq!(mock.calls_to("server", "now").len(), 2);
+        assert_eq!(*retry.errors.lock().unwrap(), vec!["Connection reset"]);
+    }
+
+    #[test]
+    fn middleware_ok_short_circuit() {
+        let mock = Mock::new();
+        let kuzzle = Kuzzle::builder(mock.clone())
+            .middleware(Offline)
+            .middleware(Trace("unreachable"))
+            .build();
+        kuzzle.connect().unwrap();
+
+        let res = kuzzle.server().now(QueryOptions::new());
+        assert!(matches!(res, Err(Error::NotConnected)));
+        assert!(mock.calls().is_empty());
+    }
+}
@@ -0,0 +1,286 @@
+use crate::controllers::server::actions as server;
+use crate::nonblocking::Protocol;
+use crate::protocols::cluster::{
+    is_idempotent, is_network_error, node_options, unhealthy, ResubscribedListener, Subscriptions,
+    HEALTH_CHECK_TIMEOUT,
+};
+use crate::types::{
+    Error, HealthStatus, KuzzleOptions, KuzzleRequest, KuzzleResponse, QueryOptions,
+};
+
+use async_trait::async_trait;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use tokio::sync::{Mutex, RwLock};
+
+/// Sends requests to a Kuzzle cluster, through one connection per node.
+/// See `protocols::Cluster`.
+pub struct Cluster {
+    _nodes: Vec<Node>,
+    _current: AtomicUsize,
+    _failover: Mutex<()>,
+    _subscriptions: Subscriptions,
+    _auto_resubscribe: bool,
+    _on_resubscribed: Option<ResubscribedListener>,
+}
+
+struct Node {
+    _address: String,
+    _protocol: RwLock<Box<dyn Protocol>>,
+}
+
+impl Cluster {
+    /// Returns a cluster client connecting to every node of `options.nodes()`,
+    /// with the protocol built by `protocol`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::nonblocking::{Cluster, Kuzzle, Websocket};
+    /// use kuzzle_sdk::types::KuzzleOptions;
+    ///
+    /// let options = KuzzleOptions::new("kuzzle-1", 7512).add_node("kuzzle-2", 7512);
+    /// let _kuzzle = Kuzzle::new(Cluster::new(options, Websocket::new));
+    /// ```
+    pub fn new<P, F>(options: KuzzleOptions, protocol: F) -> Cluster
+    where
+        P: 'static + Protocol,
+        F: Fn(KuzzleOptions) -> P,
+    {
+        Cluster {
+            _nodes: node_options(&options)
+                .into_iter()
+                .map(|(address, options)| Node {
+                    _address: address,
+                    _protocol: RwLock::new(Box::new(protocol(options))),
+                })
+                .collect(),
+            _current: AtomicUsize::new(0),
+            _failover: Mutex::new(()),
+            _subscriptions: Subscriptions::default(),
+            _auto_resubscribe: *options.auto_resubscribe(),
+            _on_resubscribed: None,
+        }
+    }
+
+    /// Sets a function called with the previous and the new room id of each subscription
+    /// made again on a new node. See `protocols::Cluster::on_resubscribed`.
+    pub fn on_resubscribed<F>(mut self, listener: F) -> Self
+    where
+        F: Fn(&str, &str) + Send + Sync + 'static,
+    {
+        self._on_resubscribed = Some(Box::new(listener));
+        self
+    }
+
+    /// Returns the address (`host:port`) of the node requests are sent to.
+    pub fn current_node(&self) -> &str {
+        &self._nodes[self.current()]._address
+    }
+
+    fn current(&self) -> usize {
+        self._current.load(Ordering::SeqCst)
+    }
+
+    /// Connects to the node at `index` if needed, and checks its health.
+    async fn revive(&self, index: usize) -> Result<(), Error> {
+        let mut protocol = self._nodes[index]._protocol.write().await;
+        if !protocol.is_ready() {
+            protocol.connect().await?;
+        }
+
+        let call = server::health_check()?;
+        let health = protocol
+            .send(
+                call.request,
+                QueryOptions::new().set_timeout(HEALTH_CHECK_TIMEOUT),
+            )
+            .await
+            .and_then(call.parse);
+        match health {
+            Ok(health) if *health.status() != HealthStatus::Red => Ok(()),
+            Ok(_) => {
+                protocol.close().await;
+                Err(unhealthy(&self._nodes[index]._address))
+            }
+            Err(err) => {
+                protocol.close().await;
+                Err(err)
+            }
+        }
+    }
+
+    /// Returns the first live node, trying them in order from `first`.
+    async fn select_node(&self, first: usize) -> Result<usize, Error> {
+        let mut last_error = Error::NotConnected;
+
+        for offset in 0..self._nodes.len() {
+            let index = (first + offset) % self._nodes.len();
+            match self.revive(index).await {
+                Ok(()) => return Ok(index),
+                Err(err) => last_error = err,
+            }
+        }
+
+        Err(last_error)
+    }
+
+    /// Replaces the node at `failed` with the next live one,
+    /// unless another request already did.
+    async fn failover(&self, failed: usize) -> Result<(), Error> {
+        let _failover = self._failover.lock().await;
+        if self.current() != failed {
+            return Ok(());
+        }
+
+        self._nodes[failed]._protocol.write().await.close().await;
+        let next = self.select_node(failed + 1).await?;
+        self._current.store(next, Ordering::SeqCst);
+
+        if self._auto_resubscribe {
+            let protocol = self._nodes[next]._protocol.read().await;
+            for (room_id, req) in self._subscriptions.requests() {
+                let res = protocol.send(req, QueryOptions::new()).await;
+                if let Some(new_room_id) = self._subscriptions.restore(&room_id, &res) {
+                    if let Some(listener) = &self._on_resubscribed {
+                        listener(&room_id, &new_room_id);
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Protocol for Cluster {
+    async fn connect(&mut self) -> Result<(), Error> {
+        let index = self.select_node(self.current()).await?;
+        self._current.store(index, Ordering::SeqCst);
+        Ok(())
+    }
+
+    async fn send(
+        &self,
+        req: KuzzleRequest,
+        options: QueryOptions,
+    ) -> Result<KuzzleResponse, Error> {
+        let idempotent = is_idempotent(&req);
+        let mut attempts = 0;
+
+        loop {
+            let index = self.current();
+            let res = self._nodes[index]
+                ._protocol
+                .read()
+                .await
+                .send(req.clone(), options.clone())
+                .await;
+
+            match res {
+                Err(err) if is_network_error(&err) => {
+                    attempts += 1;
+                    let failover = self.failover(index).await;
+                    if !idempotent || attempts >= self._nodes.len() || failover.is_err() {
+                        return Err(err);
+                    }
+                }
+                res => {
+                    if let Ok(res) = &res {
+                        self._subscriptions.track(&req, res);
+                    }
+                    return res;
+                }
+            }
+        }
+    }
+
+    async fn close(&mut self) {
+        for node in &self._nodes {
+            node._protocol.write().await.close().await;
+        }
+    }
+
+    fn is_ready(&self) -> bool {
+        match self._nodes[self.current()]._protocol.try_read() {
+            Ok(protocol) => protocol.is_ready(),
+            // Locked for writing: the node is being connected or closed
+            Err(_) => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::sync::atomic::AtomicBool;
+    use std::sync::Arc;
+
+    /// A node which answers every request, unless it is down.
+    struct FakeNode {
+        down: Arc<AtomicBool>,
+        ready: bool,
+    }
+
+    #[async_trait]
+    impl Protocol for FakeNode {
+        async fn connect(&mut self) -> Result<(), Error> {
+            if self.down.load(Ordering::SeqCst) {
+                return Err(Error::Network("Connection refused".into()));
+            }
+            self.ready = true;
+            Ok(())
+        }
+
+        async fn send(
+            &self,
+            req: KuzzleRequest,
+            _options: QueryOptions,
+        ) -> Result<KuzzleResponse, Error> {
+            if !self.ready || self.down.load(Ordering::SeqCst) {
+                return Err(Error::NotConnected);
+            }
+
+            Ok(serde_json::from_value(json!({
+                "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                "status": 200,
+                "error": null,
+                "controller": req.controller(),
+                "action": req.action(),
+                "volatile": null,
+                "result": { "status": "green" }
+            }))?)
+        }
+
+        async fn close(&mut self) {
+            self.ready = false;
+        }
+
+        fn is_ready(&self) -> bool {
+            self.ready
+        }
+    }
+
+    #[tokio::test]
+    async fn send_ok_failover() {
+        let downs: Vec<_> = (0..2).map(|_| Arc::new(AtomicBool::new(false))).collect();
+        // Nodes are told apart by their port
+        let mut cluster = Cluster::new(
+            KuzzleOptions::new("kuzzle", 0).add_node("kuzzle", 1),
+            |options| FakeNode {
+                down: downs[*options.port() as usize].clone(),
+                ready: false,
+            },
+        );
+        cluster.connect().await.unwrap();
+        downs[0].store(true, Ordering::SeqCst);
+
+        let res = cluster
+            .send(KuzzleRequest::new("document", "get"), QueryOptions::new())
+            .await;
+
+        assert!(res.is_ok());
+        assert_eq!(cluster.current_node(), "kuzzle:1");
+    }
+}
@@ -0,0 +1,213 @@
+use crate::controllers::auth::actions;
+use crate::nonblocking::Kuzzle;
+use crate::types::{
+    ApiKey, ApiKeySearchResult, Error, QueryOptions, Session, TokenValidity, User, UserRight,
+};
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::time::Duration;
+
+pub struct AuthController<'a>(pub &'a Kuzzle);
+
+impl<'a> AuthController<'a> {
+    pub async fn check_token(
+        &self,
+        token: &str,
+        options: QueryOptions,
+    ) -> Result<TokenValidity, Error> {
+        self.0.call(actions::check_token(token)?, options).await
+    }
+
+    /// Creates an API key for the current user.
+    pub async fn create_api_key(
+        &self,
+        description: &str,
+        expires_in: Option<Duration>,
+        options: QueryOptions,
+    ) -> Result<ApiKey, Error> {
+        self.0
+            .call(actions::create_api_key(description, expires_in)?, options)
+            .await
+    }
+
+    pub async fn create_my_credentials(
+        &self,
+        strategy: &str,
+        username: &str,
+        password: &str,
+        options: QueryOptions,
+    ) -> Result<Map<String, Value>, Error> {
+        self.0
+            .call(
+                actions::create_my_credentials(strategy, username, password)?,
+                options,
+            )
+            .await
+    }
+
+    pub async fn create_my_credentials_with<C: Serialize>(
+        &self,
+        strategy: &str,
+        credentials: C,
+        options: QueryOptions,
+    ) -> Result<Map<String, Value>, Error> {
+        self.0
+            .call(
+                actions::create_my_credentials_with(strategy, credentials)?,
+                options,
+            )
+            .await
+    }
+
+    pub async fn credentials_exist(
+        &self,
+        strategy: &str,
+        options: QueryOptions,
+    ) -> Result<bool, Error> {
+        self.0
+            .call(actions::credentials_exist(strategy)?, options)
+            .await
+    }
+
+    /// Deletes an API key of the current user, revoking its token.
+    pub async fn delete_api_key(&self, id: &str, options: QueryOptions) -> Result<(), Error> {
+        self.0.call(actions::delete_api_key(id)?, options).await
+    }
+
+    pub async fn delete_my_credentials(
+        &self,
+        strategy: &str,
+        options: QueryOptions,
+    ) -> Result<bool, Error> {
+        self.0
+            .call(actions::delete_my_credentials(strategy)?, options)
+            .await
+    }
+
+    pub async fn get_current_user(
+        &self,
+        strategy: &str,
+        options: QueryOptions,
+    ) -> Result<User, Error> {
+        self.0
+            .call(actions::get_current_user(strategy)?, options)
+            .await
+    }
+
+    pub async fn get_my_credentials(
+        &self,
+        strategy: &str,
+        options: QueryOptions,
+    ) -> Result<Map<String, Value>, Error> {
+        self.0
+            .call(actions::get_my_credentials(strategy)?, options)
+            .await
+    }
+
+    pub async fn get_my_rights(
+        &self,
+        strategy: &str,
+        options: QueryOptions,
+    ) -> Result<Vec<UserRight>, Error> {
+        self.0
+            .call(actions::get_my_rights(strategy)?, options)
+            .await
+    }
+
+    pub async fn get_strategies(&self, options: QueryOptions) -> Result<Vec<String>, Error> {
+        self.0.call(actions::get_strategies()?, options).await
+    }
+
+    pub async fn login(
+        &self,
+        strategy: &str,
+        username: &str,
+        password: &str,
+        options: QueryOptions,
+    ) -> Result<Session, Error> {
+        let overrides_jwt = options.overrides_jwt();
+        let session = self
+            .0
+            .call(actions::login(strategy, username, password)?, options)
+            .await?;
+
+        if !overrides_jwt {
+            self.0.set_session(Some(session.clone()));
+        }
+        Ok(session)
+    }
+
+    /// Logs in with any credentials payload accepted by the given authentication strategy.
+    /// See `controllers::AuthController::login_with`.
+    pub async fn login_with<C: Serialize>(
+        &self,
+        strategy: &str,
+        credentials: C,
+        expires_in: Option<Duration>,
+        options: QueryOptions,
+    ) -> Result<Session, Error> {
+        let overrides_jwt = options.overrides_jwt();
+        let session = self
+            .0
+            .call(
+                actions::login_with(strategy, credentials, expires_in)?,
+                options,
+            )
+            .await?;
+
+        if !overrides_jwt {
+            self.0.set_session(Some(session.clone()));
+        }
+        Ok(session)
+    }
+
+    pub async fn logout(&self, options: QueryOptions) -> Result<(), Error> {
+        let overrides_jwt = options.overrides_jwt();
+        self.0.call(actions::logout()?, options).await?;
+
+        if !overrides_jwt {
+            self.0.set_session(None);
+        }
+        Ok(())
+    }
+
+    /// Renews the current authentication token, which is replaced by the new one.
+    /// See `controllers::AuthController::refresh_token`.
+    pub async fn refresh_token(
+        &self,
+        expires_in: Option<Duration>,
+        options: QueryOptions,
+    ) -> Result<Session, Error> {
+        let overrides_jwt = options.overrides_jwt();
+        let session = self
+            .0
+            .call(actions::refresh_token(expires_in)?, options)
+            .await?;
+
+        if !overrides_jwt {
+            self.0.set_session(Some(session.clone()));
+        }
+        Ok(session)
+    }
+
+    /// Searches the API keys of the current user.
+    pub async fn search_api_keys(
+        &self,
+        query: &HashMap<String, Value>,
+        options: QueryOptions,
+    ) -> Result<ApiKeySearchResult, Error> {
+        self.0.call(actions::search_api_keys(query)?, options).await
+    }
+
+    pub async fn update_my_credentials<C: Serialize>(
+        &self,
+        strategy: &str,
+        content: C,
+        options: QueryOptions,
+    ) -> Result<Map<String, Value>, Error> {
+        self.0
+            .call(actions::update_my_credentials(strategy, content)?, options)
+            .await
+    }
+}
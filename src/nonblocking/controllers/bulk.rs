@@ -0,0 +1,21 @@
+use crate::controllers::bulk::actions;
+use crate::nonblocking::Kuzzle;
+use crate::types::{Error, QueryOptions};
+use serde_json::{Map, Value};
+
+pub struct BulkController<'a>(pub &'a Kuzzle);
+
+impl<'a> BulkController<'a> {
+    /// Creates, updates or deletes large amounts of documents as fast as possible.
+    pub async fn import(
+        &self,
+        index: &str,
+        collection: &str,
+        bulk_data: &[Value],
+        options: QueryOptions,
+    ) -> Result<Map<String, Value>, Error> {
+        self.0
+            .call(actions::import(index, collection, bulk_data)?, options)
+            .await
+    }
+}
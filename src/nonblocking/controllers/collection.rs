@@ -0,0 +1,36 @@
+use crate::controllers::collection::actions;
+use crate::nonblocking::Kuzzle;
+use crate::types::{Error, QueryOptions};
+use serde_json::{Map, Value};
+
+pub struct CollectionController<'a>(pub &'a Kuzzle);
+
+impl<'a> CollectionController<'a> {
+    /// Returns the collection mapping.
+    pub async fn get_mapping(
+        &self,
+        index: &str,
+        collection: &str,
+        options: QueryOptions,
+    ) -> Result<Map<String, Value>, Error> {
+        self.0
+            .call(actions::get_mapping(index, collection)?, options)
+            .await
+    }
+
+    /// Updates the collection mapping.
+    pub async fn update_mapping(
+        &self,
+        index: &str,
+        collection: &str,
+        mapping: &Map<String, Value>,
+        options: QueryOptions,
+    ) -> Result<(), Error> {
+        self.0
+            .call(
+                actions::update_mapping(index, collection, mapping)?,
+                options,
+            )
+            .await
+    }
+}
@@ -0,0 +1,73 @@
+use crate::controllers::document::actions;
+use crate::nonblocking::Kuzzle;
+use crate::types::{Document, Error, MultiResult, QueryOptions, SearchResult};
+use serde_json::Value;
+use std::collections::HashMap;
+
+pub struct DocumentController<'a>(pub &'a Kuzzle);
+
+impl<'a> DocumentController<'a> {
+    /// Creates multiple documents.
+    pub async fn mcreate(
+        &self,
+        index: &str,
+        collection: &str,
+        documents: &[Value],
+        options: QueryOptions,
+    ) -> Result<MultiResult<Document>, Error> {
+        self.0
+            .call(actions::mcreate(index, collection, documents)?, options)
+            .await
+    }
+
+    /// Deletes multiple documents.
+    pub async fn mdelete(
+        &self,
+        index: &str,
+        collection: &str,
+        ids: Vec<String>,
+        options: QueryOptions,
+    ) -> Result<MultiResult<String>, Error> {
+        self.0
+            .call(actions::mdelete(index, collection, ids)?, options)
+            .await
+    }
+
+    /// Updates multiple documents.
+    pub async fn mupdate(
+        &self,
+        index: &str,
+        collection: &str,
+        documents: &[Value],
+        options: QueryOptions,
+    ) -> Result<MultiResult<Document>, Error> {
+        self.0
+            .call(actions::mupdate(index, collection, documents)?, options)
+            .await
+    }
+
+    /// Moves a search cursor forward.
+    pub async fn scroll(
+        &self,
+        scroll_id: &str,
+        scroll: &str,
+        options: QueryOptions,
+    ) -> Result<SearchResult, Error> {
+        self.0
+            .call(actions::scroll(scroll_id, scroll)?, options)
+            .await
+    }
+
+    /// Searches documents in the given collection, using Elasticsearch Query DSL.
+    pub async fn search(
+        &self,
+        index: &str,
+        collection: &str,
+        query: &HashMap<String, Value>,
+        options: QueryOptions,
+    ) -> Result<SearchResult, Error> {
+        self.0
+            .call(actions::search(index, collection, query)?, options)
+            .await
+    }
+}
@@ -0,0 +1,69 @@
+use crate::controllers::index::actions;
+use crate::nonblocking::Kuzzle;
+use crate::types::{Error, MultiResult, QueryOptions};
+
+pub struct IndexController<'a>(pub &'a Kuzzle);
+
+impl<'a> IndexController<'a> {
+    /// Create a new index in Kuzzle.
+    pub async fn create(&self, index: &str, options: QueryOptions) -> Result<(), Error> {
+        self.0.call(actions::create(index)?, options).await
+    }
+
+    /// Delete an entire data index from Kuzzle.
+    pub async fn delete(&self, index: &str, options: QueryOptions) -> Result<(), Error> {
+        self.0.call(actions::delete(index)?, options).await
+    }
+
+    /// Checks if the given index exists in Kuzzle.
+    pub async fn exists(&self, index: &str, options: QueryOptions) -> Result<bool, Error> {
+        self.0.call(actions::exists(index)?, options).await
+    }
+
+    /// Return the current autorefresh status for the index.
+    pub async fn get_auto_refresh(
+        &self,
+        index: &str,
+        options: QueryOptions,
+    ) -> Result<bool, Error> {
+        self.0
+            .call(actions::get_auto_refresh(index)?, options)
+            .await
+    }
+
+    /// Get the complete list of data indexes handled by Kuzzle.
+    pub async fn list(&self, options: QueryOptions) -> Result<Vec<String>, Error> {
+        self.0.call(actions::list()?, options).await
+    }
+
+    /// Deletes multiple indexes at once.
+    pub async fn mdelete(
+        &self,
+        indexes: Vec<String>,
+        options: QueryOptions,
+    ) -> Result<MultiResult<String>, Error> {
+        self.0.call(actions::mdelete(indexes)?, options).await
+    }
+
+    /// Forces an immediate reindexation of the provided index.
+    pub async fn refresh(&self, index: &str, options: QueryOptions) -> Result<(), Error> {
+        self.0.call(actions::refresh(index)?, options).await
+    }
+
+    /// Forces an immediate reindexation of Kuzzle internal storage.
+    pub async fn refresh_internal(&self, options: QueryOptions) -> Result<(), Error> {
+        self.0.call(actions::refresh_internal()?, options).await
+    }
+
+    /// Changes the autoRefresh configuration of an index.
+    pub async fn set_auto_refresh(
+        &self,
+        index: &str,
+        auto_refresh: bool,
+        options: QueryOptions,
+    ) -> Result<(), Error> {
+        self.0
+            .call(actions::set_auto_refresh(index, auto_refresh)?, options)
+            .await
+    }
+}
@@ -0,0 +1,18 @@
+//! Async counterparts of the `controllers` module.
+//! Arguments are checked, and responses read, the same way.
+
+mod auth;
+mod bulk;
+mod collection;
+mod document;
+mod index;
+mod security;
+mod server;
+
+pub use self::auth::AuthController;
+pub use self::bulk::BulkController;
+pub use self::collection::CollectionController;
+pub use self::document::DocumentController;
+pub use self::index::IndexController;
+pub use self::security::SecurityController;
+pub use self::server::ServerController;
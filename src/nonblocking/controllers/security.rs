@@ -0,0 +1,76 @@
+use crate::controllers::security::actions;
+use crate::nonblocking::Kuzzle;
+use crate::types::{ApiKey, ApiKeySearchResult, Error, MultiResult, QueryOptions};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::time::Duration;
+
+pub struct SecurityController<'a>(pub &'a Kuzzle);
+
+impl<'a> SecurityController<'a> {
+    /// Creates an API key for the given user.
+    pub async fn create_api_key(
+        &self,
+        user_id: &str,
+        description: &str,
+        expires_in: Option<Duration>,
+        options: QueryOptions,
+    ) -> Result<ApiKey, Error> {
+        self.0
+            .call(
+                actions::create_api_key(user_id, description, expires_in)?,
+                options,
+            )
+            .await
+    }
+
+    /// Deletes an API key of the given user, revoking its token.
+    pub async fn delete_api_key(
+        &self,
+        user_id: &str,
+        id: &str,
+        options: QueryOptions,
+    ) -> Result<(), Error> {
+        self.0
+            .call(actions::delete_api_key(user_id, id)?, options)
+            .await
+    }
+
+    /// Deletes multiple profiles.
+    pub async fn mdelete_profiles(
+        &self,
+        ids: Vec<String>,
+        options: QueryOptions,
+    ) -> Result<MultiResult<String>, Error> {
+        self.0.call(actions::mdelete_profiles(ids)?, options).await
+    }
+
+    /// Deletes multiple roles.
+    pub async fn mdelete_roles(
+        &self,
+        ids: Vec<String>,
+        options: QueryOptions,
+    ) -> Result<MultiResult<String>, Error> {
+        self.0.call(actions::mdelete_roles(ids)?, options).await
+    }
+
+    /// Deletes multiple users.
+    pub async fn mdelete_users(
+        &self,
+        ids: Vec<String>,
+        options: QueryOptions,
+    ) -> Result<MultiResult<String>, Error> {
+        self.0.call(actions::mdelete_users(ids)?, options).await
+    }
+    /// Searches the API keys of the given user.
+    pub async fn search_api_keys(
+        &self,
+        user_id: &str,
+        query: &HashMap<String, Value>,
+        options: QueryOptions,
+    ) -> Result<ApiKeySearchResult, Error> {
+        self.0
+            .call(actions::search_api_keys(user_id, query)?, options)
+            .await
+    }
+}
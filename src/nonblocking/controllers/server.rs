@@ -0,0 +1,53 @@
+use crate::controllers::server::actions;
+use crate::nonblocking::Kuzzle;
+use crate::types::{Error, HealthCheck, QueryOptions, ServerConfig, ServerInfo, StatsFrame};
+
+pub struct ServerController<'a>(pub &'a Kuzzle);
+
+impl<'a> ServerController<'a> {
+    /// Checks that an administrator account exists.
+    pub async fn admin_exists(&self, options: QueryOptions) -> Result<bool, Error> {
+        self.0.call(actions::admin_exists()?, options).await
+    }
+
+    /// Gets all stored internal statistic snapshots.
+    pub async fn get_all_stats(&self, options: QueryOptions) -> Result<Vec<StatsFrame>, Error> {
+        self.0.call(actions::get_all_stats()?, options).await
+    }
+
+    /// Returns the current Kuzzle configuration.
+    pub async fn get_config(&self, options: QueryOptions) -> Result<ServerConfig, Error> {
+        self.0.call(actions::get_config()?, options).await
+    }
+
+    /// Returns the most recent statistics snapshot.
+    pub async fn get_last_stats(&self, options: QueryOptions) -> Result<StatsFrame, Error> {
+        self.0.call(actions::get_last_stats()?, options).await
+    }
+
+    /// Returns statistics snapshots within a provided Epoch millis timestamp range.
+    pub async fn get_stats(
+        &self,
+        from: i64,
+        to: i64,
+        options: QueryOptions,
+    ) -> Result<Vec<StatsFrame>, Error> {
+        self.0.call(actions::get_stats(from, to)?, options).await
+    }
+
+    /// Returns the status of Kuzzle and of its external services (Elasticsearch, Redis...).
+    pub async fn health_check(&self, options: QueryOptions) -> Result<HealthCheck, Error> {
+        self.0.call(actions::health_check()?, options).await
+    }
+
+    /// Returns information about Kuzzle: available API (base + extended), plugins,
+    /// external services (Redis, Elasticsearch, ...), servers, etc.
+    pub async fn info(&self, options: QueryOptions) -> Result<ServerInfo, Error> {
+        self.0.call(actions::info()?, options).await
+    }
+
+    /// Returns the current server timestamp, in Epoch-millis format.
+    pub async fn now(&self, options: QueryOptions) -> Result<u64, Error> {
+        self.0.call(actions::now()?, options).await
+    }
+}
@@ -0,0 +1,339 @@
+use crate::nonblocking::protocol::{bounded, Protocol};
+use crate::protocols::{self, tls, Routes, State};
+use crate::types::{Error, KuzzleOptions, KuzzleRequest, KuzzleResponse, QueryOptions, SdkError};
+
+use async_trait::async_trait;
+use reqwest::{Client, Url};
+use serde_json::Value;
+use tokio::net;
+
+pub struct Http {
+    _client: Client,
+    _options: KuzzleOptions,
+    _routes: Routes,
+    _state: State,
+    _fetch_routes: bool,
+}
+
+impl Http {
+    /// Returns a Http struct that acts as an async HTTP
+    /// client to dial with Kuzzle server.
+    /// Kuzzle HTTP routes are bundled with the SDK,
+    /// use `set_routes` to add or override some of them.
+    ///
+    /// # Arguments
+    /// * `options` - An `types::Options` used to configure Http dialer
+    ///
+    /// # Example
+    /// ```
+    /// use kuzzle_sdk::types::KuzzleOptions;
+    /// use kuzzle_sdk::nonblocking::Http;
+    ///
+    /// let http = Http::new(KuzzleOptions::new("localhost", 7512));
+    /// ```
+    pub fn new(options: KuzzleOptions) -> Http {
+        Http {
+            _client: Client::new(),
+            _options: options,
+            _routes: protocols::Http::bundled_routes(),
+            _state: State::Offline,
+            _fetch_routes: false,
+        }
+    }
+
+    /// If `fetch` is true, `connect` downloads the route table exposed by the server.
+    /// See `protocols::Http::set_fetch_routes`.
+    pub fn set_fetch_routes(mut self, fetch: bool) -> Self {
+        self._fetch_routes = fetch;
+        self
+    }
+
+    /// Adds the given routes to the bundled ones.
+    /// A route defined for an already known controller/action replaces the bundled one.
+    pub fn set_routes(mut self, routes: Routes) -> Self {
+        for (controller, actions) in routes {
+            self._routes.entry(controller).or_default().extend(actions);
+        }
+        self
+    }
+
+    /// Returns the routes used by this Http client.
+    pub fn routes(&self) -> &Routes {
+        &self._routes
+    }
+
+    /// Adds the routes exposed by the server which are missing from the known ones.
+    async fn fetch_routes(&mut self) -> Result<(), Error> {
+        let res = self
+            .send(
+                KuzzleRequest::new("server", "publicApi"),
+                QueryOptions::new(),
+            )
+            .await?;
+        let api = match res.error() {
+            None => res.result().clone(),
+            Some(_) => {
+                let res = self
+                    .send(KuzzleRequest::new("server", "info"), QueryOptions::new())
+                    .await?;
+                if let Some(k_err) = res.error() {
+                    return Err(Error::from(k_err.clone()));
+                }
+                res.result()
+                    .pointer("/serverInfo/kuzzle/api/routes")
+                    .cloned()
+                    .unwrap_or(Value::Null)
+            }
+        };
+
+        protocols::Http::add_missing_routes(&mut self._routes, &api);
+        Ok(())
+    }
+
+    /// Builds the HTTP client, configured with the TLS options if `ssl_connection` is enabled.
+    async fn build_client(&self) -> Result<Client, Error> {
+        let mut builder = Client::builder();
+
+        if *self._options.ssl_connection() {
+            builder = builder.use_preconfigured_tls(tls::connector(self._options.tls())?);
+
+            let server_name = tls::server_name(&self._options);
+            if server_name != self._options.host() {
+                // Dial the Kuzzle host while presenting the SNI hostname
+                let addr =
+                    net::lookup_host(format!("{}:{}", self._options.host(), self._options.port()))
+                        .await?
+                        .next()
+                        .ok_or_else(|| {
+                            Error::InvalidArgument(SdkError::new(
+                                "Http::connect",
+                                &format!("Unable to resolve host {}", self._options.host()),
+                            ))
+                        })?;
+                builder = builder.resolve(server_name, addr);
+            }
+        }
+
+        Ok(builder.build()?)
+    }
+}
+
+#[async_trait]
+impl Protocol for Http {
+    fn is_ready(&self) -> bool {
+        match self._state {
+            State::Ready => true,
+            State::Offline => false,
+        }
+    }
+
+    async fn close(&mut self) {
+        self._state = State::Offline;
+    }
+
+    async fn connect(&mut self) -> Result<(), Error> {
+        if self._state == State::Ready {
+            return Ok(());
+        }
+
+        let url = Url::parse(&protocols::Http::base_url(&self._options)).map_err(|err| {
+            Error::InvalidArgument(SdkError::new(
+                "Http::connect",
+                &format!("Invalid host: {}", err),
+            ))
+        })?;
+
+        self._client = self.build_client().await?;
+        self._client.get(url).send().await?;
+        self._state = State::Ready;
+
+        if self._fetch_routes {
+            // Known routes are enough to use the SDK controllers:
+            // failing to fetch server ones must not prevent connecting.
+            let _ = self.fetch_routes().await;
+        }
+
+        Ok(())
+    }
+
+    async fn send(
+        &self,
+        req: KuzzleRequest,
+        options: QueryOptions,
+    ) -> Result<KuzzleResponse, Error> {
+        if self._state == State::Offline {
+            return Err(Error::NotConnected);
+        }
+
+        let expires_at = options.expires_at(*self._options.request_timeout());
+        options.check(expires_at)?;
+
+        let prepared = protocols::Http::prepare(
+            &self._routes,
+            &protocols::Http::base_url(&self._options),
+            &req,
+        )?;
+        let mut request = self._client.request(prepared.method, prepared.url);
+
+        if let Some(body) = &prepared.body {
+            request = request.json(body);
+        }
+
+        if !prepared.query.is_empty() {
+            request = request.query(&prepared.query);
+        }
+
+        for (name, value) in prepared.headers {
+            request = request.header(name, value);
+        }
+
+        let raw_response = bounded(
+            async { Ok(request.send().await?.text().await?) },
+            &options,
+            expires_at,
+        )
+        .await?;
+        let response: KuzzleResponse = serde_json::from_str(&raw_response)?;
+
+        Ok(response)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nonblocking::Kuzzle;
+    use mockito;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn send_ok() {
+        let _m = mockito::mock("GET", "/_now")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "server",
+                    "action": "now",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": { "now": 1447151167622 }
+                }"#,
+            )
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        k.connect()
+            .await
+            .expect("Unable to connect to Kuzzle server");
+
+        // Requests can be moved to another task
+        let now = tokio::spawn(async move { k.server().now(QueryOptions::new()).await })
+            .await
+            .unwrap();
+
+        assert_eq!(now.unwrap(), 1447151167622);
+    }
+
+    #[tokio::test]
+    async fn send_query_options_ok() {
+        let _m = mockito::mock(
+            "POST",
+            "/ferris_index/ferris_collection/_create?refresh=wait_for",
+        )
+        .match_header("authorization", "Bearer ferris-token")
+        .match_header("x-kuzzle-volatile", r#"{"user":"ferris"}"#)
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "document",
+                    "action": "create",
+                    "collection": "ferris_collection",
+                    "index": "ferris_index",
+                    "volatile": { "user": "ferris" },
+                    "result": { "_id": "ferris", "_version": 1 }
+                }"#,
+        )
+        .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        k.connect()
+            .await
+            .expect("Unable to connect to Kuzzle server");
+        k.set_jwt("ferris-token".to_string());
+        let res = k
+            .query(
+                KuzzleRequest::new("document", "create")
+                    .set_index("ferris_index")
+                    .set_collection("ferris_collection"),
+                QueryOptions::new()
+                    .set_refresh("wait_for")
+                    .add_to_volatile("user", json!("ferris")),
+            )
+            .await;
+
+        assert_eq!(res.unwrap().result()["_id"], "ferris");
+    }
+
+    #[tokio::test]
+    async fn send_fail_error() {
+        let _m = mockito::mock("GET", "/_list")
+            .with_status(500)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 500,
+                    "error": {
+                        "message": "Internal error",
+                        "status": 500
+                    },
+                    "controller": "index",
+                    "action": "list",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": null
+                }"#,
+            )
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        k.connect()
+            .await
+            .expect("Unable to connect to Kuzzle server");
+        let res = k.index().list(QueryOptions::new()).await;
+
+        assert_eq!(res.unwrap_err().kuzzle_error().unwrap().status(), Some(500));
+    }
+
+    #[tokio::test]
+    async fn send_fail_not_connected() {
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k
+            .query(KuzzleRequest::new("server", "now"), QueryOptions::new())
+            .await;
+
+        assert!(matches!(res, Err(Error::NotConnected)));
+    }
+
+    #[tokio::test]
+    async fn send_fail_logged_out() {
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let res = k.auth().get_strategies(QueryOptions::new()).await;
+
+        assert!(matches!(res, Err(Error::NotConnected)));
+
+        let res = k.auth().get_my_rights("local", QueryOptions::new()).await;
+
+        assert!(matches!(res, Err(Error::InvalidArgument(_))));
+    }
+}
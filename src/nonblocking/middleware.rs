@@ -0,0 +1,105 @@
+use crate::nonblocking::Protocol;
+use crate::types::{Error, KuzzleRequest, KuzzleResponse, QueryOptions};
+
+use async_trait::async_trait;
+use std::sync::Arc;
+
+/// A hook around the requests sent by an async `Kuzzle` client.
+/// See `middleware::Middleware`.
+#[async_trait]
+pub trait Middleware: Send + Sync {
+    /// Handles `req`, usually by passing it, modified or not, to `next.run`
+    /// and returning the result, modified or not.
+    async fn handle(
+        &self,
+        req: KuzzleRequest,
+        options: QueryOptions,
+        next: &Next<'_>,
+    ) -> Result<KuzzleResponse, Error>;
+}
+
+#[async_trait]
+impl<M: Middleware + ?Sized> Middleware for Arc<M> {
+    async fn handle(
+        &self,
+        req: KuzzleRequest,
+        options: QueryOptions,
+        next: &Next<'_>,
+    ) -> Result<KuzzleResponse, Error> {
+        (**self).handle(req, options, next).await
+    }
+}
+
+/// The rest of the middleware chain, ending with the protocol.
+#[derive(Clone, Copy)]
+pub struct Next<'a> {
+    _protocol: &'a dyn Protocol,
+    _middlewares: &'a [Arc<dyn Middleware>],
+}
+
+impl<'a> Next<'a> {
+    pub(crate) fn new(protocol: &'a dyn Protocol, middlewares: &'a [Arc<dyn Middleware>]) -> Self {
+        Next {
+            _protocol: protocol,
+            _middlewares: middlewares,
+        }
+    }
+
+    /// Passes `req` to the next middleware, or sends it if there is none left.
+    pub async fn run(
+        &self,
+        req: KuzzleRequest,
+        options: QueryOptions,
+    ) -> Result<KuzzleResponse, Error> {
+        match self._middlewares.split_first() {
+            Some((middleware, rest)) => {
+                middleware
+                    .handle(req, options, &Next::new(self._protocol, rest))
+                    .await
+            }
+            None => self._protocol.send(req, options).await,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nonblocking::Kuzzle;
+    use crate::protocols::Mock;
+    use serde_json::json;
+
+    /// Adds the name of the middleware to the request volatile data.
+    struct Tag(&'static str);
+
+    #[async_trait]
+    impl Middleware for Tag {
+        async fn handle(
+            &self,
+            req: KuzzleRequest,
+            options: QueryOptions,
+            next: &Next<'_>,
+        ) -> Result<KuzzleResponse, Error> {
+            next.run(req.add_to_volatile(self.0, json!(true)), options)
+                .await
+        }
+    }
+
+    #[tokio::test]
+    async fn middlewares_ok() {
+        let mock = Mock::new();
+        mock.when("server", "now").respond(json!({ "now": 1 }));
+
+        let kuzzle = Kuzzle::builder(mock.clone())
+            .middleware(Tag("first"))
+            .middleware(Tag("second"))
+            .build();
+        kuzzle.connect().await.unwrap();
+        kuzzle.server().now(QueryOptions::new()).await.unwrap();
+
+        let calls = mock.calls();
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].volatile()["first"], json!(true));
+        assert_eq!(calls[0].volatile()["second"], json!(true));
+    }
+}
@@ -0,0 +1,40 @@
+//! Async variant of the SDK, enabled by the `async` cargo feature.
+//!
+//! It mirrors the blocking API: an async `Protocol` trait implemented by `Http`
+//! and `Websocket`, and a `Kuzzle` client whose controller methods return futures.
+//! It must be used from a tokio runtime.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use kuzzle_sdk::nonblocking::{Http, Kuzzle};
+//! use kuzzle_sdk::types::{KuzzleOptions, QueryOptions};
+//!
+//! #[tokio::main(flavor = "current_thread")]
+//! async fn main() {
+//!     let kuzzle = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+//!     kuzzle.connect().await.unwrap();
+//!
+//!     let now = kuzzle.server().now(QueryOptions::new()).await.unwrap();
+//!     println!("{}", now);
+//! }
+//! ```
+
+mod cluster;
+pub mod controllers;
+mod http;
+mod kuzzle;
+mod middleware;
+mod protocol;
+mod recorder;
+mod token_refresher;
+mod websocket;
+
+pub use self::cluster::Cluster;
+pub use self::http::Http;
+pub use self::kuzzle::{Kuzzle, KuzzleBuilder};
+pub use self::middleware::{Middleware, Next};
+pub use self::protocol::Protocol;
+pub use self::recorder::Recorder;
+pub use self::token_refresher::{TokenRefresher, TokenRefresherHandle};
+pub use self::websocket::Websocket;
@@ -0,0 +1,55 @@
+use crate::types::{Error, KuzzleRequest, KuzzleResponse, QueryOptions};
+
+use async_trait::async_trait;
+use std::future::{self, Future};
+use std::time::{Duration, Instant};
+use tokio::time;
+
+/// How often an in-flight request checks whether it has been cancelled.
+const CANCELLATION_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+#[async_trait]
+pub trait Protocol: Send + Sync {
+    async fn connect(&mut self) -> Result<(), Error>;
+    async fn send(
+        &self,
+        req: KuzzleRequest,
+        options: QueryOptions,
+    ) -> Result<KuzzleResponse, Error>;
+    async fn close(&mut self);
+    fn is_ready(&self) -> bool;
+}
+
+/// Runs `request` until it completes, `expires_at` is reached
+/// or the request is cancelled with its `CancellationToken`.
+pub(crate) async fn bounded<F, T>(
+    request: F,
+    options: &QueryOptions,
+    expires_at: Option<Instant>,
+) -> Result<T, Error>
+where
+    F: Future<Output = Result<T, Error>>,
+{
+    let cancelled = async {
+        match options.cancellation_token() {
+            Some(token) => {
+                while !token.is_cancelled() {
+                    time::sleep(CANCELLATION_POLL_INTERVAL).await;
+                }
+            }
+            None => future::pending().await,
+        }
+    };
+    let expired = async {
+        match expires_at {
+            Some(expires_at) => time::sleep_until(time::Instant::from_std(expires_at)).await,
+            None => future::pending().await,
+        }
+    };
+
+    tokio::select! {
+        res = request => res,
+        _ = cancelled => Err(Error::Cancelled),
+        _ = expired => Err(Error::Timeout),
+    }
+}
@@ -0,0 +1,145 @@
+use crate::nonblocking::Protocol;
+use crate::protocols::recorder::Fixture;
+use crate::types::{Error, KuzzleRequest, KuzzleResponse, QueryOptions};
+
+use async_trait::async_trait;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Records requests and their responses to a fixture file, or replays them.
+/// See `protocols::Recorder`.
+pub struct Recorder {
+    _protocol: Option<Box<dyn Protocol>>,
+    _fixture: Fixture,
+    _ready: AtomicBool,
+}
+
+impl Recorder {
+    /// Returns a recorder sending requests through `protocol`, and appending them
+    /// along with their responses to `path` after each request.
+    /// The file is overwritten by the first request.
+    pub fn record<P, Q>(protocol: P, path: Q) -> Recorder
+    where
+        P: 'static + Protocol,
+        Q: Into<PathBuf>,
+    {
+        Recorder {
+            _protocol: Some(Box::new(protocol)),
+            _fixture: Fixture::empty(path.into()),
+            _ready: AtomicBool::new(false),
+        }
+    }
+
+    /// Returns a recorder answering requests with the responses recorded in `path`,
+    /// without sending them.
+    pub fn replay<Q: Into<PathBuf>>(path: Q) -> Result<Recorder, Error> {
+        Ok(Recorder {
+            _protocol: None,
+            _fixture: Fixture::load(path.into())?,
+            _ready: AtomicBool::new(false),
+        })
+    }
+
+    /// Leaves the request field at `pointer` out of fixtures and of request matching.
+    pub fn ignore(mut self, pointer: &str) -> Self {
+        self._fixture.ignore(pointer);
+        self
+    }
+
+    /// Stops ignoring the request field at `pointer`.
+    pub fn compare(mut self, pointer: &str) -> Self {
+        self._fixture.compare(pointer);
+        self
+    }
+
+    pub fn path(&self) -> &Path {
+        self._fixture.path()
+    }
+
+    /// Returns true if requests are answered from the fixture file.
+    pub fn is_replaying(&self) -> bool {
+        self._protocol.is_none()
+    }
+}
+
+#[async_trait]
+impl Protocol for Recorder {
+    async fn connect(&mut self) -> Result<(), Error> {
+        match &mut self._protocol {
+            Some(protocol) => protocol.connect().await,
+            None => {
+                self._ready.store(true, Ordering::SeqCst);
+                Ok(())
+            }
+        }
+    }
+
+    async fn send(
+        &self,
+        req: KuzzleRequest,
+        options: QueryOptions,
+    ) -> Result<KuzzleResponse, Error> {
+        match &self._protocol {
+            Some(protocol) => {
+                let res = protocol.send(req.clone(), options).await;
+                self._fixture.record(&req, &res)?;
+                res
+            }
+            None if !self.is_ready() => Err(Error::NotConnected),
+            None => {
+                options.check(None)?;
+                self._fixture.replay(&req)
+            }
+        }
+    }
+
+    async fn close(&mut self) {
+        match &mut self._protocol {
+            Some(protocol) => protocol.close().await,
+            None => self._ready.store(false, Ordering::SeqCst),
+        }
+    }
+
+    fn is_ready(&self) -> bool {
+        match &self._protocol {
+            Some(protocol) => protocol.is_ready(),
+            None => self._ready.load(Ordering::SeqCst),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocols::Mock;
+    use serde_json::json;
+    use std::env;
+    use std::fs;
+    use std::process;
+
+    #[tokio::test]
+    async fn replay_ok() {
+        let path = env::temp_dir()
+            .join(format!("kuzzle_sdk_{}", process::id()))
+            .join("nonblocking_replay_ok.jsonl");
+        let mock = Mock::new();
+        mock.when("server", "now")
+            .respond(json!({ "now": 1447151167622u64 }));
+
+        let mut recorder = Recorder::record(mock, &path);
+        recorder.connect().await.unwrap();
+        recorder
+            .send(KuzzleRequest::new("server", "now"), QueryOptions::new())
+            .await
+            .unwrap();
+
+        let mut recorder = Recorder::replay(&path).unwrap();
+        recorder.connect().await.unwrap();
+        let res = recorder
+            .send(KuzzleRequest::new("server", "now"), QueryOptions::new())
+            .await;
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(res.unwrap().result()["now"], 1447151167622u64);
+    }
+}
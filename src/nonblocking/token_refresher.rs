@@ -0,0 +1,170 @@
+use crate::nonblocking::Kuzzle;
+use crate::token_refresher::{next_check, Renewal, TokenExpiredListener, DEFAULT_MARGIN};
+use crate::types::{Error, QueryOptions};
+
+use std::time::Duration;
+use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
+use tokio::time;
+
+/// Async counterpart of `token_refresher::TokenRefresher`, renewing the token from a tokio task.
+pub struct TokenRefresher {
+    _kuzzle: Kuzzle,
+    _margin: Duration,
+    _expires_in: Option<Duration>,
+    _on_token_expired: Option<TokenExpiredListener>,
+}
+
+impl TokenRefresher {
+    /// Returns a refresher renewing the token of `kuzzle`, and of its clones,
+    /// one minute before it expires.
+    pub fn new(kuzzle: Kuzzle) -> TokenRefresher {
+        TokenRefresher {
+            _kuzzle: kuzzle,
+            _margin: DEFAULT_MARGIN,
+            _expires_in: None,
+            _on_token_expired: None,
+        }
+    }
+
+    /// Sets how long before its expiration the token is renewed, in milliseconds.
+    pub fn set_margin(mut self, margin: u64) -> Self {
+        self._margin = Duration::from_millis(margin);
+        self
+    }
+
+    /// Sets the lifespan of renewed tokens, in milliseconds.
+    /// The server default is used if it is not set.
+    pub fn set_expires_in(mut self, expires_in: u64) -> Self {
+        self._expires_in = Some(Duration::from_millis(expires_in));
+        self
+    }
+
+    /// Sets the function called with the error returned by Kuzzle when
+    /// the token can't be renewed. It is called once per session.
+    ///
+    /// Renewals failing with an authentication error are not retried. Other errors,
+    /// such as network errors, are retried with an exponential backoff until the
+    /// token expires.
+    pub fn on_token_expired<F>(mut self, listener: F) -> Self
+    where
+        F: Fn(&Error) + Send + Sync + 'static,
+    {
+        self._on_token_expired = Some(Box::new(listener));
+        self
+    }
+
+    /// Starts renewing the token from a tokio task,
+    /// until the returned handle is stopped or dropped.
+    pub fn start(self) -> TokenRefresherHandle {
+        let (stop, stopped) = oneshot::channel();
+        let task = tokio::spawn(self.run(stopped));
+
+        TokenRefresherHandle {
+            _stop: stop,
+            _task: task,
+        }
+    }
+
+    async fn run(self, mut stopped: oneshot::Receiver<()>) {
+        let mut renewal = Renewal::default();
+
+        loop {
+            if let Some(expires_at) = renewal.due(self._kuzzle.session(), self._margin) {
+                let res = self
+                    ._kuzzle
+                    .auth()
+                    .refresh_token(self._expires_in, QueryOptions::new())
+                    .await;
+
+                match res {
+                    Ok(_) => renewal.succeeded(),
+                    Err(err) => {
+                        if renewal.failed(expires_at, &err) {
+                            if let Some(listener) = &self._on_token_expired {
+                                listener(&err);
+                            }
+                        }
+                    }
+                }
+            }
+
+            tokio::select! {
+                _ = time::sleep(next_check(self._kuzzle.session(), self._margin)) => continue,
+                _ = &mut stopped => return,
+            }
+        }
+    }
+}
+
+/// Stops the token renewal when dropped.
+pub struct TokenRefresherHandle {
+    _stop: oneshot::Sender<()>,
+    _task: JoinHandle<()>,
+}
+
+impl TokenRefresherHandle {
+    /// Stops the token renewal, waiting for an ongoing renewal to complete.
+    pub async fn stop(self) {
+        let _ = self._stop.send(());
+        let _ = self._task.await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nonblocking::Http;
+    use crate::types::{KuzzleOptions, Session};
+    use chrono::Utc;
+    use mockito;
+    use serde_json::json;
+    use std::time::Instant;
+
+    #[tokio::test]
+    async fn start_ok() {
+        let _m = mockito::mock("POST", "/_refreshToken")
+            .match_header("authorization", "Bearer expiringJwtToken")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "auth",
+                    "action": "refreshToken",
+                    "volatile": {},
+                    "result": {
+                      "_id": "user-kuid",
+                      "jwt": "renewedJwtToken",
+                      "expiresAt": 4102444800000,
+                      "ttl": 3600000
+                    }
+                }"#,
+            )
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        k.connect()
+            .await
+            .expect("Unable to connect to Kuzzle server");
+        let session: Session = serde_json::from_value(json!({
+            "_id": "user-kuid",
+            "jwt": "expiringJwtToken",
+            "expiresAt": (Utc::now() + chrono::Duration::seconds(30)).timestamp_millis()
+        }))
+        .unwrap();
+        k.set_session(Some(session));
+
+        let refresher = TokenRefresher::new(k.clone()).start();
+
+        let start = Instant::now();
+        while k.jwt() != "renewedJwtToken" && start.elapsed() < Duration::from_secs(5) {
+            time::sleep(Duration::from_millis(10)).await;
+        }
+        refresher.stop().await;
+
+        assert_eq!(k.jwt(), "renewedJwtToken");
+    }
+}
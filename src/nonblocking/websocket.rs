@@ -0,0 +1,413 @@
+use crate::nonblocking::protocol::{bounded, Protocol};
+use crate::protocols::{self, tls};
+use crate::types::{Error, KuzzleOptions, KuzzleRequest, KuzzleResponse, QueryOptions};
+
+use async_trait::async_trait;
+use futures_util::stream::{SplitSink, SplitStream};
+use futures_util::{SinkExt, StreamExt};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use tokio::net::TcpStream;
+use tokio::sync::{self, mpsc, oneshot};
+use tokio::task::JoinHandle;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::{Connector, MaybeTlsStream, WebSocketStream};
+
+type Socket = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+/// Requests waiting for their response, indexed by request id.
+type Senders = HashMap<String, oneshot::Sender<Result<KuzzleResponse, Error>>>;
+type Pending = Arc<Mutex<Senders>>;
+/// Receivers of the realtime notifications.
+type Listeners = Arc<Mutex<Vec<mpsc::UnboundedSender<KuzzleResponse>>>>;
+
+/// Sends requests to Kuzzle through a WebSocket connection.
+/// See `protocols::Websocket`.
+pub struct Websocket {
+    _options: KuzzleOptions,
+    _ready: Arc<AtomicBool>,
+    _sink: Option<sync::Mutex<SplitSink<Socket, Message>>>,
+    _pending: Pending,
+    _listeners: Listeners,
+    _reader: Option<JoinHandle<()>>,
+}
+
+impl Websocket {
+    /// Returns a Websocket struct that acts as an async WebSocket
+    /// client to dial with Kuzzle server.
+    /// If `ssl_connection` is enabled, the connection is secured
+    /// with the TLS options of `KuzzleOptions`.
+    ///
+    /// # Arguments
+    /// * `options` - An `types::Options` used to configure Websocket dialer
+    ///
+    /// # Example
+    /// ```
+    /// use kuzzle_sdk::types::KuzzleOptions;
+    /// use kuzzle_sdk::nonblocking::Websocket;
+    ///
+    /// let ws = Websocket::new(KuzzleOptions::new("localhost", 7512));
+    /// ```
+    pub fn new(options: KuzzleOptions) -> Websocket {
+        Websocket {
+            _options: options,
+            _ready: Arc::new(AtomicBool::new(false)),
+            _sink: None,
+            _pending: Arc::new(Mutex::new(HashMap::new())),
+            _listeners: Arc::new(Mutex::new(Vec::new())),
+            _reader: None,
+        }
+    }
+
+    /// Returns a receiver of the realtime notifications sent by Kuzzle.
+    /// See `protocols::Websocket::notifications`.
+    pub fn notifications(&self) -> mpsc::UnboundedReceiver<KuzzleResponse> {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        lock(&self._listeners).push(sender);
+        receiver
+    }
+
+    /// Reads messages from Kuzzle and hands each response over to the pending request
+    /// it answers, and each notification over to `listeners`.
+    /// Once the connection is lost, the protocol is no longer `ready` and pending requests fail.
+    async fn read(
+        mut stream: SplitStream<Socket>,
+        pending: Pending,
+        listeners: Listeners,
+        ready: Arc<AtomicBool>,
+    ) {
+        while let Some(message) = stream.next().await {
+            let text = match message {
+                Ok(Message::Text(text)) => text,
+                Ok(Message::Close(_)) | Err(_) => break,
+                Ok(_) => continue,
+            };
+            let message: Value = match serde_json::from_str(&text) {
+                Ok(message) => message,
+                Err(_) => continue,
+            };
+
+            // Notifications share the request id of the subscription, but are sent to its room
+            if protocols::Websocket::is_notification(&message) {
+                if let Ok(notification) = serde_json::from_value::<KuzzleResponse>(message) {
+                    // Receivers which have been dropped are forgotten
+                    lock(&listeners).retain(|listener| listener.send(notification.clone()).is_ok());
+                }
+                continue;
+            }
+
+            let sender = match message["requestId"].as_str() {
+                Some(request_id) => lock(&pending).remove(request_id),
+                None => None,
+            };
+            if let Some(sender) = sender {
+                let _ = sender.send(serde_json::from_value(message).map_err(Error::from));
+            }
+        }
+
+        // Requests sent from now on see that the protocol is no longer ready
+        ready.store(false, Ordering::SeqCst);
+        for (_, sender) in lock(&pending).drain() {
+            let _ = sender.send(Err(Error::Network("Connection closed by Kuzzle".into())));
+        }
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+#[async_trait]
+impl Protocol for Websocket {
+    fn is_ready(&self) -> bool {
+        self._ready.load(Ordering::SeqCst)
+    }
+
+    async fn connect(&mut self) -> Result<(), Error> {
+        if self.is_ready() {
+            return Ok(());
+        }
+        // Releases the lost connection, if any
+        self.close().await;
+
+        let stream =
+            TcpStream::connect(format!("{}:{}", self._options.host(), self._options.port()))
+                .await
+                .map_err(|err| Error::Network(Box::new(err)))?;
+        let connector = if *self._options.ssl_connection() {
+            Connector::NativeTls(tls::connector(self._options.tls())?)
+        } else {
+            Connector::Plain
+        };
+        let (socket, _) = tokio_tungstenite::client_async_tls_with_config(
+            protocols::Websocket::url(&self._options),
+            stream,
+            None,
+            Some(connector),
+        )
+        .await
+        .map_err(|err| Error::Network(err.to_string().into()))?;
+
+        let (sink, stream) = socket.split();
+        self._sink = Some(sync::Mutex::new(sink));
+        self._ready.store(true, Ordering::SeqCst);
+        self._reader = Some(tokio::spawn(Websocket::read(
+            stream,
+            self._pending.clone(),
+            self._listeners.clone(),
+            self._ready.clone(),
+        )));
+        Ok(())
+    }
+
+    async fn send(
+        &self,
+        req: KuzzleRequest,
+        options: QueryOptions,
+    ) -> Result<KuzzleResponse, Error> {
+        let sink = match &self._sink {
+            Some(sink) if self.is_ready() => sink,
+            _ => return Err(Error::NotConnected),
+        };
+
+        let expires_at = options.expires_at(*self._options.request_timeout());
+        options.check(expires_at)?;
+
+        let request_id = protocols::Websocket::request_id(&req);
+        let (sender, receiver) = oneshot::channel();
+        lock(&self._pending).insert(request_id.clone(), sender);
+        // The connection may have been lost after pending requests were failed
+        if !self.is_ready() {
+            lock(&self._pending).remove(&request_id);
+            return Err(Error::NotConnected);
+        }
+
+        let response = bounded(
+            async {
+                let sent = sink
+                    .lock()
+                    .await
+                    .send(Message::Text(
+                        protocols::Websocket::payload(&req, &request_id).to_string(),
+                    ))
+                    .await;
+                if let Err(err) = sent {
+                    // The connection is lost, even if the reader did not notice yet
+                    self._ready.store(false, Ordering::SeqCst);
+                    return Err(Error::from(err));
+                }
+
+                match receiver.await {
+                    Ok(response) => response,
+                    Err(_) => Err(Error::Network("Connection closed by Kuzzle".into())),
+                }
+            },
+            &options,
+            expires_at,
+        )
+        .await;
+
+        lock(&self._pending).remove(&request_id);
+        response
+    }
+
+    async fn close(&mut self) {
+        self._ready.store(false, Ordering::SeqCst);
+        if let Some(sink) = self._sink.take() {
+            let _ = sink.into_inner().close().await;
+        }
+        if let Some(reader) = self._reader.take() {
+            reader.abort();
+        }
+    }
+}
+
+impl Drop for Websocket {
+    fn drop(&mut self) {
+        if let Some(reader) = self._reader.take() {
+            reader.abort();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::CancellationToken;
+    use std::net::TcpListener;
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    /// Serves a single connection, answering each request in reverse order after a notification.
+    fn serve(requests: usize) -> u32 {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port() as u32;
+
+        thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut socket = tungstenite::accept(stream).unwrap();
+            let mut request_ids = Vec::new();
+            while request_ids.len() < requests {
+                if let Message::Text(text) = socket.read().unwrap() {
+                    let request: Value = serde_json::from_str(&text).unwrap();
+                    request_ids.push(request["requestId"].clone());
+                }
+            }
+
+            for request_id in request_ids.iter().rev() {
+                socket
+                    .send(Message::Text(format!(
+                        r#"{{ "requestId": {}, "room": "room", "status": 200, "result": null }}"#,
+                        request_id
+                    )))
+                    .unwrap();
+                socket
+                    .send(Message::Text(format!(
+                        r#"{{
+                            "requestId": {},
+                            "status": 200,
+                            "error": null,
+                            "controller": "server",
+                            "action": "now",
+                            "collection": null,
+                            "index": null,
+                            "volatile": null,
+                            "result": {{ "now": 1447151167622 }}
+                        }}"#,
+                        request_id
+                    )))
+                    .unwrap();
+            }
+            while socket.read().is_ok() {}
+        });
+
+        port
+    }
+
+    fn serve_silently() -> u32 {
+        serve(usize::MAX)
+    }
+
+    #[tokio::test]
+    async fn send_ok_concurrent() {
+        let mut ws = Websocket::new(KuzzleOptions::new("127.0.0.1", serve(2)));
+        let mut notifications = ws.notifications();
+        ws.connect().await.unwrap();
+
+        let first = ws.send(
+            KuzzleRequest::new("server", "now").set_request_id("first"),
+            QueryOptions::new(),
+        );
+        let second = ws.send(
+            KuzzleRequest::new("server", "now").set_request_id("second"),
+            QueryOptions::new(),
+        );
+        let (first, second) = tokio::join!(first, second);
+
+        assert_eq!(first.unwrap().request_id(), "first");
+        assert_eq!(second.unwrap().result()["now"], 1447151167622u64);
+        for request_id in &["second", "first"] {
+            let notification = notifications.recv().await.unwrap();
+            assert_eq!(notification.room_id(), "room");
+            assert_eq!(notification.request_id(), request_id);
+        }
+
+        ws.close().await;
+        assert!(!ws.is_ready());
+    }
+
+    #[tokio::test]
+    async fn connect_ok_after_connection_lost() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port() as u32;
+
+        // Drops the first connection, then answers a request on the second one
+        let server = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            drop(tungstenite::accept(stream).unwrap());
+
+            let (stream, _) = listener.accept().unwrap();
+            let mut socket = tungstenite::accept(stream).unwrap();
+            let request: Value =
+                serde_json::from_str(&socket.read().unwrap().into_text().unwrap()).unwrap();
+            socket
+                .send(Message::Text(format!(
+                    r#"{{ "requestId": {}, "status": 200, "result": null }}"#,
+                    request["requestId"]
+                )))
+                .unwrap();
+        });
+
+        let mut ws = Websocket::new(KuzzleOptions::new("127.0.0.1", port));
+        ws.connect().await.unwrap();
+        let lost = ws
+            .send(
+                KuzzleRequest::new("server", "now"),
+                QueryOptions::new().set_timeout(1000),
+            )
+            .await;
+        // Depending on whether the connection loss was noticed before the request was sent
+        assert!(matches!(
+            lost,
+            Err(Error::Network(_)) | Err(Error::NotConnected)
+        ));
+        assert!(!ws.is_ready());
+
+        ws.connect().await.unwrap();
+        let res = ws
+            .send(KuzzleRequest::new("server", "now"), QueryOptions::new())
+            .await;
+        assert_eq!(res.unwrap().status(), &200);
+        tokio::task::spawn_blocking(move || server.join().unwrap())
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn send_fail_not_connected() {
+        let ws = Websocket::new(KuzzleOptions::new("127.0.0.1", 7512));
+        let res = ws
+            .send(KuzzleRequest::new("server", "now"), QueryOptions::new())
+            .await;
+
+        assert!(matches!(res, Err(Error::NotConnected)));
+    }
+
+    #[tokio::test]
+    async fn send_fail_timeout() {
+        let options = KuzzleOptions::new("127.0.0.1", serve_silently()).set_request_timeout(50);
+        let mut ws = Websocket::new(options);
+        ws.connect().await.unwrap();
+
+        let start = Instant::now();
+        let res = ws
+            .send(KuzzleRequest::new("server", "now"), QueryOptions::new())
+            .await;
+        assert!(matches!(res, Err(Error::Timeout)));
+        assert!(start.elapsed() >= Duration::from_millis(50));
+        assert!(lock(&ws._pending).is_empty());
+    }
+
+    #[tokio::test]
+    async fn send_fail_cancelled() {
+        let mut ws = Websocket::new(KuzzleOptions::new("127.0.0.1", serve_silently()));
+        ws.connect().await.unwrap();
+
+        let token = CancellationToken::new();
+        let canceller = token.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            canceller.cancel();
+        });
+
+        let res = ws
+            .send(
+                KuzzleRequest::new("server", "now"),
+                QueryOptions::new().set_cancellation_token(token),
+            )
+            .await;
+        assert!(matches!(res, Err(Error::Cancelled)));
+    }
+}
@@ -0,0 +1,418 @@
+//! Prometheus exporter for Kuzzle server statistics.
+//!
+//! Available with the `prometheus` cargo feature.
+//!
+//! A `Collector` polls a Kuzzle node with `server:healthCheck`, `server:getLastStats`
+//! and `server:getStats`, and renders the result in the Prometheus text exposition format.
+//! Kuzzle statistics frames only hold requests counted since the previous frame:
+//! the collector sums them up into counters, starting from its first poll.
+
+use crate::kuzzle::Kuzzle;
+use crate::types::{Error, HealthStatus, QueryOptions, StatsFrame};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write;
+
+/// Polls a Kuzzle node and renders its statistics as Prometheus metrics.
+pub struct Collector {
+    _kuzzle: Kuzzle,
+    _up: bool,
+    _health: Option<(HealthStatus, BTreeMap<String, HealthStatus>)>,
+    _last_frame: Option<StatsFrame>,
+    _completed_requests: BTreeMap<String, u64>,
+    _failed_requests: BTreeMap<String, u64>,
+}
+
+impl Collector {
+    /// Returns a Collector polling the node the given `Kuzzle` client is connected to.
+    /// The client must be logged in with a user allowed to access `server` statistics.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::prometheus::Collector;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::types::KuzzleOptions;
+    ///
+    /// let kuzzle = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+    /// let mut collector = Collector::new(kuzzle);
+    ///
+    /// // Serve this page on your metrics endpoint
+    /// let page = collector.render();
+    /// ```
+    pub fn new(kuzzle: Kuzzle) -> Collector {
+        Collector {
+            _kuzzle: kuzzle,
+            _up: false,
+            _health: None,
+            _last_frame: None,
+            _completed_requests: BTreeMap::new(),
+            _failed_requests: BTreeMap::new(),
+        }
+    }
+
+    /// Fetches fresh statistics from Kuzzle.
+    pub fn poll(&mut self) -> Result<(), Error> {
+        self._up = false;
+        self._kuzzle.connect()?;
+
+        let health = self._kuzzle.server().health_check(QueryOptions::new())?;
+        self._health = Some((
+            health.status().clone(),
+            health
+                .services()
+                .iter()
+                .map(|(name, status)| (name.clone(), status.clone()))
+                .collect(),
+        ));
+
+        let last_frame = self._kuzzle.server().get_last_stats(QueryOptions::new())?;
+        let frames = match &self._last_frame {
+            None => vec![last_frame.clone()],
+            Some(previous) if previous.timestamp() < last_frame.timestamp() => self
+                ._kuzzle
+                .server()
+                .get_stats(
+                    previous.timestamp() as i64,
+                    last_frame.timestamp() as i64,
+                    QueryOptions::new(),
+                )?
+                .into_iter()
+                .filter(|frame| frame.timestamp() > previous.timestamp())
+                .collect(),
+            Some(_) => vec![],
+        };
+
+        for frame in &frames {
+            add_all(&mut self._completed_requests, frame.completed_requests());
+            add_all(&mut self._failed_requests, frame.failed_requests());
+        }
+
+        self._last_frame = Some(last_frame);
+        self._up = true;
+        Ok(())
+    }
+
+    /// Polls Kuzzle and returns the metrics page.
+    /// If Kuzzle can't be reached, `kuzzle_up` is set to 0 and the previously
+    /// collected metrics are rendered.
+    pub fn render(&mut self) -> String {
+        let _ = self.poll();
+        self.metrics()
+    }
+
+    /// Returns the metrics page, as of the last poll.
+    pub fn metrics(&self) -> String {
+        let mut page = String::new();
+
+        write_metric(
+            &mut page,
+            "kuzzle_up",
+            "gauge",
+            "Whether the last poll of the Kuzzle node succeeded.",
+            &[(vec![], if self._up { 1 } else { 0 })],
+        );
+
+        if let Some((status, services)) = &self._health {
+            write_metric(
+                &mut page,
+                "kuzzle_healthy",
+                "gauge",
+                "Whether the Kuzzle node reports a green status.",
+                &[(vec![], healthy(status))],
+            );
+            write_metric(
+                &mut page,
+                "kuzzle_service_healthy",
+                "gauge",
+                "Whether a Kuzzle external service reports a green status.",
+                &services
+                    .iter()
+                    .map(|(name, status)| (vec![("service", name.as_str())], healthy(status)))
+                    .collect::<Vec<_>>(),
+            );
+        }
+
+        if let Some(frame) = &self._last_frame {
+            write_metric(
+                &mut page,
+                "kuzzle_connections",
+                "gauge",
+                "Number of opened connections, per protocol.",
+                &per_protocol(frame.connections()),
+            );
+            write_metric(
+                &mut page,
+                "kuzzle_ongoing_requests",
+                "gauge",
+                "Number of requests being processed, per protocol.",
+                &per_protocol(frame.ongoing_requests()),
+            );
+            write_metric(
+                &mut page,
+                "kuzzle_stats_timestamp_milliseconds",
+                "gauge",
+                "Date of the last statistics frame, in Epoch-millis format.",
+                &[(vec![], frame.timestamp())],
+            );
+        }
+
+        write_metric(
+            &mut page,
+            "kuzzle_completed_requests_total",
+            "counter",
+            "Number of completed requests since the collector started, per protocol.",
+            &per_protocol(&self._completed_requests),
+        );
+        write_metric(
+            &mut page,
+            "kuzzle_failed_requests_total",
+            "counter",
+            "Number of failed requests since the collector started, per protocol.",
+            &per_protocol(&self._failed_requests),
+        );
+
+        page
+    }
+}
+
+fn add_all(counters: &mut BTreeMap<String, u64>, values: &HashMap<String, u64>) {
+    for (protocol, count) in values {
+        *counters.entry(protocol.clone()).or_insert(0) += count;
+    }
+}
+
+fn healthy(status: &HealthStatus) -> u64 {
+    if *status == HealthStatus::Green {
+        1
+    } else {
+        0
+    }
+}
+
+fn per_protocol<'a, M>(values: M) -> Vec<(Vec<(&'static str, &'a str)>, u64)>
+where
+    M: IntoIterator<Item = (&'a String, &'a u64)>,
+{
+    let mut samples: Vec<_> = values
+        .into_iter()
+        .map(|(protocol, count)| (vec![("protocol", protocol.as_str())], *count))
+        .collect();
+    samples.sort();
+    samples
+}
+
+fn write_metric(
+    page: &mut String,
+    name: &str,
+    kind: &str,
+    help: &str,
+    samples: &[(Vec<(&str, &str)>, u64)],
+) {
+    let _ = writeln!(page, "# HELP {} {}", name, help);
+    let _ = writeln!(page, "# TYPE {} {}", name, kind);
+
+    for (labels, value) in samples {
+        if labels.is_empty() {
+            let _ = writeln!(page, "{} {}", name, value);
+        } else {
+            let labels = labels
+                .iter()
+                .map(|(label, value)| format!("{}=\"{}\"", label, escape(value)))
+                .collect::<Vec<_>>()
+                .join(",");
+            let _ = writeln!(page, "{}{{{}}} {}", name, labels, value);
+        }
+    }
+}
+
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocols::Http;
+    use crate::types::KuzzleOptions;
+    use mockito;
+
+    fn response(action: &str, result: &str) -> String {
+        format!(
+            r#"{{
+                "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                "status": 200,
+                "error": null,
+                "controller": "server",
+                "action": "{}",
+                "collection": null,
+                "index": null,
+                "volatile": null,
+                "result": {}
+            }}"#,
+            action, result
+        )
+    }
+
+    #[test]
+    fn render_ok() {
+        let _health = mockito::mock("GET", "/_healthCheck")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(response(
+                "healthCheck",
+                r#"{
+                    "status": "green",
+                    "services": { "storageEngine": "green", "internalCache": "red" }
+                }"#,
+            ))
+            .create();
+        let first_frame = mockito::mock("GET", "/_getLastStats")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(response(
+                "getLastStats",
+                r#"{
+                    "completedRequests": { "websocket": 10, "http": 4 },
+                    "failedRequests": { "http": 1 },
+                    "ongoingRequests": { "http": 2 },
+                    "connections": { "websocket": 13 },
+                    "timestamp": 1453110641308
+                }"#,
+            ))
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let mut collector = Collector::new(k);
+        let page = collector.render();
+
+        assert!(page.contains("kuzzle_up 1\n"));
+        assert!(page.contains("kuzzle_healthy 1\n"));
+        assert!(page.contains("kuzzle_service_healthy{service=\"internalCache\"} 0\n"));
+        assert!(page.contains("kuzzle_connections{protocol=\"websocket\"} 13\n"));
+        assert!(page.contains("kuzzle_completed_requests_total{protocol=\"http\"} 4\n"));
+        assert!(page.contains("kuzzle_failed_requests_total{protocol=\"http\"} 1\n"));
+        drop(first_frame);
+
+        let _last_frame = mockito::mock("GET", "/_getLastStats")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(response(
+                "getLastStats",
+                r#"{
+                    "completedRequests": { "http": 3 },
+                    "connections": { "websocket": 12 },
+                    "timestamp": 1453110661308
+                }"#,
+            ))
+            .create();
+        let _stats = mockito::mock("GET", mockito::Matcher::Regex(r"^/_getStats\?".to_string()))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(response(
+                "getStats",
+                r#"{
+                "total": 3,
+                "hits": [
+                    { "completedRequests": { "http": 4 }, "timestamp": 1453110641308 },
+                    { "completedRequests": { "http": 5 }, "timestamp": 1453110651308 },
+                    { "completedRequests": { "http": 3 }, "timestamp": 1453110661308 }
+                ]
+            }"#,
+            ))
+            .create();
+
+        let page = collector.render();
+
+        assert!(page.contains("kuzzle_up 1\n"));
+        assert!(page.contains("kuzzle_connections{protocol=\"websocket\"} 12\n"));
+        assert!(page.contains("kuzzle_completed_requests_total{protocol=\"http\"} 12\n"));
+        assert!(page.contains("kuzzle_completed_requests_total{protocol=\"websocket\"} 10\n"));
+        assert!(page.contains("kuzzle_stats_timestamp_milliseconds 1453110661308\n"));
+    }
+
+    #[test]
+    fn render_ok_red() {
+        let _health = mockito::mock("GET", "/_healthCheck")
+            .with_status(503)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 503,
+                    "error": {
+                      "message": "Service unavailable.",
+                      "status": 503,
+                      "stack": null
+                    },
+                    "controller": "server",
+                    "action": "healthCheck",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": {
+                      "status": "red",
+                      "services": { "storageEngine": "red" }
+                    }
+                }"#,
+            )
+            .create();
+        let _frame = mockito::mock("GET", "/_getLastStats")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(response(
+                "getLastStats",
+                r#"{ "connections": { "websocket": 1 }, "timestamp": 1453110641308 }"#,
+            ))
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let mut collector = Collector::new(k);
+        let page = collector.render();
+
+        assert!(page.contains("kuzzle_up 1\n"));
+        assert!(page.contains("kuzzle_healthy 0\n"));
+        assert!(page.contains("kuzzle_service_healthy{service=\"storageEngine\"} 0\n"));
+    }
+
+    #[test]
+    fn render_fail_error() {
+        let _m = mockito::mock("GET", "/_healthCheck")
+            .with_status(403)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 403,
+                    "error": {
+                      "message": "Forbidden action [null/null/server/healthCheck] for user -1",
+                      "status": 403,
+                      "stack": "ForbiddenError: Forbidden action [null/null/server/healthCheck] for user -1\n"
+                    },
+                    "controller": "server",
+                    "action": "healthCheck",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": null
+                }"#,
+            )
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        let mut collector = Collector::new(k);
+
+        assert!(collector.poll().is_err());
+        assert!(collector
+            .metrics()
+            .contains("# TYPE kuzzle_up gauge\nkuzzle_up 0\n"));
+    }
+
+    #[test]
+    fn escape_ok() {
+        assert_eq!(escape("a\"b\\c\nd"), "a\\\"b\\\\c\\nd");
+    }
+}
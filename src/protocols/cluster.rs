@@ -0,0 +1,828 @@
+use crate::controllers::server::actions as server;
+use crate::protocols::{Http, Protocol, Routes};
+use crate::types::{
+    Error, HealthStatus, KuzzleOptions, KuzzleRequest, KuzzleResponse, QueryOptions,
+};
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex, OnceLock, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+/// Time given to a node to answer its health check, in milliseconds.
+pub(crate) const HEALTH_CHECK_TIMEOUT: u64 = 5000;
+
+/// Time between two checks of the connection to the current node.
+const WATCH_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Time between two health checks of the current node.
+const HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Prefixes of the actions which only read data despite being sent with `POST`.
+const POST_READERS: [&str; 5] = ["search", "mGet", "count", "validate", "checkToken"];
+
+pub(crate) type ResubscribedListener = Box<dyn Fn(&str, &str) + Send + Sync>;
+
+type Listeners = Arc<Mutex<Vec<Sender<KuzzleResponse>>>>;
+
+/// Sends requests to a Kuzzle cluster, through one connection per node.
+///
+/// Requests are sent to a single live node. Once its connection is lost, or it
+/// fails its periodic `server:healthCheck`, the next node answering one takes over.
+/// Requests which only read data and failed on the lost node are then sent again
+/// to it, while others fail with the network error.
+/// Realtime subscriptions are made again on the new node if `auto_resubscribe`
+/// is enabled, which requires a `Websocket` connection. They may then be given
+/// a new room id, reported to the `on_resubscribed` listener.
+pub struct Cluster {
+    _nodes: Arc<Nodes>,
+    _listeners: Listeners,
+    _watch_interval: Option<Duration>,
+    _watcher: Option<(Sender<()>, JoinHandle<()>)>,
+}
+
+/// The nodes of a cluster, shared with the thread watching the current one.
+struct Nodes {
+    _nodes: Vec<Node>,
+    _current: Arc<AtomicUsize>,
+    _failover: Mutex<()>,
+    _subscriptions: Subscriptions,
+    _auto_resubscribe: bool,
+    _on_resubscribed: RwLock<Option<ResubscribedListener>>,
+}
+
+struct Node {
+    _address: String,
+    _protocol: RwLock<Box<dyn Protocol>>,
+}
+
+impl Cluster {
+    /// Returns a cluster client connecting to every node of `options.nodes()`,
+    /// with the protocol built by `protocol`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::{Cluster, Websocket};
+    /// use kuzzle_sdk::types::KuzzleOptions;
+    ///
+    /// let options = KuzzleOptions::new("kuzzle-1", 7512)
+    ///     .add_node("kuzzle-2", 7512)
+    ///     .add_node("kuzzle-3", 7512);
+    /// let _kuzzle = Kuzzle::new(Cluster::new(options, Websocket::new));
+    /// ```
+    pub fn new<P, F>(options: KuzzleOptions, protocol: F) -> Cluster
+    where
+        P: 'static + Protocol,
+        F: Fn(KuzzleOptions) -> P,
+    {
+        let mut cluster = Cluster::with_nodes(
+            node_options(&options)
+                .into_iter()
+                .map(|(address, options)| {
+                    (address, Box::new(protocol(options)) as Box<dyn Protocol>)
+                })
+                .collect(),
+            *options.auto_resubscribe(),
+        );
+        cluster._watch_interval = Some(WATCH_INTERVAL);
+        cluster
+    }
+
+    fn with_nodes(nodes: Vec<(String, Box<dyn Protocol>)>, auto_resubscribe: bool) -> Cluster {
+        let current = Arc::new(AtomicUsize::new(0));
+        let listeners = Listeners::default();
+
+        for (index, (_, protocol)) in nodes.iter().enumerate() {
+            if let Some(notifications) = protocol.notification_receiver() {
+                let (current, listeners) = (current.clone(), listeners.clone());
+                thread::spawn(move || forward(index, notifications, &current, &listeners));
+            }
+        }
+
+        Cluster {
+            _nodes: Arc::new(Nodes {
+                _nodes: nodes
+                    .into_iter()
+                    .map(|(address, protocol)| Node {
+                        _address: address,
+                        _protocol: RwLock::new(protocol),
+                    })
+                    .collect(),
+                _current: current,
+                _failover: Mutex::new(()),
+                _subscriptions: Subscriptions::default(),
+                _auto_resubscribe: auto_resubscribe,
+                _on_resubscribed: RwLock::new(None),
+            }),
+            _listeners: listeners,
+            _watch_interval: None,
+            _watcher: None,
+        }
+    }
+
+    /// Sets a function called with the previous and the new room id of each subscription
+    /// made again on a new node. The new room id is the one to unsubscribe from.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::protocols::{Cluster, Websocket};
+    /// use kuzzle_sdk::types::KuzzleOptions;
+    ///
+    /// let options = KuzzleOptions::new("kuzzle-1", 7512).add_node("kuzzle-2", 7512);
+    /// let _cluster = Cluster::new(options, Websocket::new).on_resubscribed(|previous, room_id| {
+    ///     println!("Subscription {} moved to {}", previous, room_id)
+    /// });
+    /// ```
+    pub fn on_resubscribed<F>(self, listener: F) -> Self
+    where
+        F: Fn(&str, &str) + Send + Sync + 'static,
+    {
+        *self
+            ._nodes
+            ._on_resubscribed
+            .write()
+            .unwrap_or_else(PoisonError::into_inner) = Some(Box::new(listener));
+        self
+    }
+
+    /// Returns a receiver of the realtime notifications sent by the node requests
+    /// are sent to. Notifications keep coming from the new node after a failover.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use kuzzle_sdk::protocols::{Cluster, Websocket};
+    /// use kuzzle_sdk::types::KuzzleOptions;
+    ///
+    /// let options = KuzzleOptions::new("kuzzle-1", 7512).add_node("kuzzle-2", 7512);
+    /// let cluster = Cluster::new(options, Websocket::new);
+    /// for notification in cluster.notifications() {
+    ///     println!("{:?}", notification.result());
+    /// }
+    /// ```
+    pub fn notifications(&self) -> Receiver<KuzzleResponse> {
+        let (sender, receiver) = mpsc::channel();
+        self._listeners
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(sender);
+        receiver
+    }
+
+    /// Returns the address (`host:port`) of the node requests are sent to.
+    pub fn current_node(&self) -> &str {
+        &self._nodes._nodes[self._nodes.current()]._address
+    }
+
+    /// Starts watching the current node, unless it is already watched.
+    fn watch(&mut self) {
+        let interval = match (self._watch_interval, &self._watcher) {
+            (Some(interval), None) => interval,
+            _ => return,
+        };
+
+        let (stop, stopped) = mpsc::channel();
+        let nodes = self._nodes.clone();
+        let watcher = thread::spawn(move || nodes.watch(interval, &stopped));
+        self._watcher = Some((stop, watcher));
+    }
+
+    fn unwatch(&mut self) {
+        if let Some((stop, watcher)) = self._watcher.take() {
+            drop(stop);
+            let _ = watcher.join();
+        }
+    }
+}
+
+impl Drop for Cluster {
+    fn drop(&mut self) {
+        self.unwatch();
+    }
+}
+
+impl Nodes {
+    fn current(&self) -> usize {
+        self._current.load(Ordering::SeqCst)
+    }
+
+    fn protocol(&self, index: usize) -> RwLockReadGuard<'_, Box<dyn Protocol>> {
+        self._nodes[index]
+            ._protocol
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn protocol_mut(&self, index: usize) -> RwLockWriteGuard<'_, Box<dyn Protocol>> {
+        self._nodes[index]
+            ._protocol
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Checks the health of the node at `index`, through its `protocol`.
+    fn check(&self, index: usize, protocol: &dyn Protocol) -> Result<(), Error> {
+        let call = server::health_check()?;
+        let health = protocol
+            .send(
+                call.request,
+                QueryOptions::new().set_timeout(HEALTH_CHECK_TIMEOUT),
+            )
+            .and_then(call.parse)?;
+        match health.status() {
+            HealthStatus::Red => Err(unhealthy(&self._nodes[index]._address)),
+            _ => Ok(()),
+        }
+    }
+
+    /// Connects to the node at `index` if needed, and checks its health.
+    fn revive(&self, index: usize) -> Result<(), Error> {
+        let mut protocol = self.protocol_mut(index);
+        if !protocol.is_ready() {
+            protocol.connect()?;
+        }
+
+        let health = self.check(index, protocol.as_ref());
+        if health.is_err() {
+            protocol.close();
+        }
+        health
+    }
+
+    /// Returns the first live node, trying them in order from `first`.
+    fn select_node(&self, first: usize) -> Result<usize, Error> {
+        let mut last_error = Error::NotConnected;
+
+        for offset in 0..self._nodes.len() {
+            let index = (first + offset) % self._nodes.len();
+            match self.revive(index) {
+                Ok(()) => return Ok(index),
+                Err(err) => last_error = err,
+            }
+        }
+
+        Err(last_error)
+    }
+
+    /// Replaces the node at `failed` with the next live one,
+    /// unless another request already did.
+    fn failover(&self, failed: usize) -> Result<(), Error> {
+        let _failover = self
+            ._failover
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        if self.current() != failed {
+            return Ok(());
+        }
+
+        self.protocol_mut(failed).close();
+        let next = self.select_node(failed + 1)?;
+        self._current.store(next, Ordering::SeqCst);
+
+        if self._auto_resubscribe {
+            let protocol = self.protocol(next);
+            let listener = self
+                ._on_resubscribed
+                .read()
+                .unwrap_or_else(PoisonError::into_inner);
+            for (room_id, req) in self._subscriptions.requests() {
+                let res = protocol.send(req, QueryOptions::new());
+                if let Some(new_room_id) = self._subscriptions.restore(&room_id, &res) {
+                    if let Some(listener) = &*listener {
+                        listener(&room_id, &new_room_id);
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Fails over as soon as the connection to the current node is lost, or it fails
+    /// its health check, until `stopped` is disconnected. While every node is down,
+    /// another one is looked for every `interval`.
+    fn watch(&self, interval: Duration, stopped: &Receiver<()>) {
+        let mut last_check = Instant::now();
+
+        while let Err(RecvTimeoutError::Timeout) = stopped.recv_timeout(interval) {
+            let index = self.current();
+            let mut alive = self.protocol(index).is_ready();
+
+            if alive && last_check.elapsed() >= HEALTH_CHECK_INTERVAL {
+                last_check = Instant::now();
+                alive = self.check(index, self.protocol(index).as_ref()).is_ok();
+            }
+            if !alive {
+                let _ = self.failover(index);
+            }
+        }
+    }
+}
+
+/// Hands the notifications of the node at `index` over to `listeners`,
+/// as long as it is the `current` node.
+fn forward(
+    index: usize,
+    notifications: Receiver<KuzzleResponse>,
+    current: &AtomicUsize,
+    listeners: &Mutex<Vec<Sender<KuzzleResponse>>>,
+) {
+    for notification in notifications {
+        if current.load(Ordering::SeqCst) == index {
+            listeners
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .retain(|listener| listener.send(notification.clone()).is_ok());
+        }
+    }
+}
+
+impl Protocol for Cluster {
+    fn once(&self) {
+        self._nodes.protocol(self._nodes.current()).once()
+    }
+
+    fn listener_count(&self) {
+        self._nodes.protocol(self._nodes.current()).listener_count()
+    }
+
+    fn connect(&mut self) -> Result<(), Error> {
+        let index = self._nodes.select_node(self._nodes.current())?;
+        self._nodes._current.store(index, Ordering::SeqCst);
+        self.watch();
+        Ok(())
+    }
+
+    fn send(&self, req: KuzzleRequest, options: QueryOptions) -> Result<KuzzleResponse, Error> {
+        let nodes = &self._nodes;
+        let idempotent = is_idempotent(&req);
+        let mut attempts = 0;
+
+        loop {
+            let index = nodes.current();
+            let res = nodes.protocol(index).send(req.clone(), options.clone());
+
+            match res {
+                Err(err) if is_network_error(&err) => {
+                    attempts += 1;
+                    let failover = nodes.failover(index);
+                    if !idempotent || attempts >= nodes._nodes.len() || failover.is_err() {
+                        return Err(err);
+                    }
+                }
+                res => {
+                    if let Ok(res) = &res {
+                        nodes._subscriptions.track(&req, res);
+                    }
+                    return res;
+                }
+            }
+        }
+    }
+
+    fn close(&mut self) {
+        self.unwatch();
+        for index in 0..self._nodes._nodes.len() {
+            self._nodes.protocol_mut(index).close();
+        }
+    }
+
+    fn is_ready(&self) -> bool {
+        self._nodes.protocol(self._nodes.current()).is_ready()
+    }
+
+    fn notification_receiver(&self) -> Option<Receiver<KuzzleResponse>> {
+        Some(self.notifications())
+    }
+}
+
+/// Returns the address and the options of each node of the cluster.
+pub(crate) fn node_options(options: &KuzzleOptions) -> Vec<(String, KuzzleOptions)> {
+    options
+        .nodes()
+        .into_iter()
+        .map(|(host, port)| {
+            (
+                format!("{}:{}", host, port),
+                options.clone().set_host(&host).set_port(port),
+            )
+        })
+        .collect()
+}
+
+/// Returns true if sending `req` twice has the same effect as sending it once.
+pub(crate) fn is_idempotent(req: &KuzzleRequest) -> bool {
+    static ROUTES: OnceLock<Routes> = OnceLock::new();
+
+    let (controller, action) = (req.controller().as_str(), req.action().as_str());
+    if controller == "realtime" && action == "subscribe" {
+        return true;
+    }
+
+    match ROUTES
+        .get_or_init(Http::bundled_routes)
+        .get(controller)
+        .and_then(|routes| routes.get(action))
+    {
+        Some(route) if route.verb == "GET" => true,
+        Some(_) => POST_READERS.iter().any(|prefix| action.starts_with(prefix)),
+        None => false,
+    }
+}
+
+/// Returns true if `err` means that the node can't be reached.
+pub(crate) fn is_network_error(err: &Error) -> bool {
+    matches!(err, Error::Network(_) | Error::NotConnected)
+}
+
+pub(crate) fn unhealthy(address: &str) -> Error {
+    Error::Network(format!("Kuzzle node {} is unhealthy", address).into())
+}
+
+/// Realtime subscriptions made through a cluster, by room id.
+#[derive(Default)]
+pub(crate) struct Subscriptions(Mutex<HashMap<String, KuzzleRequest>>);
+
+impl Subscriptions {
+    /// Keeps track of the subscriptions made or removed by `req`.
+    pub(crate) fn track(&self, req: &KuzzleRequest, res: &KuzzleResponse) {
+        if req.controller() != "realtime" || res.error().is_some() {
+            return;
+        }
+
+        let mut subscriptions = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+        match (req.action().as_str(), res.result()["roomId"].as_str()) {
+            ("subscribe", Some(room_id)) => {
+                subscriptions.insert(room_id.to_string(), req.clone());
+            }
+            ("unsubscribe", Some(room_id)) => {
+                subscriptions.remove(room_id);
+            }
+            _ => (),
+        }
+    }
+
+    /// Returns the room id of the current subscriptions, and the requests which made them.
+    pub(crate) fn requests(&self) -> Vec<(String, KuzzleRequest)> {
+        self.0
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .iter()
+            .map(|(room_id, req)| (room_id.clone(), req.clone()))
+            .collect()
+    }
+
+    /// Keeps track of the subscription `room_id` made again on a new node, and returns
+    /// its new room id. Subscriptions refused by Kuzzle are forgotten, while the ones
+    /// which could not be sent are kept to be made again on the next failover.
+    pub(crate) fn restore(
+        &self,
+        room_id: &str,
+        res: &Result<KuzzleResponse, Error>,
+    ) -> Option<String> {
+        let res = res.as_ref().ok()?;
+        let mut subscriptions = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+        // Unsubscribed from in the meantime
+        let req = subscriptions.remove(room_id)?;
+
+        match (res.error(), res.result()["roomId"].as_str()) {
+            (None, Some(new_room_id)) => {
+                subscriptions.insert(new_room_id.to_string(), req);
+                Some(new_room_id.to_string())
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kuzzle::Kuzzle;
+    use crate::protocols::Mock;
+    use serde_json::{json, Value};
+    use std::sync::atomic::AtomicBool;
+    use std::sync::Arc;
+
+    type Received = Arc<Mutex<Vec<String>>>;
+
+    /// A node which answers every request, unless it is down.
+    struct FakeNode {
+        index: usize,
+        down: Arc<AtomicBool>,
+        ready: bool,
+        received: Received,
+    }
+
+    impl FakeNode {
+        fn boxed(index: usize, down: &Arc<AtomicBool>, received: &Received) -> Box<dyn Protocol> {
+            Box::new(FakeNode {
+                index,
+                down: down.clone(),
+                ready: false,
+                received: received.clone(),
+            })
+        }
+    }
+
+    impl Protocol for FakeNode {
+        fn once(&self) {}
+
+        fn listener_count(&self) {}
+
+        fn connect(&mut self) -> Result<(), Error> {
+            if self.down.load(Ordering::SeqCst) {
+                return Err(Error::Network("Connection refused".into()));
+            }
+            self.ready = true;
+            Ok(())
+        }
+
+        fn send(
+            &self,
+            req: KuzzleRequest,
+            _options: QueryOptions,
+        ) -> Result<KuzzleResponse, Error> {
+            if !self.ready {
+                return Err(Error::NotConnected);
+            }
+            if self.down.load(Ordering::SeqCst) {
+                return Err(Error::Network("Connection reset by peer".into()));
+            }
+
+            let action = format!("{}:{}", req.controller(), req.action());
+            self.received.lock().unwrap().push(action.clone());
+            let result = match action.as_str() {
+                "server:healthCheck" => json!({ "status": "green" }),
+                // Each node gives subscriptions its own room id
+                "realtime:subscribe" => json!({ "roomId": format!("room-{}", self.index) }),
+                "realtime:unsubscribe" => json!({ "roomId": req.body()["roomId"] }),
+                _ => Value::Null,
+            };
+
+            Ok(serde_json::from_value(json!({
+                "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                "status": 200,
+                "error": null,
+                "controller": req.controller(),
+                "action": req.action(),
+                "volatile": null,
+                "result": result
+            }))?)
+        }
+
+        fn close(&mut self) {
+            self.ready = false;
+        }
+
+        fn is_ready(&self) -> bool {
+            self.ready
+        }
+    }
+
+    fn cluster(size: usize) -> (Cluster, Vec<Arc<AtomicBool>>, Vec<Received>) {
+        let downs: Vec<_> = (0..size)
+            .map(|_| Arc::new(AtomicBool::new(false)))
+            .collect();
+        let received: Vec<Received> = (0..size).map(|_| Arc::default()).collect();
+        let nodes = (0..size)
+            .map(|i| {
+                (
+                    format!("kuzzle-{}:7512", i),
+                    FakeNode::boxed(i, &downs[i], &received[i]),
+                )
+            })
+            .collect();
+
+        (Cluster::with_nodes(nodes, true), downs, received)
+    }
+
+    #[test]
+    fn node_options_ok() {
+        let options = KuzzleOptions::new("kuzzle-1", 7512)
+            .add_node("kuzzle-2", 7513)
+            .set_ssl_connection(true);
+        let nodes = node_options(&options);
+
+        assert_eq!(nodes.len(), 2);
+        assert_eq!(nodes[1].0, "kuzzle-2:7513");
+        assert_eq!(nodes[1].1.host(), "kuzzle-2");
+        assert_eq!(*nodes[1].1.port(), 7513);
+        assert!(*nodes[1].1.ssl_connection());
+    }
+
+    #[test]
+    fn is_idempotent_ok() {
+        assert!(is_idempotent(&KuzzleRequest::new("document", "get")));
+        assert!(is_idempotent(&KuzzleRequest::new("document", "search")));
+        assert!(is_idempotent(&KuzzleRequest::new("realtime", "subscribe")));
+        assert!(!is_idempotent(&KuzzleRequest::new("document", "create")));
+        assert!(!is_idempotent(&KuzzleRequest::new("ms", "getset")));
+        assert!(!is_idempotent(&KuzzleRequest::new("ferris", "pinch")));
+    }
+
+    #[test]
+    fn connect_ok_first_node_down() {
+        let (cluster, downs, _) = cluster(3);
+        downs[0].store(true, Ordering::SeqCst);
+        let k = Kuzzle::new(cluster);
+
+        assert!(k.connect().is_ok());
+        assert!(k
+            .query(KuzzleRequest::new("server", "now"), QueryOptions::new())
+            .is_ok());
+    }
+
+    #[test]
+    fn connect_fail_all_nodes_down() {
+        let (mut cluster, downs, _) = cluster(2);
+        downs[0].store(true, Ordering::SeqCst);
+        downs[1].store(true, Ordering::SeqCst);
+
+        assert!(matches!(cluster.connect(), Err(Error::Network(_))));
+    }
+
+    #[test]
+    fn send_ok_failover() {
+        let (mut cluster, downs, received) = cluster(2);
+        cluster.connect().unwrap();
+        downs[0].store(true, Ordering::SeqCst);
+
+        let res = cluster.send(KuzzleRequest::new("document", "get"), QueryOptions::new());
+
+        assert!(res.is_ok());
+        assert_eq!(cluster.current_node(), "kuzzle-1:7512");
+        assert_eq!(
+            *received[1].lock().unwrap(),
+            vec!["server:healthCheck", "document:get"]
+        );
+    }
+
+    #[test]
+    fn send_fail_not_idempotent() {
+        let (mut cluster, downs, received) = cluster(2);
+        cluster.connect().unwrap();
+        downs[0].store(true, Ordering::SeqCst);
+
+        let res = cluster.send(
+            KuzzleRequest::new("document", "create"),
+            QueryOptions::new(),
+        );
+
+        assert!(matches!(res, Err(Error::Network(_))));
+        assert_eq!(cluster.current_node(), "kuzzle-1:7512");
+        assert_eq!(*received[1].lock().unwrap(), vec!["server:healthCheck"]);
+        assert!(cluster
+            .send(
+                KuzzleRequest::new("document", "create"),
+                QueryOptions::new()
+            )
+            .is_ok());
+    }
+
+    #[test]
+    fn send_ok_resubscribe() {
+        let (mut cluster, downs, received) = cluster(2);
+        cluster.connect().unwrap();
+        cluster
+            .send(
+                KuzzleRequest::new("realtime", "subscribe"),
+                QueryOptions::new(),
+            )
+            .unwrap();
+        downs[0].store(true, Ordering::SeqCst);
+
+        cluster
+            .send(KuzzleRequest::new("server", "now"), QueryOptions::new())
+            .unwrap();
+
+        assert_eq!(
+            *received[1].lock().unwrap(),
+            vec!["server:healthCheck", "realtime:subscribe", "server:now"]
+        );
+    }
+
+    #[test]
+    fn send_ok_unsubscribe_after_failover() {
+        let (cluster, downs, received) = cluster(2);
+        let resubscribed: Received = Arc::default();
+        let mut cluster = cluster.on_resubscribed({
+            let resubscribed = resubscribed.clone();
+            move |previous, room_id| {
+                resubscribed
+                    .lock()
+                    .unwrap()
+                    .push(format!("{} -> {}", previous, room_id))
+            }
+        });
+        cluster.connect().unwrap();
+        cluster
+            .send(
+                KuzzleRequest::new("realtime", "subscribe"),
+                QueryOptions::new(),
+            )
+            .unwrap();
+
+        downs[0].store(true, Ordering::SeqCst);
+        cluster
+            .send(KuzzleRequest::new("server", "now"), QueryOptions::new())
+            .unwrap();
+        assert_eq!(*resubscribed.lock().unwrap(), vec!["room-0 -> room-1"]);
+
+        cluster
+            .send(
+                KuzzleRequest::new("realtime", "unsubscribe")
+                    .add_to_body("roomId", json!("room-1")),
+                QueryOptions::new(),
+            )
+            .unwrap();
+        assert!(cluster._nodes._subscriptions.requests().is_empty());
+
+        // The subscription is not made again on the next failover
+        downs[0].store(false, Ordering::SeqCst);
+        downs[1].store(true, Ordering::SeqCst);
+        cluster
+            .send(KuzzleRequest::new("server", "now"), QueryOptions::new())
+            .unwrap();
+        assert_eq!(
+            *received[0].lock().unwrap(),
+            vec![
+                "server:healthCheck",
+                "realtime:subscribe",
+                "server:healthCheck",
+                "server:now"
+            ]
+        );
+    }
+
+    #[test]
+    fn watch_ok_failover_without_request() {
+        let mocks = [Mock::new(), Mock::new()];
+        for (index, mock) in mocks.iter().enumerate() {
+            mock.when("server", "healthCheck")
+                .respond(json!({ "status": "green" }));
+            mock.when("realtime", "subscribe")
+                .respond(json!({ "roomId": format!("room-{}", index) }));
+        }
+        let nodes = mocks
+            .iter()
+            .enumerate()
+            .map(|(i, mock)| {
+                (
+                    format!("kuzzle-{}:7512", i),
+                    Box::new(mock.clone()) as Box<dyn Protocol>,
+                )
+            })
+            .collect();
+        let resubscribed: Received = Arc::default();
+        let mut cluster = Cluster::with_nodes(nodes, true).on_resubscribed({
+            let resubscribed = resubscribed.clone();
+            move |previous, room_id| {
+                resubscribed
+                    .lock()
+                    .unwrap()
+                    .push(format!("{} -> {}", previous, room_id))
+            }
+        });
+        cluster._watch_interval = Some(Duration::from_millis(10));
+        let notifications = cluster.notifications();
+        cluster.connect().unwrap();
+        cluster
+            .send(
+                KuzzleRequest::new("realtime", "subscribe"),
+                QueryOptions::new(),
+            )
+            .unwrap();
+
+        // The first node goes down, and nothing is sent afterwards
+        mocks[0]
+            .when("server", "healthCheck")
+            .fail(|| Error::Network("Connection refused".into()));
+        Protocol::close(&mut mocks[0].clone());
+
+        let deadline = Instant::now() + Duration::from_secs(2);
+        while mocks[1].calls_to("realtime", "subscribe").is_empty() && Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(10));
+        }
+        assert_eq!(cluster.current_node(), "kuzzle-1:7512");
+        assert_eq!(*resubscribed.lock().unwrap(), vec!["room-0 -> room-1"]);
+
+        // Only the notifications of the current node are forwarded
+        mocks[0]
+            .notify("room-0", json!({ "action": "publish" }))
+            .unwrap();
+        mocks[1]
+            .notify("room-1", json!({ "action": "publish" }))
+            .unwrap();
+        let notification = notifications.recv_timeout(Duration::from_secs(1)).unwrap();
+        assert_eq!(notification.room_id(), "room-1");
+        assert!(notifications
+            .recv_timeout(Duration::from_millis(100))
+            .is_err());
+    }
+}
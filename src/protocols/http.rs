@@ -1,30 +1,62 @@
+use crate::protocols::{tls, Protocol, State};
+use crate::types::{
+    CancellationToken, Error, KuzzleOptions, KuzzleRequest, KuzzleResponse, QueryOptions, SdkError,
+};
+
+#[cfg(test)]
+use mockito;
+use reqwest::blocking::{Client, RequestBuilder};
+use reqwest::{Method, Url};
+use serde_json::Value;
 use std::collections::HashMap;
+use std::fs::File;
+use std::net::{SocketAddr, ToSocketAddrs};
+use std::path::Path;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// HTTP routes, indexed by controller then by action.
+pub type Routes = HashMap<String, HashMap<String, Route>>;
+
+/// Kuzzle HTTP routes bundled with the SDK.
+const BUNDLED_ROUTES: &str = include_str!("http_routes.json");
+
+/// How often an in-flight request checks whether it has been cancelled.
+const CANCELLATION_POLL_INTERVAL: Duration = Duration::from_millis(10);
 
-type Routes = HashMap<String, HashMap<String, Route>>;
+/// Timeout of cancellable requests sent without any, bounding how long the thread
+/// sending them keeps running once they are cancelled.
+const CANCELLABLE_REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
 
-#[derive(Deserialize, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
 pub struct Route {
     pub url: String,
     pub verb: String,
 }
 
-use crate::protocols::State;
-use crate::types::KuzzleOptions;
+/// An HTTP request to Kuzzle, ready to be sent by either the blocking or the async client.
+pub(crate) struct HttpRequest {
+    pub(crate) method: Method,
+    pub(crate) url: Url,
+    pub(crate) query: Vec<(String, String)>,
+    pub(crate) body: Option<HashMap<String, Value>>,
+    pub(crate) headers: Vec<(&'static str, String)>,
+}
 
 pub struct Http {
     _client: Client,
     _options: KuzzleOptions,
     _routes: Routes,
     _state: State,
+    _fetch_routes: bool,
 }
 
-use std::fs::File;
-use std::io::Read;
-
 impl Http {
     /// Returns a Http struct that acts as an HTTP
     /// client to dial with Kuzzle server.
-    /// Perhaps, Kuzzle HTTP routes are loaded from a JSON file.
+    /// Kuzzle HTTP routes are bundled with the SDK,
+    /// use `set_routes` to add or override some of them.
     ///
     /// # Arguments
     /// * `options` - An `types::Options` used to configure Http dialer
@@ -40,57 +72,418 @@ impl Http {
         Http {
             _client: Client::new(),
             _options: options,
-            _routes: Http::read_routes_from_file(".http_routes.json"),
+            _routes: Http::bundled_routes(),
             _state: State::Offline,
+            _fetch_routes: false,
+        }
+    }
+
+    /// If `fetch` is true, `connect` downloads the route table exposed by the server
+    /// and adds the routes missing from the known ones, such as plugin controllers
+    /// or actions added by newer Kuzzle versions.
+    /// Routes are read from `server:publicApi`, falling back on `server:info`
+    /// with older Kuzzle versions. If both fail, known routes are used as is.
+    ///
+    /// # Example
+    /// ```
+    /// use kuzzle_sdk::types::KuzzleOptions;
+    /// use kuzzle_sdk::protocols::Http;
+    ///
+    /// let http = Http::new(KuzzleOptions::new("localhost", 7512)).set_fetch_routes(true);
+    /// ```
+    pub fn set_fetch_routes(mut self, fetch: bool) -> Self {
+        self._fetch_routes = fetch;
+        self
+    }
+
+    /// Adds the given routes to the bundled ones.
+    /// A route defined for an already known controller/action replaces the bundled one.
+    ///
+    /// # Example
+    /// ```
+    /// use kuzzle_sdk::types::KuzzleOptions;
+    /// use kuzzle_sdk::protocols::{Http, Route, Routes};
+    ///
+    /// let mut routes = Routes::new();
+    /// routes
+    ///     .entry("ferris".to_string())
+    ///     .or_default()
+    ///     .insert(
+    ///         "pinch".to_string(),
+    ///         Route { url: "/_plugin/ferris/pinch".to_string(), verb: "POST".to_string() },
+    ///     );
+    ///
+    /// let http = Http::new(KuzzleOptions::new("localhost", 7512)).set_routes(routes);
+    /// ```
+    pub fn set_routes(mut self, routes: Routes) -> Self {
+        for (controller, actions) in routes {
+            self._routes.entry(controller).or_default().extend(actions);
         }
+        self
+    }
+
+    /// Returns the routes used by this Http client.
+    pub fn routes(&self) -> &Routes {
+        &self._routes
+    }
+
+    /// Reads routes from a JSON file, following the bundled routes format:
+    /// `{ "<controller>": { "<action>": { "url": "...", "verb": "..." } } }`.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use kuzzle_sdk::types::KuzzleOptions;
+    /// use kuzzle_sdk::protocols::Http;
+    ///
+    /// let routes = Http::read_routes("/etc/kuzzle/http_routes.json").unwrap();
+    /// let http = Http::new(KuzzleOptions::new("localhost", 7512)).set_routes(routes);
+    /// ```
+    pub fn read_routes<P: AsRef<Path>>(path: P) -> Result<Routes, Error> {
+        let file = File::open(path)?;
+        let routes: Routes = serde_json::from_reader(file)?;
+        Ok(routes)
+    }
+
+    pub(crate) fn bundled_routes() -> Routes {
+        serde_json::from_str(BUNDLED_ROUTES).expect("Bundled HTTP routes must be valid JSON")
     }
 
-    fn _get_route(&self, controller: &str, action: &str) -> Result<Route, Box<Error>> {
-        match self._routes.get(controller) {
-            Some(ctrl) => match ctrl.get(action) {
-                Some(route) => Ok(route.clone()),
-                None => Err(Box::new(SdkError::new(
-                    "Http::_get_route",
+    /// Adds the routes exposed by the server which are missing from the known ones.
+    fn fetch_routes(&mut self) -> Result<(), Error> {
+        let res = self.send(
+            KuzzleRequest::new("server", "publicApi"),
+            QueryOptions::new(),
+        )?;
+        let api = match res.error() {
+            None => res.result().clone(),
+            Some(_) => {
+                let res = self.send(KuzzleRequest::new("server", "info"), QueryOptions::new())?;
+                if let Some(k_err) = res.error() {
+                    return Err(Error::from(k_err.clone()));
+                }
+                res.result()
+                    .pointer("/serverInfo/kuzzle/api/routes")
+                    .cloned()
+                    .unwrap_or(Value::Null)
+            }
+        };
+
+        Http::add_missing_routes(&mut self._routes, &api);
+        Ok(())
+    }
+
+    /// Adds the routes described by `api` which are missing from `routes`.
+    pub(crate) fn add_missing_routes(routes: &mut Routes, api: &Value) {
+        for (controller, actions) in Http::routes_from_api(api) {
+            let known = routes.entry(controller).or_default();
+            for (action, route) in actions {
+                known.entry(action).or_insert(route);
+            }
+        }
+    }
+
+    /// Converts an API description, as returned by `server:publicApi`, into Routes:
+    /// `{ "<controller>": { "<action>": { "http": [{ "url": "...", "verb": "..." }] } } }`.
+    /// The first HTTP route of each action is kept.
+    fn routes_from_api(api: &Value) -> Routes {
+        let mut routes = Routes::new();
+
+        if let Some(controllers) = api.as_object() {
+            for (controller, actions) in controllers {
+                let actions = match actions.as_object() {
+                    Some(actions) => actions,
+                    None => continue,
+                };
+
+                for (action, description) in actions {
+                    let route = description
+                        .pointer("/http/0")
+                        .and_then(|route| serde_json::from_value::<Route>(route.clone()).ok());
+
+                    if let Some(route) = route {
+                        routes
+                            .entry(controller.clone())
+                            .or_default()
+                            .insert(action.clone(), route);
+                    }
+                }
+            }
+        }
+
+        routes
+    }
+
+    /// Replaces each `:placeholder` of the route URL with the matching request argument.
+    /// Arguments used in the path are removed from the query strings.
+    fn build_path(
+        url: &str,
+        req: &KuzzleRequest,
+        query_strings: &mut HashMap<String, Value>,
+    ) -> Result<String, Error> {
+        let mut path = String::new();
+
+        for segment in url.split('/').skip(1) {
+            path.push('/');
+
+            if !segment.starts_with(':') {
+                path.push_str(segment);
+                continue;
+            }
+
+            let name = &segment[1..];
+            let value = match name {
+                "index" => req.index(),
+                "collection" => req.collection(),
+                "_id" => req.id(),
+                "strategy" => req.strategy(),
+                _ => String::new(),
+            };
+            let value = if value.is_empty() {
+                query_strings
+                    .remove(name)
+                    .and_then(|value| Http::query_value(&value))
+                    .unwrap_or_default()
+            } else {
+                value
+            };
+
+            if value.is_empty() {
+                return Err(Error::InvalidArgument(SdkError::new(
+                    "Http::send",
                     &format!(
-                        "Unable to find route for (controller/action) {}/{}",
-                        controller, action
+                        "Missing argument \"{}\" for (controller/action) {}/{}",
+                        name,
+                        req.controller(),
+                        req.action()
                     ),
-                ))),
+                )));
+            }
+
+            path.push_str(&encode_path_segment(&value));
+        }
+
+        if path.is_empty() {
+            path.push('/');
+        }
+
+        Ok(path)
+    }
+
+    /// Converts arguments to query string pairs, sorted by name.
+    fn build_query(query_strings: &HashMap<String, Value>) -> Vec<(String, String)> {
+        let mut query: Vec<(String, String)> = query_strings
+            .iter()
+            .filter_map(|(name, value)| Http::query_value(value).map(|value| (name.clone(), value)))
+            .collect();
+        query.sort();
+        query
+    }
+
+    /// Formats a query string value the way Kuzzle parses it: arrays of scalars
+    /// are comma-separated, objects are JSON-encoded, `false` and `null` are omitted.
+    fn query_value(value: &Value) -> Option<String> {
+        match value {
+            Value::Null | Value::Bool(false) => None,
+            Value::Bool(true) => Some(String::from("true")),
+            Value::Number(number) => Some(number.to_string()),
+            Value::String(string) => Some(string.clone()),
+            Value::Array(items)
+                if items
+                    .iter()
+                    .all(|item| !item.is_object() && !item.is_array()) =>
+            {
+                Some(
+                    items
+                        .iter()
+                        .filter_map(Http::query_value)
+                        .collect::<Vec<String>>()
+                        .join(","),
+                )
+            }
+            Value::Array(_) | Value::Object(_) => Some(value.to_string()),
+        }
+    }
+
+    /// Returns the base URL of the Kuzzle server: `https` is used if `ssl_connection`
+    /// is enabled, and the host is replaced by the SNI hostname if one is set.
+    #[cfg_attr(test, allow(unused_variables))]
+    pub(crate) fn base_url(options: &KuzzleOptions) -> String {
+        #[cfg(not(test))]
+        let url = format!(
+            "{}://{}:{}",
+            if *options.ssl_connection() {
+                "https"
+            } else {
+                "http"
             },
-            None => Err(Box::new(SdkError::new(
-                "Http::_get_route",
-                &format!(
-                    "Unable to find route for (controller/action) {}/{}",
-                    controller, action
-                ),
-            ))),
+            tls::server_name(options),
+            options.port()
+        );
+        #[cfg(test)]
+        let url = mockito::server_url();
+
+        url
+    }
+
+    /// Builds the HTTP client, configured with the TLS options if `ssl_connection` is enabled.
+    fn build_client(&self) -> Result<Client, Error> {
+        let mut builder = Client::builder();
+
+        if *self._options.ssl_connection() {
+            builder = builder.use_preconfigured_tls(tls::connector(self._options.tls())?);
+
+            if let Some(addr) = Http::sni_address(&self._options)? {
+                builder = builder.resolve(tls::server_name(&self._options), addr);
+            }
         }
+
+        Ok(builder.build()?)
     }
 
-    fn read_routes_from_file(file: &str) -> Routes {
-        let mut file = File::open(file).unwrap();
+    /// Returns the Kuzzle host address to dial when the SNI hostname differs from it.
+    pub(crate) fn sni_address(options: &KuzzleOptions) -> Result<Option<SocketAddr>, Error> {
+        if tls::server_name(options) == options.host() {
+            return Ok(None);
+        }
 
-        let mut contents = String::new();
-        file.read_to_string(&mut contents).unwrap();
+        // Dial the Kuzzle host while presenting the SNI hostname
+        let addr = format!("{}:{}", options.host(), options.port())
+            .to_socket_addrs()?
+            .next()
+            .ok_or_else(|| {
+                Error::InvalidArgument(SdkError::new(
+                    "Http::connect",
+                    &format!("Unable to resolve host {}", options.host()),
+                ))
+            })?;
 
-        // Deserialize and print Rust data structure.
-        let data: Routes = match serde_json::from_str(&contents) {
-            Ok(json) => json,
-            Err(err) => panic!("{}", err),
-        };
+        Ok(Some(addr))
+    }
+
+    /// Converts a Kuzzle request to the HTTP request matching its route.
+    pub(crate) fn prepare(
+        routes: &Routes,
+        base_url: &str,
+        req: &KuzzleRequest,
+    ) -> Result<HttpRequest, Error> {
+        let kuzzle_route = Http::find_route(routes, req.controller(), req.action())?;
+        let mut query_strings = req.query_strings().clone();
+        let route = Http::build_path(&kuzzle_route.url, req, &mut query_strings)?;
+
+        let url: Url = Url::parse(&format!("{}{}", base_url, route)).map_err(|err| {
+            Error::InvalidArgument(SdkError::new(
+                "Http::send",
+                &format!("Invalid URL: {}", err),
+            ))
+        })?;
+        let method: Method = Method::from_bytes(kuzzle_route.verb.as_bytes()).map_err(|err| {
+            Error::InvalidArgument(SdkError::new(
+                "Http::send",
+                &format!("Invalid verb: {}", err),
+            ))
+        })?;
+
+        // GET requests can't have a body: Kuzzle reads their arguments from the query string
+        let body = req.body().clone();
+        let has_body = method != Method::GET;
+        if !has_body {
+            query_strings.extend(body.clone());
+        }
+
+        let mut headers = Vec::new();
+
+        if !req.jwt().is_empty() {
+            headers.push(("authorization", format!("Bearer {}", req.jwt())));
+        }
+
+        if !req.request_id().is_empty() {
+            headers.push(("x-kuzzle-request-id", req.request_id()));
+        }
 
-        data
+        if !req.volatile().is_empty() {
+            headers.push(("x-kuzzle-volatile", serde_json::to_string(req.volatile())?));
+        }
+
+        Ok(HttpRequest {
+            method,
+            url,
+            query: Http::build_query(&query_strings),
+            body: if has_body && !body.is_empty() {
+                Some(body)
+            } else {
+                None
+            },
+            headers,
+        })
+    }
+
+    /// Sends the request from another thread, so that it can be given up
+    /// as soon as `token` is cancelled.
+    ///
+    /// Blocking requests cannot be aborted: cancelling only stops waiting for the response,
+    /// and the thread sending the request runs until it completes or times out.
+    /// Requests without a timeout are given `CANCELLABLE_REQUEST_TIMEOUT`, so that
+    /// cancelled requests never leave a thread behind for longer.
+    fn send_cancellable(
+        request: RequestBuilder,
+        timeout: Option<Duration>,
+        token: &CancellationToken,
+    ) -> Result<String, Error> {
+        if token.is_cancelled() {
+            return Err(Error::Cancelled);
+        }
+
+        let request = request.timeout(timeout.unwrap_or(CANCELLABLE_REQUEST_TIMEOUT));
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            let _ = tx.send(request.send().and_then(|res| res.text()));
+        });
+
+        loop {
+            if token.is_cancelled() {
+                return Err(Error::Cancelled);
+            }
+
+            match rx.recv_timeout(CANCELLATION_POLL_INTERVAL) {
+                Ok(res) => return Ok(res?),
+                Err(RecvTimeoutError::Timeout) => continue,
+                Err(RecvTimeoutError::Disconnected) => {
+                    return Err(Error::Network("HTTP request aborted".into()))
+                }
+            }
+        }
+    }
+
+    fn _get_route(&self, controller: &str, action: &str) -> Result<Route, Error> {
+        Http::find_route(&self._routes, controller, action)
+    }
+
+    fn find_route(routes: &Routes, controller: &str, action: &str) -> Result<Route, Error> {
+        match routes.get(controller).and_then(|ctrl| ctrl.get(action)) {
+            Some(route) => Ok(route.clone()),
+            None => Err(Error::UnknownRoute {
+                controller: controller.to_string(),
+                action: action.to_string(),
+            }),
+        }
     }
 }
 
-use crate::protocols::Protocol;
-use crate::types::{KuzzleRequest, KuzzleResponse, QueryOptions, SdkError};
+/// Percent-encodes every byte of `segment` but RFC 3986 unreserved characters.
+fn encode_path_segment(segment: &str) -> String {
+    let mut encoded = String::with_capacity(segment.len());
 
-use reqwest::{Client, Method, Url};
-use std::error::Error;
+    for byte in segment.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
 
-#[cfg(test)]
-use mockito;
+    encoded
+}
 
 impl Protocol for Http {
     fn is_ready(&self) -> bool {
@@ -104,74 +497,64 @@ impl Protocol for Http {
         self._state = State::Offline;
     }
 
-    fn connect(&mut self) -> Result<(), Box<Error>> {
+    fn connect(&mut self) -> Result<(), Error> {
         if self._state == State::Ready {
             return Ok(());
         }
 
-        #[cfg(not(test))]
-        let url = Url::parse(&format!(
-            "http://{}:{}",
-            self._options.host(),
-            self._options.port()
-        ))?;
+        let url = Url::parse(&Http::base_url(&self._options)).map_err(|err| {
+            Error::InvalidArgument(SdkError::new(
+                "Http::connect",
+                &format!("Invalid host: {}", err),
+            ))
+        })?;
 
-        #[cfg(test)]
-        let url = &mockito::server_url();
+        self._client = self.build_client()?;
+        self._client.get(url).send()?;
+        self._state = State::Ready;
 
-        match reqwest::get(url) {
-            Ok(_) => {
-                self._state = State::Ready;
-                Ok(())
-            }
-            Err(err) => Err(Box::new(err)),
+        if self._fetch_routes {
+            // Known routes are enough to use the SDK controllers:
+            // failing to fetch server ones must not prevent connecting.
+            let _ = self.fetch_routes();
         }
+
+        Ok(())
     }
 
-    fn send(
-        &self,
-        req: KuzzleRequest,
-        _query_options: QueryOptions,
-    ) -> Result<KuzzleResponse, Box<Error>> {
+    fn send(&self, req: KuzzleRequest, options: QueryOptions) -> Result<KuzzleResponse, Error> {
         if self._state == State::Offline {
-            return Err(Box::new(SdkError::new(
-                "Http::send",
-                "Unable to execute request: not connected to a Kuzzle server.",
-            )));
+            return Err(Error::NotConnected);
         }
 
-        let kuzzle_route = self._get_route(req.controller(), req.action())?;
-        let route = kuzzle_route
-            .url
-            .replace(":index", &req.index())
-            .replace(":collection", &req.collection())
-            .replace(":strategy", &req.strategy());
-
-        #[cfg(not(test))]
-        let host = &format!("http://{}:{}", self._options.host(), self._options.port(),);
-        #[cfg(test)]
-        let host = &mockito::server_url();
-
-        let url: Url = Url::parse(&format!("{}{}", host, route))?;
-        let method: Method = Method::from_bytes(kuzzle_route.verb.as_bytes())?;
+        let expires_at = options.expires_at(*self._options.request_timeout());
+        options.check(expires_at)?;
 
-        let mut request = self._client.request(method, url);
+        let prepared = Http::prepare(&self._routes, &Http::base_url(&self._options), &req)?;
+        let mut request = self._client.request(prepared.method, prepared.url);
 
-        if !req.body().is_empty() {
-            request = request.json(&req.body());
+        if let Some(body) = &prepared.body {
+            request = request.json(body);
         }
 
-        if !req.query_strings().is_empty() {
-            request = request.query(&req.query_strings());
+        if !prepared.query.is_empty() {
+            request = request.query(&prepared.query);
         }
 
-        if !req.jwt().is_empty() {
-            request = request.bearer_auth(&req.jwt());
+        for (name, value) in prepared.headers {
+            request = request.header(name, value);
         }
 
-        let mut raw_response = request.send()?;
-        dbg!(&raw_response);
-        let response : KuzzleResponse = raw_response.json()?;
+        let timeout =
+            expires_at.map(|expires_at| expires_at.saturating_duration_since(Instant::now()));
+        let raw_response = match options.cancellation_token() {
+            Some(token) => Http::send_cancellable(request, timeout, token)?,
+            None => match timeout {
+                Some(timeout) => request.timeout(timeout).send()?.text()?,
+                None => request.send()?.text()?,
+            },
+        };
+        let response: KuzzleResponse = serde_json::from_str(&raw_response)?;
 
         Ok(response)
     }
@@ -184,3 +567,510 @@ impl Protocol for Http {
         unimplemented!();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kuzzle::Kuzzle;
+    use serde_json::json;
+    use std::net::TcpListener;
+
+    fn custom_routes() -> Routes {
+        serde_json::from_str(
+            r#"{
+                "ferris": {
+                    "pinch": { "url": "/_plugin/ferris/pinch/:_id", "verb": "POST" }
+                },
+                "server": {
+                    "now": { "url": "/_now_v2", "verb": "GET" }
+                }
+            }"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn bundled_routes_ok() {
+        let http = Http::new(KuzzleOptions::new("localhost", 7512));
+        let route = http._get_route("server", "now").unwrap();
+
+        assert_eq!(route.url, "/_now");
+        assert_eq!(route.verb, "GET");
+    }
+
+    #[test]
+    fn set_routes_ok() {
+        let http = Http::new(KuzzleOptions::new("localhost", 7512)).set_routes(custom_routes());
+
+        assert_eq!(http._get_route("server", "now").unwrap().url, "/_now_v2");
+        assert_eq!(
+            http._get_route("server", "info").unwrap().url,
+            "/_serverInfo"
+        );
+        assert_eq!(
+            http._get_route("ferris", "pinch").unwrap().url,
+            "/_plugin/ferris/pinch/:_id"
+        );
+    }
+
+    #[test]
+    fn set_routes_send_ok() {
+        let _m = mockito::mock("POST", "/_plugin/ferris/pinch/corro")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "ferris",
+                    "action": "pinch",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": { "pinched": true }
+                }"#,
+            )
+            .create();
+
+        let k = Kuzzle::new(
+            Http::new(KuzzleOptions::new("localhost", 7512)).set_routes(custom_routes()),
+        );
+        k.connect().expect("Unable to connect to Kuzzle server");
+        let res = k.query(
+            KuzzleRequest::new("ferris", "pinch").set_id("corro"),
+            QueryOptions::new(),
+        );
+
+        assert!(res.is_ok());
+        assert_eq!(res.unwrap().result()["pinched"], true);
+    }
+
+    #[test]
+    fn connect_fetch_routes_ok() {
+        let _m = mockito::mock("GET", "/_publicApi")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "server",
+                    "action": "publicApi",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": {
+                        "server": {
+                            "now": {
+                                "controller": "server",
+                                "action": "now",
+                                "http": [{ "url": "/_now_v2", "verb": "GET" }]
+                            }
+                        },
+                        "ferris": {
+                            "pinch": {
+                                "controller": "ferris",
+                                "action": "pinch",
+                                "http": [
+                                    { "url": "/_plugin/ferris/pinch/:_id", "verb": "POST" },
+                                    { "url": "/_plugin/ferris/pinch", "verb": "GET" }
+                                ]
+                            },
+                            "websocketOnly": {
+                                "controller": "ferris",
+                                "action": "websocketOnly"
+                            }
+                        }
+                    }
+                }"#,
+            )
+            .create();
+
+        let mut http = Http::new(KuzzleOptions::new("localhost", 7512)).set_fetch_routes(true);
+        http.connect().expect("Unable to connect to Kuzzle server");
+
+        assert_eq!(http._get_route("server", "now").unwrap().url, "/_now");
+        assert_eq!(
+            http._get_route("ferris", "pinch").unwrap(),
+            Route {
+                url: "/_plugin/ferris/pinch/:_id".to_string(),
+                verb: "POST".to_string()
+            }
+        );
+        assert!(http._get_route("ferris", "websocketOnly").is_err());
+    }
+
+    #[test]
+    fn connect_fetch_routes_ok_server_info() {
+        let _public_api = mockito::mock("GET", "/_publicApi")
+            .with_status(404)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 404,
+                    "error": {
+                        "message": "API URL not found: /_publicApi",
+                        "status": 404
+                    },
+                    "controller": null,
+                    "action": null,
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": null
+                }"#,
+            )
+            .create();
+        let _info = mockito::mock("GET", "/_serverInfo")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "server",
+                    "action": "info",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": {
+                        "serverInfo": {
+                            "kuzzle": {
+                                "api": {
+                                    "routes": {
+                                        "crab": {
+                                            "walk": {
+                                                "name": "walk",
+                                                "http": [{ "url": "/_plugin/crab/walk", "verb": "GET" }]
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }"#,
+            )
+            .create();
+
+        let mut http = Http::new(KuzzleOptions::new("localhost", 7512)).set_fetch_routes(true);
+        http.connect().expect("Unable to connect to Kuzzle server");
+
+        assert_eq!(
+            http._get_route("crab", "walk").unwrap().url,
+            "/_plugin/crab/walk"
+        );
+    }
+
+    #[test]
+    fn connect_fetch_routes_ok_unavailable() {
+        let _m = mockito::mock("GET", "/_publicApi")
+            .with_status(500)
+            .with_body("Internal error")
+            .create();
+
+        let mut http = Http::new(KuzzleOptions::new("localhost", 7512)).set_fetch_routes(true);
+
+        assert!(http.connect().is_ok());
+        assert!(http._get_route("server", "now").is_ok());
+    }
+
+    #[test]
+    fn build_path_ok() {
+        let req = KuzzleRequest::new("ms", "hget")
+            .set_id("ferris crab/ü")
+            .add_to_query_strings("field", json!("age"))
+            .add_to_query_strings("refresh", json!("wait_for"));
+        let mut query_strings = req.query_strings().clone();
+        let path = Http::build_path("/ms/_hget/:_id/:field", &req, &mut query_strings).unwrap();
+
+        assert_eq!(path, "/ms/_hget/ferris%20crab%2F%C3%BC/age");
+        assert_eq!(query_strings.len(), 1);
+        assert!(query_strings.contains_key("refresh"));
+        assert_eq!(
+            Http::build_path("/", &req, &mut HashMap::new()).unwrap(),
+            "/"
+        );
+    }
+
+    #[test]
+    fn build_path_fail_missing_argument() {
+        let req = KuzzleRequest::new("document", "get").set_index("ferris_index");
+        let res = Http::build_path("/:index/:collection/:_id", &req, &mut HashMap::new());
+
+        assert!(matches!(res, Err(Error::InvalidArgument(_))));
+    }
+
+    #[test]
+    fn build_query_ok() {
+        let mut query_strings = HashMap::new();
+        query_strings.insert("keys".to_string(), json!(["ferris", "corro"]));
+        query_strings.insert("from".to_string(), json!(10));
+        query_strings.insert("includeTrash".to_string(), json!(true));
+        query_strings.insert("silent".to_string(), json!(false));
+        query_strings.insert("volatile".to_string(), json!({ "app": "crab" }));
+        query_strings.insert("nothing".to_string(), Value::Null);
+
+        assert_eq!(
+            Http::build_query(&query_strings),
+            vec![
+                ("from".to_string(), "10".to_string()),
+                ("includeTrash".to_string(), "true".to_string()),
+                ("keys".to_string(), "ferris,corro".to_string()),
+                ("volatile".to_string(), r#"{"app":"crab"}"#.to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn send_get_body_as_query_strings_ok() {
+        let _m = mockito::mock("GET", "/ms/_mget?keys=ferris%2Ccorro")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "ms",
+                    "action": "mget",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": ["crab", null]
+                }"#,
+            )
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        k.connect().expect("Unable to connect to Kuzzle server");
+        let res = k.query(
+            KuzzleRequest::new("ms", "mget").add_to_body("keys", json!(["ferris", "corro"])),
+            QueryOptions::new(),
+        );
+
+        assert!(res.is_ok());
+        assert_eq!(res.unwrap().result()[0], "crab");
+    }
+
+    #[test]
+    fn send_delete_body_ok() {
+        let _m = mockito::mock("DELETE", "/ms")
+            .match_body(r#"{"keys":["ferris"]}"#)
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "ms",
+                    "action": "del",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": 1
+                }"#,
+            )
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        k.connect().expect("Unable to connect to Kuzzle server");
+        let res = k.query(
+            KuzzleRequest::new("ms", "del").add_to_body("keys", json!(["ferris"])),
+            QueryOptions::new(),
+        );
+
+        assert!(res.is_ok());
+    }
+
+    #[test]
+    fn send_query_options_ok() {
+        let _m = mockito::mock(
+            "POST",
+            "/ferris_index/ferris_collection/_create?refresh=wait_for&silent=true",
+        )
+        .match_header("x-kuzzle-request-id", "ferris-request")
+        .match_header("x-kuzzle-volatile", r#"{"user":"ferris"}"#)
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            r#"{
+                    "requestId": "ferris-request",
+                    "status": 200,
+                    "error": null,
+                    "controller": "document",
+                    "action": "create",
+                    "collection": "ferris_collection",
+                    "index": "ferris_index",
+                    "volatile": { "user": "ferris" },
+                    "result": { "_id": "ferris", "_version": 1 }
+                }"#,
+        )
+        .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        k.connect().expect("Unable to connect to Kuzzle server");
+        let res = k.query(
+            KuzzleRequest::new("document", "create")
+                .set_index("ferris_index")
+                .set_collection("ferris_collection"),
+            QueryOptions::new()
+                .set_refresh("wait_for")
+                .set_silent(true)
+                .add_to_volatile("user", json!("ferris"))
+                .set_request_id("ferris-request"),
+        );
+
+        assert_eq!(res.unwrap().request_id(), "ferris-request");
+    }
+
+    #[test]
+    fn send_cancellable_ok() {
+        let _m = mockito::mock("GET", "/_now")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "server",
+                    "action": "now",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": { "now": 1447151167622 }
+                }"#,
+            )
+            .create();
+
+        let k = Kuzzle::new(Http::new(
+            KuzzleOptions::new("localhost", 7512).set_request_timeout(5000),
+        ));
+        k.connect().expect("Unable to connect to Kuzzle server");
+        let res = k.query(
+            KuzzleRequest::new("server", "now"),
+            QueryOptions::new().set_cancellation_token(CancellationToken::new()),
+        );
+
+        assert_eq!(res.unwrap().result()["now"], 1447151167622u64);
+    }
+
+    #[test]
+    fn send_ok_shared_across_threads() {
+        let _m = mockito::mock("GET", "/_now")
+            .match_header("authorization", "Bearer ferris-token")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "server",
+                    "action": "now",
+                    "collection": null,
+                    "index": null,
+                    "volatile": null,
+                    "result": { "now": 1447151167622 }
+                }"#,
+            )
+            .expect(4)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        k.connect().expect("Unable to connect to Kuzzle server");
+        k.clone().set_jwt("ferris-token".to_string());
+
+        let workers: Vec<_> = (0..4)
+            .map(|_| {
+                let k = k.clone();
+                thread::spawn(move || k.server().now(QueryOptions::new()))
+            })
+            .collect();
+
+        for worker in workers {
+            assert_eq!(worker.join().unwrap().unwrap(), 1447151167622);
+        }
+        _m.assert();
+    }
+
+    #[test]
+    fn send_fail_cancelled() {
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        k.connect().expect("Unable to connect to Kuzzle server");
+
+        let token = CancellationToken::new();
+        token.cancel();
+        let res = k.query(
+            KuzzleRequest::new("server", "now"),
+            QueryOptions::new().set_cancellation_token(token),
+        );
+
+        assert!(matches!(res, Err(Error::Cancelled)));
+    }
+
+    #[test]
+    fn send_cancellable_fail_cancelled_in_flight() {
+        // Accepts connections but never answers them.
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/_now", listener.local_addr().unwrap());
+        thread::spawn(move || {
+            let _pending: Vec<_> = listener.incoming().collect();
+        });
+
+        let token = CancellationToken::new();
+        let canceller = {
+            let token = token.clone();
+            thread::spawn(move || {
+                thread::sleep(Duration::from_millis(50));
+                token.cancel();
+            })
+        };
+        let started = Instant::now();
+        let res = Http::send_cancellable(
+            Client::new().get(&url),
+            Some(Duration::from_secs(5)),
+            &token,
+        );
+
+        assert!(matches!(res, Err(Error::Cancelled)));
+        assert!(started.elapsed() < Duration::from_secs(5));
+        canceller.join().unwrap();
+    }
+
+    #[test]
+    fn send_fail_timeout() {
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        k.connect().expect("Unable to connect to Kuzzle server");
+        let res = k.query(
+            KuzzleRequest::new("server", "now"),
+            QueryOptions::new().set_deadline(Instant::now()),
+        );
+
+        assert!(matches!(res, Err(Error::Timeout)));
+    }
+
+    #[test]
+    fn get_route_fail_unknown() {
+        let http = Http::new(KuzzleOptions::new("localhost", 7512));
+
+        assert!(matches!(
+            http._get_route("ferris", "pinch"),
+            Err(Error::UnknownRoute { .. })
+        ));
+    }
+
+    #[test]
+    fn read_routes_fail_missing_file() {
+        assert!(matches!(
+            Http::read_routes("/nonexistent/http_routes.json"),
+            Err(Error::Io(_))
+        ));
+    }
+}
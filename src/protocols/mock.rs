@@ -0,0 +1,523 @@
+use crate::protocols::Protocol;
+use crate::types::{Error, KuzzleError, KuzzleRequest, KuzzleResponse, QueryOptions, SdkError};
+
+use serde_json::{json, Map, Value};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+type Matcher = Box<dyn Fn(&KuzzleRequest) -> bool + Send + Sync>;
+type Reply = Arc<dyn Fn(&KuzzleRequest) -> Result<KuzzleResponse, Error> + Send + Sync>;
+
+/// An in-memory `Protocol` answering requests with scripted responses,
+/// to unit test code using the SDK without a Kuzzle server.
+///
+/// Clones share the same responses and calls: keep a clone of the mock
+/// given to `Kuzzle` to script it and to check the requests it received.
+///
+/// # Example
+///
+/// ```
+/// use kuzzle_sdk::kuzzle::Kuzzle;
+/// use kuzzle_sdk::protocols::Mock;
+/// use kuzzle_sdk::types::QueryOptions;
+/// use serde_json::json;
+///
+/// let mock = Mock::new();
+/// mock.when("server", "now").respond(json!({ "now": 1447151167622u64 }));
+///
+/// let kuzzle = Kuzzle::new(mock.clone());
+/// kuzzle.connect().unwrap();
+///
+/// assert_eq!(kuzzle.server().now(QueryOptions::new()).unwrap(), 1447151167622);
+/// assert_eq!(mock.calls_to("server", "now").len(), 1);
+/// ```
+#[derive(Clone, Default)]
+pub struct Mock {
+    _state: Arc<MockState>,
+}
+
+#[derive(Default)]
+struct MockState {
+    ready: AtomicBool,
+    rules: Mutex<Vec<Rule>>,
+    calls: Mutex<Vec<KuzzleRequest>>,
+    listeners: Mutex<Vec<Sender<KuzzleResponse>>>,
+}
+
+struct Rule {
+    matchers: Vec<Matcher>,
+    reply: Reply,
+    once: bool,
+}
+
+/// A response being scripted for the requests matching some criteria.
+/// It is only used once `respond`, `respond_error`, `reply` or `fail` is called.
+pub struct MockRule {
+    _mock: Mock,
+    _matchers: Vec<Matcher>,
+    _once: bool,
+}
+
+impl Mock {
+    pub fn new() -> Mock {
+        Mock::default()
+    }
+
+    /// Starts scripting the response to the requests sent to `controller:action`.
+    /// If several responses match a request, the last scripted one is used.
+    pub fn when(&self, controller: &str, action: &str) -> MockRule {
+        let (controller, action) = (controller.to_string(), action.to_string());
+
+        MockRule {
+            _mock: self.clone(),
+            _matchers: vec![Box::new(move |req: &KuzzleRequest| {
+                *req.controller() == controller && *req.action() == action
+            })],
+            _once: false,
+        }
+    }
+
+    /// Returns all the requests received, in order.
+    pub fn calls(&self) -> Vec<KuzzleRequest> {
+        lock(&self._state.calls).clone()
+    }
+
+    /// Returns the requests received for `controller:action`, in order.
+    pub fn calls_to(&self, controller: &str, action: &str) -> Vec<KuzzleRequest> {
+        self.calls()
+            .into_iter()
+            .filter(|req| req.controller() == controller && req.action() == action)
+            .collect()
+    }
+
+    /// Forgets the scripted responses and the received requests.
+    pub fn reset(&self) {
+        lock(&self._state.rules).clear();
+        lock(&self._state.calls).clear();
+    }
+
+    /// Returns a receiver of the realtime notifications emitted with `notify`.
+    pub fn notifications(&self) -> Receiver<KuzzleResponse> {
+        let (sender, receiver) = mpsc::channel();
+        lock(&self._state.listeners).push(sender);
+        receiver
+    }
+
+    /// Emits a realtime notification for the subscription `room_id`,
+    /// received by every receiver returned by `notifications`.
+    pub fn notify(&self, room_id: &str, notification: Value) -> Result<(), Error> {
+        let mut notification = match notification {
+            Value::Object(notification) => notification,
+            _ => {
+                return Err(Error::InvalidArgument(SdkError::new(
+                    "Mock::notify",
+                    "notification must be a JSON object.",
+                )))
+            }
+        };
+        notification.insert("room".to_string(), Value::from(room_id));
+        notification.entry("requestId").or_insert(json!(""));
+        notification.entry("status").or_insert(json!(200));
+        notification.entry("result").or_insert(Value::Null);
+        let notification: KuzzleResponse = serde_json::from_value(Value::Object(notification))?;
+
+        // Receivers which have been dropped are forgotten
+        lock(&self._state.listeners).retain(|listener| listener.send(notification.clone()).is_ok());
+        Ok(())
+    }
+
+    fn add_rule(&self, rule: Rule) {
+        lock(&self._state.rules).push(rule);
+    }
+
+    /// Records `req` and returns the response scripted for it.
+    fn answer(&self, req: KuzzleRequest, options: QueryOptions) -> Result<KuzzleResponse, Error> {
+        if !self.is_ready() {
+            return Err(Error::NotConnected);
+        }
+        options.check(None)?;
+
+        lock(&self._state.calls).push(req.clone());
+
+        // Replies may call back into the mock: they are run once the rules are unlocked
+        let reply = {
+            let mut rules = lock(&self._state.rules);
+            let index = rules
+                .iter()
+                .rposition(|rule| rule.matchers.iter().all(|matcher| matcher(&req)));
+            match index {
+                Some(index) if rules[index].once => Some(rules.remove(index).reply),
+                Some(index) => Some(rules[index].reply.clone()),
+                None => None,
+            }
+        };
+
+        match reply {
+            Some(reply) => reply(&req),
+            None => Err(Error::InvalidArgument(SdkError::new(
+                "Mock::send",
+                &format!(
+                    "No response scripted for (controller/action) {}/{}",
+                    req.controller(),
+                    req.action()
+                ),
+            ))),
+        }
+    }
+
+    fn is_ready(&self) -> bool {
+        self._state.ready.load(Ordering::SeqCst)
+    }
+
+    fn set_ready(&self, ready: bool) {
+        self._state.ready.store(ready, Ordering::SeqCst);
+    }
+}
+
+impl MockRule {
+    /// Only matches requests on the given index.
+    pub fn with_index(self, index: &str) -> Self {
+        let index = index.to_string();
+        self.matching(move |req| req.index() == index)
+    }
+
+    /// Only matches requests on the given collection.
+    pub fn with_collection(self, collection: &str) -> Self {
+        let collection = collection.to_string();
+        self.matching(move |req| req.collection() == collection)
+    }
+
+    /// Only matches requests on the given document, user, role...
+    pub fn with_id(self, id: &str) -> Self {
+        let id = id.to_string();
+        self.matching(move |req| req.id() == id)
+    }
+
+    /// Only matches requests having the argument `name` set to `value`.
+    pub fn with_argument(self, name: &str, value: Value) -> Self {
+        let name = name.to_string();
+        self.matching(move |req| req.query_strings().get(&name) == Some(&value))
+    }
+
+    /// Only matches requests whose body is exactly `body`.
+    pub fn with_body(self, body: Value) -> Self {
+        self.matching(move |req| {
+            let request_body: Map<String, Value> = req.body().clone().into_iter().collect();
+            Value::Object(request_body) == body
+        })
+    }
+
+    /// Only matches requests for which `matcher` returns true.
+    pub fn matching<F>(mut self, matcher: F) -> Self
+    where
+        F: Fn(&KuzzleRequest) -> bool + Send + Sync + 'static,
+    {
+        self._matchers.push(Box::new(matcher));
+        self
+    }
+
+    /// Only uses this response for the next matching request:
+    /// later ones get the responses scripted before.
+    pub fn once(mut self) -> Self {
+        self._once = true;
+        self
+    }
+
+    /// Answers matching requests with `result`.
+    pub fn respond(self, result: Value) {
+        self.reply(move |req| response(req, 200, None, result.clone()))
+    }
+
+    /// Answers matching requests with an error sent back by Kuzzle.
+    pub fn respond_error(self, error: KuzzleError) {
+        let status = error.status().unwrap_or(500);
+        self.reply(move |req| response(req, status, Some(error.clone()), Value::Null))
+    }
+
+    /// Makes the sending of matching requests fail with the error returned by `error`,
+    /// such as `Error::Timeout` or `Error::NotConnected`.
+    pub fn fail<F>(self, error: F)
+    where
+        F: Fn() -> Error + Send + Sync + 'static,
+    {
+        self.reply(move |_| Err(error()))
+    }
+
+    /// Answers matching requests with the result of `reply`.
+    pub fn reply<F>(self, reply: F)
+    where
+        F: Fn(&KuzzleRequest) -> Result<KuzzleResponse, Error> + Send + Sync + 'static,
+    {
+        self._mock.add_rule(Rule {
+            matchers: self._matchers,
+            reply: Arc::new(reply),
+            once: self._once,
+        });
+    }
+}
+
+/// Returns the response Kuzzle would send to `req`.
+fn response(
+    req: &KuzzleRequest,
+    status: u16,
+    error: Option<KuzzleError>,
+    result: Value,
+) -> Result<KuzzleResponse, Error> {
+    Ok(serde_json::from_value(json!({
+        "requestId": req.request_id(),
+        "status": status,
+        "error": error,
+        "controller": req.controller(),
+        "action": req.action(),
+        "index": Some(req.index()).filter(|index| !index.is_empty()),
+        "collection": Some(req.collection()).filter(|collection| !collection.is_empty()),
+        "volatile": req.volatile(),
+        "result": result
+    }))?)
+}
+
+impl Protocol for Mock {
+    fn once(&self) {}
+
+    fn listener_count(&self) {}
+
+    fn connect(&mut self) -> Result<(), Error> {
+        self.set_ready(true);
+        Ok(())
+    }
+
+    fn send(&self, req: KuzzleRequest, options: QueryOptions) -> Result<KuzzleResponse, Error> {
+        self.answer(req, options)
+    }
+
+    fn close(&mut self) {
+        self.set_ready(false);
+    }
+
+    fn is_ready(&self) -> bool {
+        Mock::is_ready(self)
+    }
+
+    fn notification_receiver(&self) -> Option<Receiver<KuzzleResponse>> {
+        Some(self.notifications())
+    }
+}
+
+#[cfg(feature = "async")]
+#[async_trait::async_trait]
+impl crate::nonblocking::Protocol for Mock {
+    async fn connect(&mut self) -> Result<(), Error> {
+        self.set_ready(true);
+        Ok(())
+    }
+
+    async fn send(
+        &self,
+        req: KuzzleRequest,
+        options: QueryOptions,
+    ) -> Result<KuzzleResponse, Error> {
+        self.answer(req, options)
+    }
+
+    async fn close(&mut self) {
+        self.set_ready(false);
+    }
+
+    fn is_ready(&self) -> bool {
+        Mock::is_ready(self)
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kuzzle::Kuzzle;
+    use std::time::Duration;
+
+    fn connected() -> Mock {
+        let mut mock = Mock::new();
+        mock.connect().unwrap();
+        mock
+    }
+
+    #[test]
+    fn send_ok() {
+        let mock = connected();
+        mock.when("document", "get")
+            .with_index("nyc-open-data")
+            .with_collection("yellow-taxi")
+            .with_id("some-id")
+            .respond(json!({ "_id": "some-id", "_source": {} }));
+
+        let req = KuzzleRequest::new("document", "get")
+            .set_index("nyc-open-data")
+            .set_collection("yellow-taxi")
+            .set_id("some-id")
+            .set_request_id("a-request-id");
+        let res = mock.send(req, QueryOptions::new()).unwrap();
+
+        assert_eq!(*res.status(), 200);
+        assert_eq!(res.request_id(), "a-request-id");
+        assert_eq!(res.index(), "nyc-open-data");
+        assert_eq!(res.result()["_id"], "some-id");
+    }
+
+    #[test]
+    fn send_ok_last_match() {
+        let mock = connected();
+        mock.when("server", "now").respond(json!({ "now": 1 }));
+        mock.when("server", "now")
+            .once()
+            .respond(json!({ "now": 2 }));
+        mock.when("server", "now")
+            .with_argument("refresh", json!("wait_for"))
+            .respond(json!({ "now": 3 }));
+
+        let now = || {
+            mock.send(KuzzleRequest::new("server", "now"), QueryOptions::new())
+                .unwrap()
+                .result()["now"]
+                .clone()
+        };
+
+        assert_eq!(now(), 2);
+        assert_eq!(now(), 1);
+        assert_eq!(now(), 1);
+    }
+
+    #[test]
+    fn send_ok_body() {
+        let mock = connected();
+        mock.when("auth", "login")
+            .with_body(json!({ "username": "ferris" }))
+            .respond(json!({ "jwt": "aSuperJwtToken" }));
+
+        let matching = KuzzleRequest::new("auth", "login").add_to_body("username", json!("ferris"));
+        let other = KuzzleRequest::new("auth", "login").add_to_body("username", json!("bob"));
+
+        assert!(mock.send(matching, QueryOptions::new()).is_ok());
+        assert!(matches!(
+            mock.send(other, QueryOptions::new()),
+            Err(Error::InvalidArgument(_))
+        ));
+    }
+
+    #[test]
+    fn send_ok_through_kuzzle() {
+        let mock = Mock::new();
+        mock.when("server", "now")
+            .respond(json!({ "now": 1447151167622u64 }));
+
+        let k = Kuzzle::new(mock.clone());
+        k.connect().unwrap();
+        k.set_jwt("aSuperJwtToken".to_string());
+
+        assert_eq!(k.server().now(QueryOptions::new()).unwrap(), 1447151167622);
+        let calls = mock.calls_to("server", "now");
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].jwt(), "aSuperJwtToken");
+    }
+
+    #[test]
+    fn send_fail_kuzzle_error() {
+        let mock = connected();
+        mock.when("document", "get").respond_error(
+            KuzzleError::new(Some(404), "Document not found").set_id("services.storage.not_found"),
+        );
+
+        let res = mock.send(KuzzleRequest::new("document", "get"), QueryOptions::new());
+        let res = res.unwrap();
+
+        assert_eq!(*res.status(), 404);
+        assert!(res
+            .error()
+            .as_ref()
+            .unwrap()
+            .is("services.storage.not_found"));
+    }
+
+    #[test]
+    fn send_fail_scripted() {
+        let mock = connected();
+        mock.when("server", "now").fail(|| Error::Timeout);
+
+        let res = mock.send(KuzzleRequest::new("server", "now"), QueryOptions::new());
+
+        assert!(matches!(res, Err(Error::Timeout)));
+        assert_eq!(mock.calls().len(), 1);
+    }
+
+    #[test]
+    fn send_fail_not_connected() {
+        let mut mock = connected();
+        mock.when("server", "now").respond(json!({ "now": 1 }));
+        mock.close();
+
+        let res = mock.send(KuzzleRequest::new("server", "now"), QueryOptions::new());
+
+        assert!(matches!(res, Err(Error::NotConnected)));
+        assert!(mock.calls().is_empty());
+    }
+
+    #[test]
+    fn reset_ok() {
+        let mock = connected();
+        mock.when("server", "now").respond(json!({ "now": 1 }));
+        let _ = mock.send(KuzzleRequest::new("server", "now"), QueryOptions::new());
+        mock.reset();
+
+        let res = mock.send(KuzzleRequest::new("server", "now"), QueryOptions::new());
+
+        assert!(res.is_err());
+        assert_eq!(mock.calls().len(), 1);
+    }
+
+    #[test]
+    fn notify_ok() {
+        let mock = Mock::new();
+        let notifications = mock.notifications();
+        drop(mock.notifications());
+
+        mock.notify(
+            "a-room-id",
+            json!({ "action": "create", "result": { "_id": "some-id" } }),
+        )
+        .unwrap();
+        let notification = notifications.recv_timeout(Duration::from_secs(1)).unwrap();
+
+        assert_eq!(notification.room_id(), "a-room-id");
+        assert_eq!(notification.action(), "create");
+        assert_eq!(notification.result()["_id"], "some-id");
+        assert_eq!(lock(&mock._state.listeners).len(), 1);
+    }
+
+    #[test]
+    fn notify_fail_not_an_object() {
+        let mock = Mock::new();
+
+        assert!(matches!(
+            mock.notify("a-room-id", json!("created")),
+            Err(Error::InvalidArgument(_))
+        ));
+    }
+
+    #[test]
+    fn send_ok_reentrant_reply() {
+        let mock = connected();
+        let inner = mock.clone();
+        mock.when("server", "now").reply(move |req| {
+            inner.when("server", "now").respond(json!({ "now": 2 }));
+            response(req, 200, None, json!({ "now": inner.calls().len() }))
+        });
+
+        let res = mock.send(KuzzleRequest::new("server", "now"), QueryOptions::new());
+        assert_eq!(res.unwrap().result()["now"], 1);
+        let res = mock.send(KuzzleRequest::new("server", "now"), QueryOptions::new());
+        assert_eq!(res.unwrap().result()["now"], 2);
+    }
+}
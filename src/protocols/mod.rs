@@ -1,5 +1,9 @@
+pub(crate) mod cluster;
 mod http;
+mod mock;
 mod protocol;
+pub(crate) mod recorder;
+pub(crate) mod tls;
 mod websocket;
 
 #[derive(PartialEq)]
@@ -8,6 +12,9 @@ pub enum State {
     Offline,
 }
 
-pub use self::http::Http;
+pub use self::cluster::Cluster;
+pub use self::http::{Http, Route, Routes};
+pub use self::mock::{Mock, MockRule};
 pub use self::protocol::Protocol;
+pub use self::recorder::Recorder;
 pub use self::websocket::Websocket;
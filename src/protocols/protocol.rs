@@ -1,12 +1,17 @@
-use crate::types::{KuzzleRequest, KuzzleResponse, QueryOptions};
-use std::error::Error;
+use crate::types::{Error, KuzzleRequest, KuzzleResponse, QueryOptions};
+use std::sync::mpsc::Receiver;
 
-pub trait Protocol {
+pub trait Protocol: Send + Sync {
     fn once(&self);
     fn listener_count(&self);
-    fn connect(&mut self) -> Result<(), Box<Error>>;
-    fn send(&self, req: KuzzleRequest, options: QueryOptions)
-        -> Result<KuzzleResponse, Box<Error>>;
+    fn connect(&mut self) -> Result<(), Error>;
+    fn send(&self, req: KuzzleRequest, options: QueryOptions) -> Result<KuzzleResponse, Error>;
     fn close(&mut self);
     fn is_ready(&self) -> bool;
+
+    /// Returns a receiver of the realtime notifications sent by Kuzzle,
+    /// or None if the protocol can't receive any.
+    fn notification_receiver(&self) -> Option<Receiver<KuzzleResponse>> {
+        None
+    }
 }
@@ -0,0 +1,554 @@
+use crate::protocols::{Protocol, Websocket};
+use crate::types::{Error, KuzzleError, KuzzleRequest, KuzzleResponse, QueryOptions, SdkError};
+
+use serde_json::Value;
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::Receiver;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+/// Request fields left out of fixtures, as they change from one run to another.
+const DEFAULT_IGNORED: [&str; 2] = ["/requestId", "/jwt"];
+
+/// Records the requests sent through a protocol and their responses to a fixture file,
+/// or answers requests with the responses recorded in a fixture file.
+///
+/// Fixtures hold one JSON object per line, appended as requests are answered.
+/// Errors returned by the protocol, such as network errors or timeouts, are recorded
+/// and replayed too.
+///
+/// Requests are matched on their JSON payload, as sent to Kuzzle by `Websocket`,
+/// except for the fields ignored with `ignore`: by default `requestId` and `jwt`.
+/// Identical requests get their recorded responses in order, the last one being
+/// repeated once they have all been served.
+///
+/// Ignored fields are not written to the fixture, but the responses are recorded
+/// as they are: fixtures of sessions including a login contain the token returned by Kuzzle.
+///
+/// # Example
+///
+/// ```no_run
+/// use kuzzle_sdk::kuzzle::Kuzzle;
+/// use kuzzle_sdk::protocols::{Http, Recorder};
+/// use kuzzle_sdk::types::KuzzleOptions;
+///
+/// let fixture = "tests/fixtures/create_document.jsonl";
+/// let protocol = if std::env::var("KUZZLE_RECORD").is_ok() {
+///     Recorder::record(Http::new(KuzzleOptions::new("localhost", 7512)), fixture)
+/// } else {
+///     Recorder::replay(fixture).unwrap()
+/// };
+/// let kuzzle = Kuzzle::new(protocol.ignore("/body/createdAt"));
+/// ```
+pub struct Recorder {
+    _protocol: Option<Box<dyn Protocol>>,
+    _fixture: Fixture,
+    _ready: AtomicBool,
+}
+
+impl Recorder {
+    /// Returns a recorder sending requests through `protocol`, and appending them
+    /// along with their responses to `path` after each request.
+    /// The file is overwritten by the first request.
+    pub fn record<P, Q>(protocol: P, path: Q) -> Recorder
+    where
+        P: 'static + Protocol,
+        Q: Into<PathBuf>,
+    {
+        Recorder {
+            _protocol: Some(Box::new(protocol)),
+            _fixture: Fixture::empty(path.into()),
+            _ready: AtomicBool::new(false),
+        }
+    }
+
+    /// Returns a recorder answering requests with the responses recorded in `path`,
+    /// without sending them.
+    pub fn replay<Q: Into<PathBuf>>(path: Q) -> Result<Recorder, Error> {
+        Ok(Recorder {
+            _protocol: None,
+            _fixture: Fixture::load(path.into())?,
+            _ready: AtomicBool::new(false),
+        })
+    }
+
+    /// Leaves the request field at `pointer` out of fixtures and of request matching,
+    /// e.g. `/body/createdAt` for a timestamp.
+    pub fn ignore(mut self, pointer: &str) -> Self {
+        self._fixture.ignore(pointer);
+        self
+    }
+
+    /// Stops ignoring the request field at `pointer`, such as `/jwt` to match
+    /// requests on the token they are sent with.
+    pub fn compare(mut self, pointer: &str) -> Self {
+        self._fixture.compare(pointer);
+        self
+    }
+
+    pub fn path(&self) -> &Path {
+        self._fixture.path()
+    }
+
+    /// Returns true if requests are answered from the fixture file.
+    pub fn is_replaying(&self) -> bool {
+        self._protocol.is_none()
+    }
+}
+
+impl Protocol for Recorder {
+    fn once(&self) {
+        if let Some(protocol) = &self._protocol {
+            protocol.once()
+        }
+    }
+
+    fn listener_count(&self) {
+        if let Some(protocol) = &self._protocol {
+            protocol.listener_count()
+        }
+    }
+
+    fn notification_receiver(&self) -> Option<Receiver<KuzzleResponse>> {
+        self._protocol
+            .as_ref()
+            .and_then(|protocol| protocol.notification_receiver())
+    }
+
+    fn connect(&mut self) -> Result<(), Error> {
+        match &mut self._protocol {
+            Some(protocol) => protocol.connect(),
+            None => {
+                self._ready.store(true, Ordering::SeqCst);
+                Ok(())
+            }
+        }
+    }
+
+    fn send(&self, req: KuzzleRequest, options: QueryOptions) -> Result<KuzzleResponse, Error> {
+        match &self._protocol {
+            Some(protocol) => {
+                let res = protocol.send(req.clone(), options);
+                self._fixture.record(&req, &res)?;
+                res
+            }
+            None if !self.is_ready() => Err(Error::NotConnected),
+            None => {
+                options.check(None)?;
+                self._fixture.replay(&req)
+            }
+        }
+    }
+
+    fn close(&mut self) {
+        match &mut self._protocol {
+            Some(protocol) => protocol.close(),
+            None => self._ready.store(false, Ordering::SeqCst),
+        }
+    }
+
+    fn is_ready(&self) -> bool {
+        match &self._protocol {
+            Some(protocol) => protocol.is_ready(),
+            None => self._ready.load(Ordering::SeqCst),
+        }
+    }
+}
+
+/// A recorded request, and the response Kuzzle sent to it or the protocol error.
+#[derive(Deserialize, Serialize)]
+struct Exchange {
+    request: Value,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    response: Option<KuzzleResponse>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    error: Option<RecordedError>,
+    #[serde(skip)]
+    replayed: bool,
+}
+
+/// An error returned by the recorded protocol.
+#[derive(Deserialize, Serialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+enum RecordedError {
+    Network { message: String },
+    Timeout,
+    Cancelled,
+    NotConnected,
+    InvalidArgument { cause: String, message: String },
+    UnknownRoute { controller: String, action: String },
+    Kuzzle { error: KuzzleError },
+    Other { message: String },
+}
+
+impl From<&Error> for RecordedError {
+    fn from(err: &Error) -> RecordedError {
+        if let Some(error) = err.kuzzle_error() {
+            return RecordedError::Kuzzle {
+                error: error.clone(),
+            };
+        }
+
+        match err {
+            Error::Network(err) => RecordedError::Network {
+                message: err.to_string(),
+            },
+            Error::Timeout => RecordedError::Timeout,
+            Error::Cancelled => RecordedError::Cancelled,
+            Error::NotConnected => RecordedError::NotConnected,
+            Error::InvalidArgument(err) => RecordedError::InvalidArgument {
+                cause: err.cause().clone(),
+                message: err.message().clone(),
+            },
+            Error::UnknownRoute { controller, action } => RecordedError::UnknownRoute {
+                controller: controller.clone(),
+                action: action.clone(),
+            },
+            err => RecordedError::Other {
+                message: err.to_string(),
+            },
+        }
+    }
+}
+
+impl From<&RecordedError> for Error {
+    fn from(err: &RecordedError) -> Error {
+        match err {
+            RecordedError::Network { message } => Error::Network(message.clone().into()),
+            RecordedError::Timeout => Error::Timeout,
+            RecordedError::Cancelled => Error::Cancelled,
+            RecordedError::NotConnected => Error::NotConnected,
+            RecordedError::InvalidArgument { cause, message } => {
+                Error::InvalidArgument(SdkError::new(cause, message))
+            }
+            RecordedError::UnknownRoute { controller, action } => Error::UnknownRoute {
+                controller: controller.clone(),
+                action: action.clone(),
+            },
+            RecordedError::Kuzzle { error } => Error::from(error.clone()),
+            RecordedError::Other { message } => Error::Io(io::Error::other(message.clone())),
+        }
+    }
+}
+
+/// The exchanges of a fixture file, shared by the blocking and async recorders.
+pub(crate) struct Fixture {
+    _path: PathBuf,
+    _exchanges: Mutex<Vec<Exchange>>,
+    _file: Mutex<Option<File>>,
+    _ignored: Vec<String>,
+}
+
+impl Fixture {
+    fn new(path: PathBuf, exchanges: Vec<Exchange>) -> Fixture {
+        Fixture {
+            _path: path,
+            _exchanges: Mutex::new(exchanges),
+            _file: Mutex::new(None),
+            _ignored: DEFAULT_IGNORED
+                .iter()
+                .map(|field| field.to_string())
+                .collect(),
+        }
+    }
+
+    pub(crate) fn load(path: PathBuf) -> Result<Fixture, Error> {
+        let mut exchanges = Vec::new();
+        for line in fs::read_to_string(&path)?.lines() {
+            if !line.trim().is_empty() {
+                exchanges.push(serde_json::from_str(line)?);
+            }
+        }
+        Ok(Fixture::new(path, exchanges))
+    }
+
+    pub(crate) fn empty(path: PathBuf) -> Fixture {
+        Fixture::new(path, Vec::new())
+    }
+
+    pub(crate) fn ignore(&mut self, pointer: &str) {
+        if !self._ignored.iter().any(|ignored| ignored == pointer) {
+            self._ignored.push(pointer.to_string());
+        }
+    }
+
+    pub(crate) fn compare(&mut self, pointer: &str) {
+        self._ignored.retain(|ignored| ignored != pointer);
+    }
+
+    pub(crate) fn path(&self) -> &Path {
+        &self._path
+    }
+
+    /// Appends `req` and its outcome to the fixture file, created by the first exchange.
+    pub(crate) fn record(
+        &self,
+        req: &KuzzleRequest,
+        res: &Result<KuzzleResponse, Error>,
+    ) -> Result<(), Error> {
+        let exchange = Exchange {
+            request: self.request(req),
+            response: res.as_ref().ok().cloned(),
+            error: res.as_ref().err().map(RecordedError::from),
+            replayed: false,
+        };
+        let mut line = serde_json::to_vec(&exchange)?;
+        line.push(b'\n');
+
+        let mut file = self._file.lock().unwrap_or_else(PoisonError::into_inner);
+        if file.is_none() {
+            if let Some(parent) = self._path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            *file = Some(File::create(&self._path)?);
+        }
+        if let Some(file) = file.as_mut() {
+            file.write_all(&line)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the recorded response to `req`, with the request id of `req` if it has one.
+    pub(crate) fn replay(&self, req: &KuzzleRequest) -> Result<KuzzleResponse, Error> {
+        let request = self.request(req);
+        let mut exchanges = self.exchanges();
+
+        let matching: Vec<usize> = (0..exchanges.len())
+            .filter(|&index| self.strip(exchanges[index].request.clone()) == request)
+            .collect();
+        let index = matching
+            .iter()
+            .find(|&&index| !exchanges[index].replayed)
+            .or_else(|| matching.last())
+            .copied()
+            .ok_or_else(|| {
+                Error::InvalidArgument(SdkError::new(
+                    "Recorder::send",
+                    &format!(
+                        "No response recorded in {} for {}",
+                        self._path.display(),
+                        request
+                    ),
+                ))
+            })?;
+        exchanges[index].replayed = true;
+
+        let response = match (&exchanges[index].response, &exchanges[index].error) {
+            (Some(response), _) => response,
+            (None, Some(error)) => return Err(Error::from(error)),
+            (None, None) => {
+                return Err(Error::InvalidArgument(SdkError::new(
+                    "Recorder::send",
+                    &format!("No outcome recorded in {}", self._path.display()),
+                )))
+            }
+        };
+        let mut response = serde_json::to_value(response)?;
+        if !req.request_id().is_empty() {
+            response["requestId"] = Value::from(req.request_id());
+        }
+        Ok(serde_json::from_value(response)?)
+    }
+
+    /// Returns the payload of `req`, without the ignored fields.
+    fn request(&self, req: &KuzzleRequest) -> Value {
+        self.strip(Websocket::payload(req, &req.request_id()))
+    }
+
+    fn strip(&self, mut request: Value) -> Value {
+        for pointer in &self._ignored {
+            let (parent, field) = match pointer.rfind('/') {
+                Some(separator) => (&pointer[..separator], &pointer[separator + 1..]),
+                None => continue,
+            };
+            // Escaped as described by RFC 6901
+            let field = field.replace("~1", "/").replace("~0", "~");
+
+            if let Some(parent) = request.pointer_mut(parent).and_then(Value::as_object_mut) {
+                parent.remove(&field);
+            }
+        }
+        request
+    }
+
+    fn exchanges(&self) -> MutexGuard<'_, Vec<Exchange>> {
+        self._exchanges
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocols::Mock;
+    use serde_json::json;
+    use std::env;
+    use std::process;
+
+    fn fixture_path(name: &str) -> PathBuf {
+        env::temp_dir()
+            .join(format!("kuzzle_sdk_{}", process::id()))
+            .join(name)
+    }
+
+    fn get_document(id: &str) -> KuzzleRequest {
+        KuzzleRequest::new("document", "get")
+            .set_index("nyc-open-data")
+            .set_collection("yellow-taxi")
+            .set_id(id)
+            .set_jwt("aSuperJwtToken".to_string())
+    }
+
+    fn record(path: &Path) {
+        let mock = Mock::new();
+        mock.when("document", "get")
+            .respond(json!({ "_id": "some-id", "_version": 1 }));
+        mock.when("document", "get")
+            .once()
+            .respond(json!({ "_id": "some-id", "_version": 2 }));
+
+        let mut recorder = Recorder::record(mock, path);
+        recorder.connect().unwrap();
+        for _ in 0..2 {
+            recorder
+                .send(get_document("some-id"), QueryOptions::new())
+                .unwrap();
+        }
+    }
+
+    #[test]
+    fn replay_ok() {
+        let path = fixture_path("replay_ok.jsonl");
+        record(&path);
+
+        let mut recorder = Recorder::replay(&path).unwrap();
+        recorder.connect().unwrap();
+        let version = |req: KuzzleRequest| {
+            recorder.send(req, QueryOptions::new()).unwrap().result()["_version"].clone()
+        };
+
+        assert_eq!(version(get_document("some-id")), 2);
+        assert_eq!(
+            version(get_document("some-id").set_jwt("anotherJwtToken".to_string())),
+            1
+        );
+        assert_eq!(version(get_document("some-id")), 1);
+
+        let fixture = fs::read_to_string(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert!(!fixture.contains("aSuperJwtToken"));
+    }
+
+    #[test]
+    fn replay_ok_request_id() {
+        let path = fixture_path("replay_ok_request_id.jsonl");
+        record(&path);
+
+        let mut recorder = Recorder::replay(&path).unwrap();
+        recorder.connect().unwrap();
+        let res = recorder.send(
+            get_document("some-id").set_request_id("a-request-id"),
+            QueryOptions::new(),
+        );
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(res.unwrap().request_id(), "a-request-id");
+    }
+
+    #[test]
+    fn replay_ok_ignored_body_field() {
+        let path = fixture_path("replay_ok_ignored_body_field.jsonl");
+        let mock = Mock::new();
+        mock.when("document", "create")
+            .respond(json!({ "_id": "some-id" }));
+        let create = |created_at: u64| {
+            KuzzleRequest::new("document", "create")
+                .add_to_body("name", json!("ferris"))
+                .add_to_body("createdAt", json!(created_at))
+        };
+
+        let mut recorder = Recorder::record(mock, &path).ignore("/body/createdAt");
+        recorder.connect().unwrap();
+        recorder.send(create(1), QueryOptions::new()).unwrap();
+
+        let mut recorder = Recorder::replay(&path).unwrap().ignore("/body/createdAt");
+        recorder.connect().unwrap();
+        let res = recorder.send(create(2), QueryOptions::new());
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(res.unwrap().result()["_id"], "some-id");
+    }
+
+    #[test]
+    fn replay_ok_errors() {
+        let path = fixture_path("replay_ok_errors.jsonl");
+        let mock = Mock::new();
+        mock.when("server", "now").fail(|| Error::Timeout);
+        mock.when("document", "get")
+            .fail(|| Error::Network("Connection reset".into()));
+
+        let mut recorder = Recorder::record(mock, &path);
+        recorder.connect().unwrap();
+        for _ in 0..2 {
+            let res = recorder.send(KuzzleRequest::new("server", "now"), QueryOptions::new());
+            assert!(matches!(res, Err(Error::Timeout)));
+        }
+        let res = recorder.send(get_document("some-id"), QueryOptions::new());
+        assert!(matches!(res, Err(Error::Network(_))));
+
+        let fixture = fs::read_to_string(&path).unwrap();
+        assert_eq!(fixture.lines().count(), 3);
+
+        let mut recorder = Recorder::replay(&path).unwrap();
+        recorder.connect().unwrap();
+        let timeout = recorder.send(KuzzleRequest::new("server", "now"), QueryOptions::new());
+        let network = recorder.send(get_document("some-id"), QueryOptions::new());
+        fs::remove_file(&path).unwrap();
+
+        assert!(matches!(timeout, Err(Error::Timeout)));
+        match network {
+            Err(Error::Network(err)) => assert_eq!(err.to_string(), "Connection reset"),
+            _ => panic!("Expected a network error"),
+        }
+    }
+
+    #[test]
+    fn replay_fail_not_recorded() {
+        let path = fixture_path("replay_fail_not_recorded.jsonl");
+        record(&path);
+
+        let mut recorder = Recorder::replay(&path).unwrap().compare("/jwt");
+        recorder.connect().unwrap();
+        let other_id = recorder.send(get_document("other-id"), QueryOptions::new());
+        let other_jwt = recorder.send(
+            get_document("some-id").set_jwt("anotherJwtToken".to_string()),
+            QueryOptions::new(),
+        );
+        fs::remove_file(&path).unwrap();
+
+        assert!(matches!(other_id, Err(Error::InvalidArgument(_))));
+        assert!(matches!(other_jwt, Err(Error::InvalidArgument(_))));
+    }
+
+    #[test]
+    fn replay_fail_not_connected() {
+        let path = fixture_path("replay_fail_not_connected.jsonl");
+        record(&path);
+
+        let recorder = Recorder::replay(&path).unwrap();
+        let res = recorder.send(get_document("some-id"), QueryOptions::new());
+        fs::remove_file(&path).unwrap();
+
+        assert!(matches!(res, Err(Error::NotConnected)));
+    }
+
+    #[test]
+    fn replay_fail_missing_fixture() {
+        let res = Recorder::replay(fixture_path("replay_fail_missing_fixture.jsonl"));
+
+        assert!(matches!(res, Err(Error::Io(_))));
+    }
+}
@@ -0,0 +1,101 @@
+use crate::types::{ClientIdentity, Error, KuzzleOptions, SdkError, TlsOptions};
+use native_tls::{Certificate, Identity, TlsConnector};
+use std::fs;
+
+/// Builds the TLS connector shared by the `Http` and `Websocket` protocols.
+pub(crate) fn connector(tls: &TlsOptions) -> Result<TlsConnector, Error> {
+    let mut builder = TlsConnector::builder();
+
+    if let Some(path) = tls.ca_bundle() {
+        let certificates = Certificate::stack_from_pem(&fs::read(path)?)
+            .map_err(|err| invalid(&format!("Invalid CA bundle {}: {}", path.display(), err)))?;
+
+        if certificates.is_empty() {
+            return Err(invalid(&format!(
+                "Invalid CA bundle {}: no certificate found",
+                path.display()
+            )));
+        }
+
+        for certificate in certificates {
+            builder.add_root_certificate(certificate);
+        }
+    }
+
+    if let Some(identity) = tls.client_identity() {
+        builder.identity(read_identity(identity)?);
+    }
+
+    builder.danger_accept_invalid_certs(tls.accept_invalid_certs());
+    builder
+        .build()
+        .map_err(|err| invalid(&format!("Unable to configure TLS: {}", err)))
+}
+
+/// Returns the host name to present to the server: the SNI override
+/// when TLS is enabled and one is configured, the Kuzzle host otherwise.
+pub(crate) fn server_name(options: &KuzzleOptions) -> &str {
+    match options.tls().sni_hostname() {
+        Some(hostname) if *options.ssl_connection() => hostname,
+        _ => options.host(),
+    }
+}
+
+fn read_identity(identity: &ClientIdentity) -> Result<Identity, Error> {
+    let res = match identity {
+        ClientIdentity::Pem { certificate, key } => {
+            Identity::from_pkcs8(&fs::read(certificate)?, &fs::read(key)?)
+        }
+        ClientIdentity::Pkcs12 { archive, password } => {
+            Identity::from_pkcs12(&fs::read(archive)?, password)
+        }
+    };
+
+    res.map_err(|err| invalid(&format!("Invalid client certificate: {}", err)))
+}
+
+fn invalid(message: &str) -> Error {
+    Error::InvalidArgument(SdkError::new("tls::connector", message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    #[test]
+    fn connector_ok() {
+        let tls = TlsOptions::new().set_accept_invalid_certs(true);
+
+        assert!(connector(&tls).is_ok());
+    }
+
+    #[test]
+    fn connector_fail_missing_ca_bundle() {
+        let tls = TlsOptions::new().set_ca_bundle("/nonexistent/ca.pem");
+
+        assert!(matches!(connector(&tls), Err(Error::Io(_))));
+    }
+
+    #[test]
+    fn connector_fail_invalid_ca_bundle() {
+        let path = env::temp_dir().join("kuzzle_sdk_invalid_ca.pem");
+        fs::write(&path, "not a certificate").unwrap();
+        let res = connector(&TlsOptions::new().set_ca_bundle(&path));
+        fs::remove_file(&path).unwrap();
+
+        assert!(matches!(res, Err(Error::InvalidArgument(_))));
+    }
+
+    #[test]
+    fn server_name_ok() {
+        let tls = TlsOptions::new().set_sni_hostname("kuzzle.example.com");
+        let options = KuzzleOptions::new("10.0.0.12", 7443).set_tls(tls);
+
+        assert_eq!(server_name(&options), "10.0.0.12");
+        assert_eq!(
+            server_name(&options.set_ssl_connection(true)),
+            "kuzzle.example.com"
+        );
+    }
+}
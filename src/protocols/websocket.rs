@@ -1,19 +1,315 @@
-use crate::protocols::{Protocol, State};
-use crate::types::{KuzzleOptions, KuzzleRequest, KuzzleResponse, QueryOptions};
-use std::error::Error;
+use crate::protocols::{tls, Protocol};
+use crate::types::{Error, KuzzleOptions, KuzzleRequest, KuzzleResponse, QueryOptions};
 
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::io::ErrorKind;
+use std::net::TcpStream;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender, TryRecvError};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+use tungstenite::stream::MaybeTlsStream;
+use tungstenite::{Message, WebSocket};
+
+type Socket = WebSocket<MaybeTlsStream<TcpStream>>;
+
+/// Requests waiting for their response, indexed by request id.
+type Senders = HashMap<String, Sender<Result<KuzzleResponse, Error>>>;
+type Pending = Arc<Mutex<Senders>>;
+/// Receivers of the realtime notifications.
+type Listeners = Arc<Mutex<Vec<Sender<KuzzleResponse>>>>;
+
+/// Used to generate request ids unique to this process.
+static REQUEST_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// How often a pending request checks whether it has been cancelled.
+const CANCELLATION_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// How often the connection stops waiting for messages from Kuzzle
+/// to send the requests queued in the meantime.
+const WRITE_POLL_INTERVAL: Duration = Duration::from_millis(5);
+
+/// Sends requests to Kuzzle through a WebSocket connection.
+///
+/// The connection is handled by a background thread, writing requests as they
+/// are sent and handing each response over to the request it answers:
+/// requests sent from several threads share the connection without waiting
+/// for each other. Realtime notifications are handed over to the receivers
+/// returned by `notifications`.
+///
+/// The protocol is no longer ready once the connection is lost:
+/// `connect` opens a new one.
 pub struct Websocket {
     _options: KuzzleOptions,
-    _state: State,
+    _ready: Arc<AtomicBool>,
+    _outgoing: Option<Mutex<Sender<Message>>>,
+    _pending: Pending,
+    _listeners: Listeners,
+    _connection: Option<JoinHandle<()>>,
 }
 
 impl Websocket {
+    /// Returns a Websocket struct that acts as a WebSocket
+    /// client to dial with Kuzzle server.
+    /// If `ssl_connection` is enabled, the connection is secured
+    /// with the TLS options of `KuzzleOptions`.
+    ///
+    /// # Arguments
+    /// * `options` - An `types::Options` used to configure Websocket dialer
+    ///
+    /// # Example
+    /// ```
+    /// use kuzzle_sdk::types::KuzzleOptions;
+    /// use kuzzle_sdk::protocols::Websocket;
+    ///
+    /// let ws = Websocket::new(KuzzleOptions::new("localhost", 7512));
+    /// ```
     pub fn new(options: KuzzleOptions) -> Websocket {
         Websocket {
             _options: options,
-            _state: State::Offline,
+            _ready: Arc::new(AtomicBool::new(false)),
+            _outgoing: None,
+            _pending: Arc::new(Mutex::new(HashMap::new())),
+            _listeners: Arc::new(Mutex::new(Vec::new())),
+            _connection: None,
+        }
+    }
+
+    /// Returns a receiver of the realtime notifications sent by Kuzzle,
+    /// for the subscriptions made through this connection.
+    /// Receivers are kept when reconnecting: get them before handing
+    /// the protocol over to `Kuzzle`.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Websocket;
+    /// use kuzzle_sdk::types::KuzzleOptions;
+    ///
+    /// let ws = Websocket::new(KuzzleOptions::new("localhost", 7512));
+    /// let notifications = ws.notifications();
+    /// let kuzzle = Kuzzle::new(ws);
+    /// kuzzle.connect().unwrap();
+    ///
+    /// for notification in notifications {
+    ///     println!("{}: {}", notification.room_id(), notification.result());
+    /// }
+    /// ```
+    pub fn notifications(&self) -> Receiver<KuzzleResponse> {
+        let (sender, receiver) = mpsc::channel();
+        lock(&self._listeners).push(sender);
+        receiver
+    }
+
+    /// Opens the TCP connection to Kuzzle, secured with TLS if `ssl_connection` is enabled.
+    fn open_stream(&self) -> Result<MaybeTlsStream<TcpStream>, Error> {
+        let stream =
+            TcpStream::connect(format!("{}:{}", self._options.host(), self._options.port()))
+                .map_err(|err| Error::Network(Box::new(err)))?;
+
+        if !*self._options.ssl_connection() {
+            return Ok(MaybeTlsStream::Plain(stream));
+        }
+
+        let connector = tls::connector(self._options.tls())?;
+        let stream = connector
+            .connect(tls::server_name(&self._options), stream)
+            .map_err(|err| Error::Network(err.to_string().into()))?;
+
+        Ok(MaybeTlsStream::NativeTls(stream))
+    }
+
+    /// Returns the WebSocket URL of the Kuzzle server: `wss` is used if `ssl_connection`
+    /// is enabled, and the host is replaced by the SNI hostname if one is set.
+    pub(crate) fn url(options: &KuzzleOptions) -> String {
+        format!(
+            "{}://{}:{}",
+            if *options.ssl_connection() {
+                "wss"
+            } else {
+                "ws"
+            },
+            tls::server_name(options),
+            options.port()
+        )
+    }
+
+    /// Returns the request id set by the user, or a new one unique to this process.
+    pub(crate) fn request_id(req: &KuzzleRequest) -> String {
+        match req.request_id() {
+            request_id if request_id.is_empty() => format!(
+                "kuzzle-sdk-rust-{}",
+                REQUEST_COUNTER.fetch_add(1, Ordering::SeqCst)
+            ),
+            request_id => request_id,
         }
     }
+
+    /// Converts a request to the JSON payload expected by Kuzzle,
+    /// with query strings as top level arguments.
+    pub(crate) fn payload(req: &KuzzleRequest, request_id: &str) -> Value {
+        let mut payload: Map<String, Value> = req
+            .query_strings()
+            .iter()
+            .map(|(name, value)| (name.clone(), value.clone()))
+            .collect();
+
+        payload.insert(
+            "controller".to_string(),
+            Value::from(req.controller().as_str()),
+        );
+        payload.insert("action".to_string(), Value::from(req.action().as_str()));
+        payload.insert("requestId".to_string(), Value::from(request_id));
+
+        for (name, value) in &[
+            ("index", req.index()),
+            ("collection", req.collection()),
+            ("_id", req.id()),
+            ("strategy", req.strategy()),
+            ("jwt", req.jwt()),
+        ] {
+            if !value.is_empty() {
+                payload.insert(name.to_string(), Value::from(value.as_str()));
+            }
+        }
+
+        if !req.volatile().is_empty() {
+            payload.insert(
+                "volatile".to_string(),
+                Value::Object(req.volatile().clone().into_iter().collect()),
+            );
+        }
+
+        if !req.body().is_empty() {
+            payload.insert(
+                "body".to_string(),
+                Value::Object(req.body().clone().into_iter().collect()),
+            );
+        }
+
+        Value::Object(payload)
+    }
+
+    /// Returns true if `message` is a realtime notification rather than a response:
+    /// Kuzzle may also send responses to a room, named after their request id.
+    pub(crate) fn is_notification(message: &Value) -> bool {
+        match message["room"].as_str() {
+            Some(room) => message["requestId"].as_str() != Some(room),
+            None => false,
+        }
+    }
+
+    /// Writes the requests queued in `outgoing` and reads messages from Kuzzle,
+    /// handing each response over to the pending request it answers and each
+    /// notification over to `listeners`, until the connection is lost or closed.
+    /// The protocol is then no longer `ready`, and pending requests fail.
+    fn run(
+        mut socket: Socket,
+        outgoing: Receiver<Message>,
+        pending: Pending,
+        listeners: Listeners,
+        ready: Arc<AtomicBool>,
+    ) {
+        'connection: loop {
+            loop {
+                match outgoing.try_recv() {
+                    Ok(message) => {
+                        if socket.send(message).is_err() {
+                            break 'connection;
+                        }
+                    }
+                    Err(TryRecvError::Empty) => break,
+                    Err(TryRecvError::Disconnected) => {
+                        let _ = socket.close(None);
+                        let _ = socket.flush();
+                        break 'connection;
+                    }
+                }
+            }
+
+            match socket.read() {
+                Ok(Message::Text(text)) => Websocket::dispatch(&text, &pending, &listeners),
+                Ok(Message::Close(_)) => break,
+                Ok(_) => (),
+                Err(tungstenite::Error::Io(ref err))
+                    if err.kind() == ErrorKind::WouldBlock || err.kind() == ErrorKind::TimedOut => {
+                }
+                Err(_) => break,
+            }
+        }
+
+        // Requests can no longer be queued once pending ones are failed
+        ready.store(false, Ordering::SeqCst);
+        drop(outgoing);
+        for (_, sender) in lock(&pending).drain() {
+            let _ = sender.send(Err(closed()));
+        }
+    }
+
+    /// Hands the response in `text` over to the pending request it answers,
+    /// or the notification in `text` over to every listener.
+    fn dispatch(text: &str, pending: &Pending, listeners: &Listeners) {
+        let message: Value = match serde_json::from_str(text) {
+            Ok(message) => message,
+            Err(_) => return,
+        };
+
+        // Notifications share the request id of the subscription, but are sent to its room
+        if Websocket::is_notification(&message) {
+            if let Ok(notification) = serde_json::from_value::<KuzzleResponse>(message) {
+                // Receivers which have been dropped are forgotten
+                lock(listeners).retain(|listener| listener.send(notification.clone()).is_ok());
+            }
+            return;
+        }
+
+        let sender = match message["requestId"].as_str() {
+            Some(request_id) => lock(pending).remove(request_id),
+            None => None,
+        };
+        if let Some(sender) = sender {
+            let _ = sender.send(serde_json::from_value(message).map_err(Error::from));
+        }
+    }
+
+    /// Waits for the response of a request until it expires or is cancelled.
+    fn wait(
+        response: &Receiver<Result<KuzzleResponse, Error>>,
+        options: &QueryOptions,
+        expires_at: Option<Instant>,
+    ) -> Result<KuzzleResponse, Error> {
+        loop {
+            options.check(expires_at)?;
+
+            let mut timeout =
+                expires_at.map(|expires_at| expires_at.saturating_duration_since(Instant::now()));
+            if options.cancellation_token().is_some() {
+                timeout = Some(timeout.map_or(CANCELLATION_POLL_INTERVAL, |timeout| {
+                    timeout.min(CANCELLATION_POLL_INTERVAL)
+                }));
+            }
+
+            let res = match timeout {
+                Some(timeout) => response.recv_timeout(timeout),
+                None => response.recv().map_err(|_| RecvTimeoutError::Disconnected),
+            };
+            match res {
+                Ok(res) => return res,
+                Err(RecvTimeoutError::Timeout) => continue,
+                Err(RecvTimeoutError::Disconnected) => return Err(closed()),
+            }
+        }
+    }
+}
+
+fn closed() -> Error {
+    Error::Network("Connection closed by Kuzzle".into())
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
 }
 
 impl Protocol for Websocket {
@@ -26,25 +322,409 @@ impl Protocol for Websocket {
     }
 
     fn is_ready(&self) -> bool {
-        match self._state {
-            State::Ready => true,
-            State::Offline => false,
-        }
+        self._ready.load(Ordering::SeqCst)
     }
 
-    fn connect(&mut self) -> Result<(), Box<Error>> {
-        unimplemented!();
+    fn notification_receiver(&self) -> Option<Receiver<KuzzleResponse>> {
+        Some(self.notifications())
     }
 
-    fn send(
-        &self,
-        _req: KuzzleRequest,
-        _options: QueryOptions,
-    ) -> Result<KuzzleResponse, Box<Error>> {
-        unimplemented!();
+    fn connect(&mut self) -> Result<(), Error> {
+        if self.is_ready() {
+            return Ok(());
+        }
+        // Releases the lost connection, if any
+        self.close();
+
+        let url = Websocket::url(&self._options);
+        let (socket, _) = tungstenite::client(url.as_str(), self.open_stream()?)
+            .map_err(|err| Error::Network(err.to_string().into()))?;
+
+        let timeout = Some(WRITE_POLL_INTERVAL);
+        match socket.get_ref() {
+            MaybeTlsStream::Plain(stream) => stream.set_read_timeout(timeout),
+            MaybeTlsStream::NativeTls(stream) => stream.get_ref().set_read_timeout(timeout),
+            _ => Ok(()),
+        }
+        .map_err(|err| Error::Network(Box::new(err)))?;
+
+        let (outgoing, receiver) = mpsc::channel();
+        let (pending, listeners) = (self._pending.clone(), self._listeners.clone());
+        let ready = self._ready.clone();
+        ready.store(true, Ordering::SeqCst);
+        self._connection = Some(thread::spawn(move || {
+            Websocket::run(socket, receiver, pending, listeners, ready)
+        }));
+        self._outgoing = Some(Mutex::new(outgoing));
+        Ok(())
+    }
+
+    fn send(&self, req: KuzzleRequest, options: QueryOptions) -> Result<KuzzleResponse, Error> {
+        let outgoing = match &self._outgoing {
+            Some(outgoing) if self.is_ready() => outgoing,
+            _ => return Err(Error::NotConnected),
+        };
+
+        let expires_at = options.expires_at(*self._options.request_timeout());
+        options.check(expires_at)?;
+
+        let request_id = Websocket::request_id(&req);
+        let (sender, response) = mpsc::channel();
+        lock(&self._pending).insert(request_id.clone(), sender);
+
+        // The lock is only held to queue the request: the connection thread writes it
+        let queued = lock(outgoing).send(Message::Text(
+            Websocket::payload(&req, &request_id).to_string(),
+        ));
+
+        let res = match queued {
+            Ok(()) => Websocket::wait(&response, &options, expires_at),
+            Err(_) => Err(closed()),
+        };
+        if res.is_err() {
+            lock(&self._pending).remove(&request_id);
+        }
+
+        res
     }
 
     fn close(&mut self) {
-        self._state = State::Offline;
+        // The connection thread closes the socket once requests can no longer be queued
+        self._ready.store(false, Ordering::SeqCst);
+        self._outgoing = None;
+        if let Some(connection) = self._connection.take() {
+            let _ = connection.join();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kuzzle::Kuzzle;
+    use crate::types::CancellationToken;
+    use serde_json::json;
+    use std::net::TcpListener;
+
+    /// Serves a single connection, answering each request after an unrelated message.
+    fn serve() -> (u32, thread::JoinHandle<Value>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port() as u32;
+
+        let server = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut socket = tungstenite::accept(stream).unwrap();
+            let request: Value = match socket.read().unwrap() {
+                Message::Text(text) => serde_json::from_str(&text).unwrap(),
+                message => panic!("Unexpected message {:?}", message),
+            };
+
+            socket
+                .send(Message::Text(
+                    r#"{ "requestId": "other", "status": 200, "result": null }"#.to_string(),
+                ))
+                .unwrap();
+            socket
+                .send(Message::Text(format!(
+                    r#"{{
+                        "requestId": {},
+                        "status": 200,
+                        "error": null,
+                        "controller": "server",
+                        "action": "now",
+                        "collection": null,
+                        "index": null,
+                        "volatile": null,
+                        "result": {{ "now": 1447151167622 }}
+                    }}"#,
+                    request["requestId"]
+                )))
+                .unwrap();
+
+            request
+        });
+
+        (port, server)
+    }
+
+    #[test]
+    fn send_ok() {
+        let (port, server) = serve();
+        let mut ws = Websocket::new(KuzzleOptions::new("127.0.0.1", port));
+        ws.connect().unwrap();
+
+        let req = KuzzleRequest::new("document", "get")
+            .set_index("index")
+            .set_collection("collection")
+            .set_jwt("token".to_string())
+            .add_to_query_strings("refresh", Value::from("wait_for"))
+            .add_to_volatile("user", Value::from("ferris"));
+        let res = ws.send(req, QueryOptions::new()).unwrap();
+        let request = server.join().unwrap();
+
+        assert_eq!(res.result()["now"], 1447151167622u64);
+        assert_eq!(res.request_id(), request["requestId"].as_str().unwrap());
+        assert_eq!(request["controller"], "document");
+        assert_eq!(request["index"], "index");
+        assert_eq!(request["jwt"], "token");
+        assert_eq!(request["refresh"], "wait_for");
+        assert_eq!(request["volatile"]["user"], "ferris");
+        assert!(request.get("_id").is_none());
+
+        ws.close();
+        assert!(!ws.is_ready());
+    }
+
+    #[test]
+    fn send_ok_custom_request_id() {
+        let (port, server) = serve();
+        let mut ws = Websocket::new(KuzzleOptions::new("127.0.0.1", port));
+        ws.connect().unwrap();
+
+        let req = KuzzleRequest::new("server", "now").set_request_id("ferris-request");
+        let res = ws.send(req, QueryOptions::new()).unwrap();
+
+        assert_eq!(server.join().unwrap()["requestId"], "ferris-request");
+        assert_eq!(res.request_id(), "ferris-request");
+    }
+
+    #[test]
+    fn send_ok_jwt_override() {
+        let (port, server) = serve();
+        let k = Kuzzle::new(Websocket::new(KuzzleOptions::new("127.0.0.1", port)));
+        k.connect().unwrap();
+        k.set_jwt("clientToken".to_string());
+
+        let res = k.server().now(QueryOptions::new().set_jwt("userToken"));
+
+        assert!(res.is_ok());
+        assert_eq!(server.join().unwrap()["jwt"], "userToken");
+        assert_eq!(k.jwt(), "clientToken");
+    }
+
+    #[test]
+    fn send_ok_anonymous() {
+        let (port, server) = serve();
+        let k = Kuzzle::new(Websocket::new(KuzzleOptions::new("127.0.0.1", port)));
+        k.connect().unwrap();
+        k.set_jwt("clientToken".to_string());
+
+        let res = k.server().now(QueryOptions::new().set_anonymous(true));
+
+        assert!(res.is_ok());
+        assert!(server.join().unwrap().get("jwt").is_none());
+    }
+
+    #[test]
+    fn send_ok_concurrent() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port() as u32;
+
+        // Answers two requests in the reverse order
+        let server = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut socket = tungstenite::accept(stream).unwrap();
+            let requests: Vec<Value> = (0..2)
+                .map(|_| {
+                    serde_json::from_str(&socket.read().unwrap().into_text().unwrap()).unwrap()
+                })
+                .collect();
+
+            for request in requests.iter().rev() {
+                let response = json!({
+                    "requestId": request["requestId"],
+                    "status": 200,
+                    "error": null,
+                    "result": request["body"]["value"]
+                });
+                socket.send(Message::Text(response.to_string())).unwrap();
+            }
+        });
+
+        let mut ws = Websocket::new(KuzzleOptions::new("127.0.0.1", port));
+        ws.connect().unwrap();
+
+        let results: Vec<Value> = thread::scope(|scope| {
+            let workers: Vec<_> = (0..2)
+                .map(|value| {
+                    let ws = &ws;
+                    scope.spawn(move || {
+                        let req = KuzzleRequest::new("server", "now")
+                            .add_to_body("value", Value::from(value));
+                        ws.send(req, QueryOptions::new()).unwrap().result().clone()
+                    })
+                })
+                .collect();
+            workers
+                .into_iter()
+                .map(|worker| worker.join().unwrap())
+                .collect()
+        });
+        server.join().unwrap();
+
+        assert_eq!(results, vec![json!(0), json!(1)]);
+    }
+
+    #[test]
+    fn notifications_ok() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port() as u32;
+
+        // Sends a notification, then the response to the subscription, sent to its own room
+        let server = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut socket = tungstenite::accept(stream).unwrap();
+            let request: Value =
+                serde_json::from_str(&socket.read().unwrap().into_text().unwrap()).unwrap();
+
+            for message in &[
+                json!({
+                    "requestId": request["requestId"],
+                    "room": "ferris-channel",
+                    "status": 200,
+                    "scope": "in",
+                    "result": { "_id": "taxi-1" }
+                }),
+                json!({
+                    "requestId": request["requestId"],
+                    "room": request["requestId"],
+                    "status": 200,
+                    "result": { "roomId": "ferris-room", "channel": "ferris-channel" }
+                }),
+            ] {
+                socket.send(Message::Text(message.to_string())).unwrap();
+            }
+            while socket.read().is_ok() {}
+        });
+
+        let mut ws = Websocket::new(KuzzleOptions::new("127.0.0.1", port));
+        let notifications = ws.notifications();
+        drop(ws.notifications());
+        ws.connect().unwrap();
+
+        let res = ws
+            .send(
+                KuzzleRequest::new("realtime", "subscribe"),
+                QueryOptions::new(),
+            )
+            .unwrap();
+        let notification = notifications.recv_timeout(Duration::from_secs(1)).unwrap();
+        ws.close();
+        server.join().unwrap();
+
+        assert_eq!(res.result()["roomId"], "ferris-room");
+        assert_eq!(notification.room_id(), "ferris-channel");
+        assert_eq!(notification.result()["_id"], "taxi-1");
+        assert_eq!(lock(&ws._listeners).len(), 1);
+    }
+
+    /// Accepts a single connection and never answers.
+    fn serve_silently() -> u32 {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port() as u32;
+
+        thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut socket = tungstenite::accept(stream).unwrap();
+            while socket.read().is_ok() {}
+        });
+
+        port
+    }
+
+    #[test]
+    fn send_fail_timeout() {
+        let options = KuzzleOptions::new("127.0.0.1", serve_silently()).set_request_timeout(50);
+        let mut ws = Websocket::new(options);
+        ws.connect().unwrap();
+
+        let start = Instant::now();
+        let res = ws.send(KuzzleRequest::new("server", "now"), QueryOptions::new());
+        assert!(matches!(res, Err(Error::Timeout)));
+        assert!(start.elapsed() >= Duration::from_millis(50));
+
+        let res = ws.send(
+            KuzzleRequest::new("server", "now"),
+            QueryOptions::new().set_timeout(10),
+        );
+        assert!(matches!(res, Err(Error::Timeout)));
+    }
+
+    #[test]
+    fn send_fail_cancelled() {
+        let mut ws = Websocket::new(KuzzleOptions::new("127.0.0.1", serve_silently()));
+        ws.connect().unwrap();
+
+        let token = CancellationToken::new();
+        let canceller = token.clone();
+        thread::spawn(move || {
+            thread::sleep(Duration::from_millis(50));
+            canceller.cancel();
+        });
+
+        let res = ws.send(
+            KuzzleRequest::new("server", "now"),
+            QueryOptions::new().set_cancellation_token(token),
+        );
+        assert!(matches!(res, Err(Error::Cancelled)));
+    }
+
+    #[test]
+    fn connect_ok_after_connection_lost() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port() as u32;
+
+        // Drops the first connection, then answers a request on the second one
+        let server = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            drop(tungstenite::accept(stream).unwrap());
+
+            let (stream, _) = listener.accept().unwrap();
+            let mut socket = tungstenite::accept(stream).unwrap();
+            let request: Value =
+                serde_json::from_str(&socket.read().unwrap().into_text().unwrap()).unwrap();
+            let response = json!({
+                "requestId": request["requestId"],
+                "status": 200,
+                "result": { "now": 1447151167622u64 }
+            });
+            socket.send(Message::Text(response.to_string())).unwrap();
+        });
+
+        let k = Kuzzle::new(Websocket::new(KuzzleOptions::new("127.0.0.1", port)));
+        k.connect().unwrap();
+        let lost = k.server().now(QueryOptions::new().set_timeout(1000));
+        let offline = k.server().now(QueryOptions::new());
+        // Depending on whether the connection loss was noticed before the request was sent
+        assert!(matches!(
+            lost,
+            Err(Error::Network(_)) | Err(Error::NotConnected)
+        ));
+        assert!(matches!(offline, Err(Error::NotConnected)));
+
+        k.connect().unwrap();
+        assert_eq!(k.server().now(QueryOptions::new()).unwrap(), 1447151167622);
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn connect_fail_refused() {
+        // Nothing listens on the port once the listener is dropped
+        let port = TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port() as u32;
+        let mut ws = Websocket::new(KuzzleOptions::new("127.0.0.1", port));
+
+        assert!(matches!(ws.connect(), Err(Error::Network(_))));
+    }
+
+    #[test]
+    fn send_fail_not_connected() {
+        let ws = Websocket::new(KuzzleOptions::new("localhost", 7512));
+        let res = ws.send(KuzzleRequest::new("server", "now"), QueryOptions::new());
+
+        assert!(matches!(res, Err(Error::NotConnected)));
     }
 }
@@ -0,0 +1,456 @@
+//! Background renewal of the authentication token.
+//!
+//! A `TokenRefresher` watches the session of a `Kuzzle` client and calls
+//! `auth:refreshToken` a configurable margin before the token expires.
+//! Sessions replaced by a new login are picked up automatically.
+
+use crate::kuzzle::Kuzzle;
+use crate::types::{Error, QueryOptions, Session};
+
+use chrono::{DateTime, Utc};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+/// How often the session is checked when no token is about to expire.
+const SESSION_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Margin used if none is set with `set_margin`.
+pub(crate) const DEFAULT_MARGIN: Duration = Duration::from_secs(60);
+
+/// Delay before the first retry of a renewal which failed with a transient error.
+const RETRY_DELAY: Duration = Duration::from_secs(1);
+
+/// Longest delay between two retries, the delay doubling after each failure.
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(30);
+
+pub(crate) type TokenExpiredListener = Box<dyn Fn(&Error) + Send + Sync>;
+
+pub struct TokenRefresher {
+    _kuzzle: Kuzzle,
+    _margin: Duration,
+    _expires_in: Option<Duration>,
+    _on_token_expired: Option<TokenExpiredListener>,
+}
+
+impl TokenRefresher {
+    /// Returns a refresher renewing the token of `kuzzle`, and of its clones,
+    /// one minute before it expires.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::token_refresher::TokenRefresher;
+    /// use kuzzle_sdk::types::{KuzzleOptions, QueryOptions};
+    ///
+    /// let kuzzle = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+    /// kuzzle.connect().unwrap();
+    /// kuzzle
+    ///     .auth()
+    ///     .login("local", "admin", "password", QueryOptions::new())
+    ///     .unwrap();
+    ///
+    /// let refresher = TokenRefresher::new(kuzzle.clone())
+    ///     .set_margin(30000)
+    ///     .on_token_expired(|err| eprintln!("Unable to renew the token: {}", err))
+    ///     .start();
+    ///
+    /// // ...
+    ///
+    /// refresher.stop();
+    /// ```
+    pub fn new(kuzzle: Kuzzle) -> TokenRefresher {
+        TokenRefresher {
+            _kuzzle: kuzzle,
+            _margin: DEFAULT_MARGIN,
+            _expires_in: None,
+            _on_token_expired: None,
+        }
+    }
+
+    /// Sets how long before its expiration the token is renewed, in milliseconds.
+    pub fn set_margin(mut self, margin: u64) -> Self {
+        self._margin = Duration::from_millis(margin);
+        self
+    }
+
+    /// Sets the lifespan of renewed tokens, in milliseconds.
+    /// The server default is used if it is not set.
+    pub fn set_expires_in(mut self, expires_in: u64) -> Self {
+        self._expires_in = Some(Duration::from_millis(expires_in));
+        self
+    }
+
+    /// Sets the function called with the error returned by Kuzzle when
+    /// the token can't be renewed. It is called once per session.
+    ///
+    /// Renewals failing with an authentication error are not retried. Other errors,
+    /// such as network errors, are retried with an exponential backoff until the
+    /// token expires.
+    pub fn on_token_expired<F>(mut self, listener: F) -> Self
+    where
+        F: Fn(&Error) + Send + Sync + 'static,
+    {
+        self._on_token_expired = Some(Box::new(listener));
+        self
+    }
+
+    /// Starts renewing the token from a background thread,
+    /// until the returned handle is stopped or dropped.
+    pub fn start(self) -> TokenRefresherHandle {
+        let (stop, stopped) = mpsc::channel();
+        let thread = thread::spawn(move || self.run(stopped));
+
+        TokenRefresherHandle {
+            _stop: stop,
+            _thread: Some(thread),
+        }
+    }
+
+    fn run(self, stopped: mpsc::Receiver<()>) {
+        let mut renewal = Renewal::default();
+
+        loop {
+            if let Some(expires_at) = renewal.due(self._kuzzle.session(), self._margin) {
+                let res = self
+                    ._kuzzle
+                    .auth()
+                    .refresh_token(self._expires_in, QueryOptions::new());
+
+                match res {
+                    Ok(_) => renewal.succeeded(),
+                    Err(err) => {
+                        if renewal.failed(expires_at, &err) {
+                            if let Some(listener) = &self._on_token_expired {
+                                listener(&err);
+                            }
+                        }
+                    }
+                }
+            }
+
+            match stopped.recv_timeout(next_check(self._kuzzle.session(), self._margin)) {
+                Err(RecvTimeoutError::Timeout) => continue,
+                _ => return,
+            }
+        }
+    }
+}
+
+/// Stops the token renewal when dropped.
+pub struct TokenRefresherHandle {
+    _stop: mpsc::Sender<()>,
+    _thread: Option<JoinHandle<()>>,
+}
+
+impl TokenRefresherHandle {
+    /// Stops the token renewal, waiting for an ongoing renewal to complete.
+    pub fn stop(mut self) {
+        let _ = self._stop.send(());
+        if let Some(thread) = self._thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// Failed renewals of the session token: transient errors are retried with
+/// an exponential backoff until the token expires, authentication errors are not.
+#[derive(Default)]
+pub(crate) struct Renewal {
+    /// Expiration of the last token which could not be renewed
+    given_up: Option<DateTime<Utc>>,
+    /// Expiration of the token being retried, when to retry it and the last delay used
+    retry: Option<(DateTime<Utc>, DateTime<Utc>, Duration)>,
+}
+
+impl Renewal {
+    /// Returns the expiration of the session token if it must be renewed now.
+    pub(crate) fn due(&self, session: Option<Session>, margin: Duration) -> Option<DateTime<Utc>> {
+        let expires_at = expiring_token(session, margin, self.given_up)?;
+
+        match self.retry {
+            Some((retried, retry_at, _)) if retried == expires_at && retry_at > Utc::now() => None,
+            _ => Some(expires_at),
+        }
+    }
+
+    pub(crate) fn succeeded(&mut self) {
+        self.retry = None;
+    }
+
+    /// Schedules a retry of the renewal of the token expiring at `expires_at`.
+    /// Returns true if the renewal is given up instead.
+    pub(crate) fn failed(&mut self, expires_at: DateTime<Utc>, err: &Error) -> bool {
+        let delay = match self.retry {
+            Some((retried, _, delay)) if retried == expires_at => (delay * 2).min(MAX_RETRY_DELAY),
+            _ => RETRY_DELAY,
+        };
+        let retry_at = Utc::now() + to_chrono(delay);
+
+        if is_auth_error(err) || retry_at >= expires_at {
+            self.given_up = Some(expires_at);
+            self.retry = None;
+            return true;
+        }
+
+        self.retry = Some((expires_at, retry_at, delay));
+        false
+    }
+}
+
+/// Returns true if retrying the renewal can't succeed: the token is invalid
+/// or the user is not allowed to renew it.
+fn is_auth_error(err: &Error) -> bool {
+    match err {
+        Error::Unauthorized(_) => true,
+        _ => err.kuzzle_error().and_then(|err| err.status()) == Some(403),
+    }
+}
+
+/// Returns the expiration of the session token if it must be renewed now.
+/// A token which could not be renewed (`failed`) is not renewed again.
+pub(crate) fn expiring_token(
+    session: Option<Session>,
+    margin: Duration,
+    failed: Option<DateTime<Utc>>,
+) -> Option<DateTime<Utc>> {
+    let expires_at = session?.expires_at()?;
+
+    if Some(expires_at) == failed || refresh_at(expires_at, margin) > Utc::now() {
+        return None;
+    }
+
+    Some(expires_at)
+}
+
+/// Returns how long to wait before checking the session again.
+pub(crate) fn next_check(session: Option<Session>, margin: Duration) -> Duration {
+    session
+        .and_then(|session| session.expires_at())
+        .and_then(|expires_at| (refresh_at(expires_at, margin) - Utc::now()).to_std().ok())
+        .map_or(SESSION_POLL_INTERVAL, |delay| {
+            delay.min(SESSION_POLL_INTERVAL)
+        })
+}
+
+fn refresh_at(expires_at: DateTime<Utc>, margin: Duration) -> DateTime<Utc> {
+    expires_at - to_chrono(margin)
+}
+
+fn to_chrono(duration: Duration) -> chrono::Duration {
+    chrono::Duration::from_std(duration).unwrap_or_else(|_| chrono::Duration::zero())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocols::{Http, Mock};
+    use crate::types::{KuzzleError, KuzzleOptions};
+    use mockito;
+    use serde_json::json;
+    use std::time::Instant;
+
+    fn session(expires_in: chrono::Duration) -> Session {
+        serde_json::from_value(json!({
+            "_id": "user-kuid",
+            "jwt": "expiringJwtToken",
+            "expiresAt": (Utc::now() + expires_in).timestamp_millis()
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn expiring_token_ok() {
+        let margin = Duration::from_secs(60);
+        let expiring = session(chrono::Duration::seconds(30));
+        let expires_at = expiring.expires_at();
+
+        assert_eq!(
+            expiring_token(Some(expiring.clone()), margin, None),
+            expires_at
+        );
+        assert_eq!(expiring_token(Some(expiring), margin, expires_at), None);
+        assert_eq!(
+            expiring_token(Some(session(chrono::Duration::hours(1))), margin, None),
+            None
+        );
+        assert_eq!(
+            expiring_token(Some(Session::new("aSuperJwtToken")), margin, None),
+            None
+        );
+        assert_eq!(expiring_token(None, margin, None), None);
+    }
+
+    #[test]
+    fn renewal_ok_retry() {
+        let margin = Duration::from_secs(60);
+        let expiring = session(chrono::Duration::seconds(30));
+        let expires_at = expiring.expires_at().unwrap();
+        let mut renewal = Renewal::default();
+
+        assert_eq!(
+            renewal.due(Some(expiring.clone()), margin),
+            Some(expires_at)
+        );
+        assert!(!renewal.failed(expires_at, &Error::Network("Connection refused".into())));
+        assert_eq!(renewal.due(Some(expiring.clone()), margin), None);
+        assert_eq!(renewal.retry.map(|(_, _, delay)| delay), Some(RETRY_DELAY));
+
+        // The delay doubles with each failure
+        assert!(!renewal.failed(expires_at, &Error::Timeout));
+        assert_eq!(
+            renewal.retry.map(|(_, _, delay)| delay),
+            Some(RETRY_DELAY * 2)
+        );
+
+        renewal.retry = Some((expires_at, Utc::now(), RETRY_DELAY));
+        assert_eq!(renewal.due(Some(expiring), margin), Some(expires_at));
+        renewal.succeeded();
+        assert!(renewal.retry.is_none());
+    }
+
+    #[test]
+    fn renewal_fail_given_up() {
+        let margin = Duration::from_secs(60);
+        let expiring = session(chrono::Duration::seconds(30));
+        let expires_at = expiring.expires_at().unwrap();
+        let unauthorized = Error::from(KuzzleError::new(Some(401), "Token expired"));
+
+        let mut renewal = Renewal::default();
+        assert!(renewal.failed(expires_at, &unauthorized));
+        assert_eq!(renewal.due(Some(expiring), margin), None);
+
+        // Transient errors are no longer retried once the token is about to expire
+        let expiring = session(chrono::Duration::milliseconds(500));
+        let expires_at = expiring.expires_at().unwrap();
+        let mut renewal = Renewal::default();
+        assert!(renewal.failed(expires_at, &Error::Timeout));
+        assert_eq!(renewal.due(Some(expiring), margin), None);
+    }
+
+    #[test]
+    fn start_ok_retry() {
+        let mock = Mock::new();
+        mock.when("auth", "refreshToken").respond(json!({
+            "_id": "user-kuid",
+            "jwt": "renewedJwtToken",
+            "expiresAt": 4102444800000u64,
+            "ttl": 3600000
+        }));
+        mock.when("auth", "refreshToken")
+            .once()
+            .fail(|| Error::Network("Connection refused".into()));
+
+        let k = Kuzzle::new(mock.clone());
+        k.connect().unwrap();
+        k.set_session(Some(session(chrono::Duration::seconds(30))));
+
+        let refresher = TokenRefresher::new(k.clone()).start();
+
+        let start = Instant::now();
+        while k.jwt() != "renewedJwtToken" && start.elapsed() < Duration::from_secs(5) {
+            thread::sleep(Duration::from_millis(10));
+        }
+        refresher.stop();
+
+        assert_eq!(k.jwt(), "renewedJwtToken");
+        assert_eq!(mock.calls_to("auth", "refreshToken").len(), 2);
+    }
+
+    #[test]
+    fn next_check_ok() {
+        let margin = Duration::from_secs(60);
+
+        assert_eq!(next_check(None, margin), SESSION_POLL_INTERVAL);
+        assert!(
+            next_check(Some(session(chrono::Duration::milliseconds(60500))), margin)
+                <= Duration::from_millis(500)
+        );
+    }
+
+    #[test]
+    fn start_ok() {
+        let _m = mockito::mock("POST", "/_refreshToken?expiresIn=3600000")
+            .match_header("authorization", "Bearer expiringJwtToken")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "auth",
+                    "action": "refreshToken",
+                    "volatile": {},
+                    "result": {
+                      "_id": "user-kuid",
+                      "jwt": "renewedJwtToken",
+                      "expiresAt": 4102444800000,
+                      "ttl": 3600000
+                    }
+                }"#,
+            )
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        k.connect().expect("Unable to connect to Kuzzle server");
+        k.set_session(Some(session(chrono::Duration::seconds(30))));
+
+        let refresher = TokenRefresher::new(k.clone())
+            .set_expires_in(3600000)
+            .start();
+
+        let start = Instant::now();
+        while k.jwt() != "renewedJwtToken" && start.elapsed() < Duration::from_secs(5) {
+            thread::sleep(Duration::from_millis(10));
+        }
+        refresher.stop();
+
+        assert_eq!(k.jwt(), "renewedJwtToken");
+    }
+
+    #[test]
+    fn start_fail_token_expired() {
+        let _m = mockito::mock("POST", "/_refreshToken")
+            .with_status(401)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 401,
+                    "error": {
+                        "message": "Token expired",
+                        "status": 401
+                    },
+                    "controller": "auth",
+                    "action": "refreshToken",
+                    "volatile": {},
+                    "result": null
+                }"#,
+            )
+            .expect(1)
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        k.connect().expect("Unable to connect to Kuzzle server");
+        k.set_session(Some(session(chrono::Duration::seconds(30))));
+
+        let (expired, on_expired) = mpsc::channel();
+        let refresher = TokenRefresher::new(k.clone())
+            .on_token_expired(move |err| {
+                let _ = expired.send(err.to_string());
+            })
+            .start();
+
+        let err = on_expired.recv_timeout(Duration::from_secs(5));
+        // Let the refresher check the session again
+        thread::sleep(Duration::from_millis(1500));
+        refresher.stop();
+
+        assert!(err.unwrap().contains("Token expired"));
+        assert!(on_expired.try_recv().is_err());
+        _m.assert();
+    }
+}
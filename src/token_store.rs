@@ -0,0 +1,373 @@
+//! Persistence of the authentication session.
+//!
+//! A `TokenStore` keeps the session of a `Kuzzle` client created with
+//! `Kuzzle::with_token_store` up to date, so that it can be restored with
+//! `Kuzzle::restore_session` the next time the program starts instead of
+//! asking the user to log in again.
+
+use crate::types::{Error, Session};
+
+use chrono::Utc;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, PoisonError};
+
+/// Storage of the authentication session of a `Kuzzle` client.
+pub trait TokenStore: Send + Sync {
+    /// Returns the stored session, if any.
+    fn load(&self) -> Result<Option<Session>, Error>;
+
+    /// Stores `session`, or forgets the stored one if `None`.
+    fn save(&self, session: Option<&Session>) -> Result<(), Error>;
+}
+
+impl<S: TokenStore + ?Sized> TokenStore for Arc<S> {
+    fn load(&self) -> Result<Option<Session>, Error> {
+        (**self).load()
+    }
+
+    fn save(&self, session: Option<&Session>) -> Result<(), Error> {
+        (**self).save(session)
+    }
+}
+
+/// Keeps the session in memory: it is lost when the program exits.
+#[derive(Default)]
+pub struct MemoryTokenStore {
+    _session: Mutex<Option<Session>>,
+}
+
+impl MemoryTokenStore {
+    pub fn new() -> MemoryTokenStore {
+        MemoryTokenStore::default()
+    }
+}
+
+impl TokenStore for MemoryTokenStore {
+    fn load(&self) -> Result<Option<Session>, Error> {
+        Ok(self
+            ._session
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone())
+    }
+
+    fn save(&self, session: Option<&Session>) -> Result<(), Error> {
+        *self._session.lock().unwrap_or_else(PoisonError::into_inner) = session.cloned();
+        Ok(())
+    }
+}
+
+/// Keeps the session in a JSON file.
+///
+/// On Unix, the file is only readable and writable by its owner,
+/// as anyone able to read it can use the token.
+pub struct FileTokenStore {
+    _path: PathBuf,
+}
+
+impl FileTokenStore {
+    /// Returns a store writing the session to `path`.
+    /// Missing parent directories are created on the first save.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use kuzzle_sdk::kuzzle::Kuzzle;
+    /// use kuzzle_sdk::protocols::Http;
+    /// use kuzzle_sdk::token_store::FileTokenStore;
+    /// use kuzzle_sdk::types::{KuzzleOptions, QueryOptions};
+    ///
+    /// let kuzzle = Kuzzle::with_token_store(
+    ///     Http::new(KuzzleOptions::new("localhost", 7512)),
+    ///     FileTokenStore::new("/home/ferris/.config/crab-cli/session.json"),
+    /// );
+    /// kuzzle.connect().unwrap();
+    ///
+    /// if !kuzzle.restore_session(QueryOptions::new()).unwrap() {
+    ///     kuzzle
+    ///         .auth()
+    ///         .login("local", "ferris", "password", QueryOptions::new())
+    ///         .unwrap();
+    /// }
+    /// ```
+    pub fn new<P: Into<PathBuf>>(path: P) -> FileTokenStore {
+        FileTokenStore { _path: path.into() }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self._path
+    }
+
+    fn write(&self, session: &Session) -> Result<(), Error> {
+        if let Some(parent) = self._path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        // Written next to the store then renamed, so that a crash never leaves a truncated session
+        let tmp_path = self._path.with_extension("tmp");
+        let mut file = private_file(&tmp_path)?;
+        file.write_all(&serde_json::to_vec(session)?)?;
+        file.sync_all()?;
+
+        fs::rename(&tmp_path, &self._path)?;
+        Ok(())
+    }
+}
+
+impl TokenStore for FileTokenStore {
+    fn load(&self) -> Result<Option<Session>, Error> {
+        match fs::read(&self._path) {
+            Ok(content) => Ok(Some(serde_json::from_slice(&content)?)),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(Error::from(err)),
+        }
+    }
+
+    fn save(&self, session: Option<&Session>) -> Result<(), Error> {
+        match session {
+            Some(session) => self.write(session),
+            None => match fs::remove_file(&self._path) {
+                Err(err) if err.kind() != io::ErrorKind::NotFound => Err(Error::from(err)),
+                _ => Ok(()),
+            },
+        }
+    }
+}
+
+/// Opens `path` for writing, readable and writable by its owner only.
+#[cfg(unix)]
+fn private_file(path: &Path) -> io::Result<fs::File> {
+    use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+
+    let file = OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .mode(0o600)
+        .open(path)?;
+    // The mode is only applied to new files
+    file.set_permissions(fs::Permissions::from_mode(0o600))?;
+    Ok(file)
+}
+
+#[cfg(not(unix))]
+fn private_file(path: &Path) -> io::Result<fs::File> {
+    OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(path)
+}
+
+/// Returns the stored session, unless it has expired.
+/// Expired sessions are removed from the store.
+pub(crate) fn stored_session(store: &dyn TokenStore) -> Result<Option<Session>, Error> {
+    match store.load()? {
+        Some(session) if session.is_expired(Utc::now()) => {
+            store.save(None)?;
+            Ok(None)
+        }
+        session => Ok(session),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kuzzle::Kuzzle;
+    use crate::protocols::Http;
+    use crate::types::{KuzzleOptions, QueryOptions};
+    use mockito;
+    use serde_json::json;
+    use std::env;
+    use std::process;
+
+    fn session(expires_in: chrono::Duration) -> Session {
+        serde_json::from_value(json!({
+            "_id": "user-kuid",
+            "jwt": "storedJwtToken",
+            "expiresAt": (Utc::now() + expires_in).timestamp_millis()
+        }))
+        .unwrap()
+    }
+
+    fn store_path(name: &str) -> PathBuf {
+        env::temp_dir()
+            .join(format!("kuzzle_sdk_{}", process::id()))
+            .join(name)
+    }
+
+    #[test]
+    fn memory_store_ok() {
+        let store = MemoryTokenStore::new();
+        let stored = session(chrono::Duration::hours(1));
+
+        assert_eq!(store.load().unwrap(), None);
+        store.save(Some(&stored)).unwrap();
+        assert_eq!(store.load().unwrap(), Some(stored));
+        store.save(None).unwrap();
+        assert_eq!(store.load().unwrap(), None);
+    }
+
+    #[test]
+    fn file_store_ok() {
+        let store = FileTokenStore::new(store_path("file_store_ok/session.json"));
+        let stored = session(chrono::Duration::hours(1));
+
+        assert_eq!(store.load().unwrap(), None);
+        store.save(Some(&stored)).unwrap();
+        assert_eq!(store.load().unwrap(), Some(stored));
+        store.save(None).unwrap();
+        assert_eq!(store.load().unwrap(), None);
+        assert!(!store.path().exists());
+        store.save(None).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn file_store_ok_private() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let path = store_path("file_store_ok_private.json");
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, "{}").unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).unwrap();
+
+        let store = FileTokenStore::new(&path);
+        store
+            .save(Some(&session(chrono::Duration::hours(1))))
+            .unwrap();
+        let mode = fs::metadata(&path).unwrap().permissions().mode();
+        store.save(None).unwrap();
+
+        assert_eq!(mode & 0o777, 0o600);
+    }
+
+    #[test]
+    fn file_store_fail_corrupted() {
+        let path = store_path("file_store_fail_corrupted.json");
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, "not a session").unwrap();
+
+        let res = FileTokenStore::new(&path).load();
+        fs::remove_file(&path).unwrap();
+
+        assert!(matches!(res, Err(Error::Serialization(_))));
+    }
+
+    #[test]
+    fn stored_session_ok_expired() {
+        let store = MemoryTokenStore::new();
+        store
+            .save(Some(&session(chrono::Duration::seconds(-1))))
+            .unwrap();
+
+        assert_eq!(stored_session(&store).unwrap(), None);
+        assert_eq!(store.load().unwrap(), None);
+    }
+
+    #[test]
+    fn restore_session_ok() {
+        let _m = mockito::mock("POST", "/_checkToken")
+            .match_body(r#"{"token":"storedJwtToken"}"#)
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "auth",
+                    "action": "checkToken",
+                    "volatile": {},
+                    "result": {
+                      "valid": true,
+                      "expiresAt": 4102444800000
+                    }
+                }"#,
+            )
+            .create();
+
+        let store = Arc::new(MemoryTokenStore::new());
+        let stored = session(chrono::Duration::hours(1));
+        store.save(Some(&stored)).unwrap();
+
+        let k = Kuzzle::with_token_store(
+            Http::new(KuzzleOptions::new("localhost", 7512)),
+            store.clone(),
+        );
+        k.connect().expect("Unable to connect to Kuzzle server");
+        let res = k.restore_session(QueryOptions::new());
+
+        assert!(res.unwrap());
+        assert_eq!(k.session(), Some(stored));
+
+        k.set_jwt("aNewJwtToken".to_string());
+        assert_eq!(store.load().unwrap().unwrap().jwt(), "aNewJwtToken");
+    }
+
+    #[test]
+    fn restore_session_ok_invalid_token() {
+        let _m = mockito::mock("POST", "/_checkToken")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "auth",
+                    "action": "checkToken",
+                    "volatile": {},
+                    "result": {
+                      "valid": false,
+                      "state": "Invalid token"
+                    }
+                }"#,
+            )
+            .create();
+
+        let store = Arc::new(MemoryTokenStore::new());
+        store
+            .save(Some(&session(chrono::Duration::hours(1))))
+            .unwrap();
+
+        let k = Kuzzle::with_token_store(
+            Http::new(KuzzleOptions::new("localhost", 7512)),
+            store.clone(),
+        );
+        k.connect().expect("Unable to connect to Kuzzle server");
+        let res = k.restore_session(QueryOptions::new());
+
+        assert!(!res.unwrap());
+        assert_eq!(k.session(), None);
+        assert_eq!(store.load().unwrap(), None);
+    }
+
+    #[test]
+    fn api_key_ok_not_stored() {
+        let store = Arc::new(MemoryTokenStore::new());
+        let before = Kuzzle::builder(Http::new(KuzzleOptions::new("localhost", 7512)))
+            .token_store(store.clone())
+            .api_key("ferris-api-key")
+            .build();
+        let after = Kuzzle::builder(Http::new(KuzzleOptions::new("localhost", 7512)))
+            .api_key("ferris-api-key")
+            .token_store(store.clone())
+            .build();
+
+        assert_eq!(before.jwt(), "ferris-api-key");
+        assert_eq!(after.jwt(), "ferris-api-key");
+        assert_eq!(store.load().unwrap(), None);
+    }
+
+    #[test]
+    fn restore_session_ok_no_store() {
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        k.connect().expect("Unable to connect to Kuzzle server");
+
+        assert!(!k.restore_session(QueryOptions::new()).unwrap());
+    }
+}
@@ -0,0 +1,678 @@
+use crate::kuzzle::Kuzzle;
+use crate::transfer::{Chunk, ImportReport, TransferOptions};
+use crate::types::{Error, QueryOptions, SdkError};
+use chrono::{DateTime, NaiveDate, NaiveDateTime};
+use csv::StringRecord;
+use serde_json::{json, Map, Value};
+use std::collections::{HashMap, HashSet};
+use std::io::{self, Read};
+
+/// Describes how CSV columns are turned into document fields.
+///
+/// By default, each column is imported in the field named after its header.
+/// A dotted field name (e.g. `address.city`) produces nested objects.
+#[derive(Debug)]
+pub struct CsvOptions {
+    _delimiter: u8,
+    _id_column: Option<String>,
+    _fields: HashMap<String, String>,
+    _ignored_columns: HashSet<String>,
+    _geo_points: Vec<(String, String, String)>,
+}
+
+impl Default for CsvOptions {
+    fn default() -> CsvOptions {
+        CsvOptions {
+            _delimiter: b',',
+            _id_column: None,
+            _fields: HashMap::new(),
+            _ignored_columns: HashSet::new(),
+            _geo_points: Vec::new(),
+        }
+    }
+}
+
+impl CsvOptions {
+    /// Returns a CsvOptions struct with default values.
+    /// When created this struct follow the builder pattern and permits
+    /// on the fly updates.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::transfer::CsvOptions;
+    /// let options = CsvOptions::new()
+    ///     .set_delimiter(b';')
+    ///     .set_id_column("reference")
+    ///     .map_column("Town", "address.city")
+    ///     .ignore_column("comment")
+    ///     .add_geo_point("location", "latitude", "longitude");
+    /// ```
+    pub fn new() -> CsvOptions {
+        Self::default()
+    }
+
+    pub fn delimiter(&self) -> &u8 {
+        &self._delimiter
+    }
+
+    pub fn id_column(&self) -> String {
+        match &self._id_column {
+            Some(column) => column.clone(),
+            None => String::new(),
+        }
+    }
+
+    pub fn set_delimiter(mut self, delimiter: u8) -> Self {
+        self._delimiter = delimiter;
+        self
+    }
+
+    /// Uses the given column as document identifier instead of a document field.
+    pub fn set_id_column(mut self, column: &str) -> Self {
+        self._id_column = Some(String::from(column));
+        self
+    }
+
+    /// Imports the given column in `field` instead of the field named after the column.
+    pub fn map_column(mut self, column: &str, field: &str) -> Self {
+        self._fields
+            .insert(String::from(column), String::from(field));
+        self
+    }
+
+    /// Leaves the given column out of imported documents.
+    pub fn ignore_column(mut self, column: &str) -> Self {
+        self._ignored_columns.insert(String::from(column));
+        self
+    }
+
+    /// Builds the geo_point `field` out of a latitude and a longitude columns.
+    /// Both columns are left out of imported documents.
+    pub fn add_geo_point(mut self, field: &str, lat_column: &str, lon_column: &str) -> Self {
+        self._geo_points.push((
+            String::from(field),
+            String::from(lat_column),
+            String::from(lon_column),
+        ));
+        self
+    }
+
+    fn field(&self, column: &str) -> Option<String> {
+        if self._ignored_columns.contains(column) || self.id_column() == column {
+            return None;
+        }
+
+        if self
+            ._geo_points
+            .iter()
+            .any(|(_, lat, lon)| lat == column || lon == column)
+        {
+            return None;
+        }
+
+        match self._fields.get(column) {
+            Some(field) => Some(field.clone()),
+            None => Some(String::from(column)),
+        }
+    }
+}
+
+/// Reads CSV rows from `reader` and writes them to a collection by chunks,
+/// using `bulk:import`.
+///
+/// Cell values are coerced according to the collection mapping, fetched with
+/// `collection:getMapping`: numbers, booleans, dates and geo_points are converted,
+/// other values are kept as strings and empty cells are left out.
+/// Malformed rows, rows that can't be coerced and documents rejected by Kuzzle
+/// are skipped and listed in the returned report.
+///
+/// The `offset` transfer option is a number of data rows to skip, header excluded.
+/// Files with duplicate column names, or with columns imported into the same field
+/// or into a field and one of its subfields, are rejected before any row is read.
+///
+/// # Example
+///
+/// ```
+/// use kuzzle_sdk::kuzzle::Kuzzle;
+/// use kuzzle_sdk::protocols::Http;
+/// use kuzzle_sdk::transfer::{self, CsvOptions, TransferOptions};
+/// use kuzzle_sdk::types::KuzzleOptions;
+///
+/// let kuzzle = Kuzzle::new(
+///     Http::new(
+///         KuzzleOptions::new("localhost", 7512)
+///     )
+/// );
+///
+/// let csv = "id,name,age,lat,lon\nferris,Ferris,4,43.6,1.44\n";
+/// let res = transfer::import_csv(
+///     &kuzzle,
+///     "ferris_index",
+///     "ferris_collection",
+///     csv.as_bytes(),
+///     CsvOptions::new()
+///         .set_id_column("id")
+///         .add_geo_point("location", "lat", "lon"),
+///     TransferOptions::new(),
+/// );
+///
+/// ```
+///
+pub fn import_csv<R: Read>(
+    kuzzle: &Kuzzle,
+    index: &str,
+    collection: &str,
+    reader: R,
+    csv_options: CsvOptions,
+    mut options: TransferOptions,
+) -> Result<ImportReport, Error> {
+    let chunk_size = *options.chunk_size();
+    if chunk_size == 0 {
+        return Err(Error::InvalidArgument(SdkError::new(
+            "transfer::import_csv",
+            "chunk_size option must be greater than 0.",
+        )));
+    }
+
+    let mut csv_reader = ::csv::ReaderBuilder::new()
+        .delimiter(*csv_options.delimiter())
+        .flexible(true)
+        .from_reader(reader);
+    let headers = csv_reader.headers().map_err(io::Error::from)?.clone();
+    check_columns(&headers, &csv_options)
+        .map_err(|reason| Error::InvalidArgument(SdkError::new("transfer::import_csv", &reason)))?;
+
+    let mapping = kuzzle
+        .collection()
+        .get_mapping(index, collection, QueryOptions::new())?;
+
+    let offset = *options.offset();
+    let mut position = 0;
+    let mut report = ImportReport::default();
+    let mut chunk = Chunk::default();
+
+    for record in csv_reader.records() {
+        position += 1;
+        if position <= offset {
+            continue;
+        }
+
+        let record = match record {
+            Ok(record) => record,
+            Err(err) if err.is_io_error() => return Err(io::Error::from(err).into()),
+            Err(err) => {
+                let line = err.position().map(|p| p.line()).unwrap_or(0);
+                report.reject(line, err.to_string());
+                continue;
+            }
+        };
+        let line = record.position().map(|p| p.line()).unwrap_or(0);
+        if record.len() != headers.len() {
+            report.reject(
+                line,
+                format!("expected {} fields, found {}", headers.len(), record.len()),
+            );
+            continue;
+        }
+
+        match to_document(&headers, &record, &csv_options, &mapping) {
+            Ok((id, document)) => chunk.push(line, id, document),
+            Err(reason) => report.reject(line, reason),
+        }
+
+        if chunk.len() == chunk_size {
+            chunk.import(kuzzle, index, collection, &mut report)?;
+            options.notify_progress(position);
+        }
+    }
+
+    if !chunk.is_empty() {
+        chunk.import(kuzzle, index, collection, &mut report)?;
+        options.notify_progress(position);
+    }
+
+    Ok(report)
+}
+
+/// Checks that column names are unique, and that no two columns are imported
+/// into the same field, or into a field and one of its subfields.
+fn check_columns(headers: &StringRecord, csv_options: &CsvOptions) -> Result<(), String> {
+    let mut columns = HashSet::new();
+    if let Some(column) = headers.iter().find(|column| !columns.insert(*column)) {
+        return Err(format!("duplicate column \"{}\"", column));
+    }
+
+    let fields: Vec<String> = headers
+        .iter()
+        .filter_map(|column| csv_options.field(column))
+        .chain(
+            csv_options
+                ._geo_points
+                .iter()
+                .map(|(field, _, _)| field.clone()),
+        )
+        .collect();
+    for (position, field) in fields.iter().enumerate() {
+        for other in &fields[position + 1..] {
+            let (shorter, longer) = if field.len() <= other.len() {
+                (field, other)
+            } else {
+                (other, field)
+            };
+            if longer == shorter || longer.starts_with(&format!("{}.", shorter)) {
+                return Err(format!("fields \"{}\" and \"{}\" conflict", field, other));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the cell of `column` in `record`, if the file has this column.
+fn cell<'a>(headers: &StringRecord, record: &'a StringRecord, column: &str) -> Option<&'a str> {
+    let position = headers.iter().position(|header| header == column)?;
+    record.get(position)
+}
+
+/// Builds the document of a row, with its fields in column order.
+fn to_document(
+    headers: &StringRecord,
+    record: &StringRecord,
+    csv_options: &CsvOptions,
+    mapping: &Map<String, Value>,
+) -> Result<(Option<String>, Map<String, Value>), String> {
+    let mut document = Map::new();
+
+    for (column, cell) in headers.iter().zip(record.iter()) {
+        let field = match csv_options.field(column) {
+            Some(field) => field,
+            None => continue,
+        };
+
+        if cell.is_empty() {
+            continue;
+        }
+
+        let value = match field_type(mapping, &field) {
+            Some(field_type) => coerce(cell, &field_type)
+                .map_err(|reason| format!("column \"{}\": {}", column, reason))?,
+            None => Value::String(cell.to_string()),
+        };
+        insert_field(&mut document, &field, value);
+    }
+
+    for (field, lat_column, lon_column) in &csv_options._geo_points {
+        let lat = cell(headers, record, lat_column).unwrap_or("");
+        let lon = cell(headers, record, lon_column).unwrap_or("");
+        if lat.is_empty() && lon.is_empty() {
+            continue;
+        }
+
+        match (lat.trim().parse::<f64>(), lon.trim().parse::<f64>()) {
+            (Ok(lat), Ok(lon)) => {
+                insert_field(&mut document, field, json!({ "lat": lat, "lon": lon }))
+            }
+            _ => {
+                return Err(format!(
+                    "columns \"{}\" and \"{}\": invalid geo_point coordinates \"{}\", \"{}\"",
+                    lat_column, lon_column, lat, lon
+                ))
+            }
+        }
+    }
+
+    let id = match cell(headers, record, &csv_options.id_column()) {
+        Some(id) if !id.is_empty() => Some(id.to_string()),
+        _ => None,
+    };
+
+    Ok((id, document))
+}
+
+/// Returns the mapping type of a (possibly dotted) field, if the mapping defines it.
+fn field_type(mapping: &Map<String, Value>, field: &str) -> Option<String> {
+    let mut properties = mapping.get("properties")?;
+    let mut definition = None;
+
+    for name in field.split('.') {
+        let current = properties.get(name)?;
+        definition = Some(current);
+        properties = match current.get("properties") {
+            Some(nested) => nested,
+            None => &Value::Null,
+        };
+    }
+
+    definition?
+        .get("type")
+        .and_then(|t| t.as_str())
+        .map(String::from)
+}
+
+/// Converts a CSV cell to the JSON value expected by an Elasticsearch field type.
+fn coerce(cell: &str, field_type: &str) -> Result<Value, String> {
+    let value = cell.trim();
+
+    match field_type {
+        "long" | "integer" | "short" | "byte" => value
+            .parse::<i64>()
+            .map(Value::from)
+            .map_err(|_| format!("\"{}\" is not a valid {}", value, field_type)),
+        "double" | "float" | "half_float" | "scaled_float" => value
+            .parse::<f64>()
+            .map(Value::from)
+            .map_err(|_| format!("\"{}\" is not a valid {}", value, field_type)),
+        "boolean" => match value.to_lowercase().as_str() {
+            "true" | "yes" | "1" => Ok(Value::Bool(true)),
+            "false" | "no" | "0" => Ok(Value::Bool(false)),
+            _ => Err(format!("\"{}\" is not a valid boolean", value)),
+        },
+        "date" => coerce_date(value),
+        "geo_point" => {
+            let coordinates: Vec<&str> = value.split(',').map(|c| c.trim()).collect();
+            match coordinates.as_slice() {
+                [lat, lon] => match (lat.parse::<f64>(), lon.parse::<f64>()) {
+                    (Ok(lat), Ok(lon)) => Ok(json!({ "lat": lat, "lon": lon })),
+                    _ => Err(format!("\"{}\" is not a valid geo_point", value)),
+                },
+                _ => Err(format!("\"{}\" is not a valid geo_point", value)),
+            }
+        }
+        _ => Ok(Value::String(cell.to_string())),
+    }
+}
+
+/// Dates are accepted as Epoch millis, RFC 3339 strings, `YYYY-MM-DD` dates
+/// or `YYYY-MM-DD HH:MM:SS` datetimes (normalized to `YYYY-MM-DDTHH:MM:SS`).
+fn coerce_date(value: &str) -> Result<Value, String> {
+    if let Ok(millis) = value.parse::<i64>() {
+        return Ok(Value::from(millis));
+    }
+
+    if DateTime::parse_from_rfc3339(value).is_ok()
+        || NaiveDate::parse_from_str(value, "%Y-%m-%d").is_ok()
+    {
+        return Ok(Value::String(value.to_string()));
+    }
+
+    for format in &["%Y-%m-%dT%H:%M:%S", "%Y-%m-%d %H:%M:%S"] {
+        if let Ok(datetime) = NaiveDateTime::parse_from_str(value, format) {
+            return Ok(Value::String(
+                datetime.format("%Y-%m-%dT%H:%M:%S").to_string(),
+            ));
+        }
+    }
+
+    Err(format!("\"{}\" is not a valid date", value))
+}
+
+fn insert_field(document: &mut Map<String, Value>, field: &str, value: Value) {
+    let mut names: Vec<&str> = field.split('.').collect();
+    let last = names.pop().unwrap();
+    let mut current = document;
+
+    for name in names {
+        let entry = current
+            .entry(name.to_string())
+            .or_insert_with(|| Value::Object(Map::new()));
+        if !entry.is_object() {
+            *entry = Value::Object(Map::new());
+        }
+        current = entry.as_object_mut().unwrap();
+    }
+
+    current.insert(last.to_string(), value);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocols::{Http, Mock};
+    use crate::types::KuzzleOptions;
+    use mockito;
+
+    fn mapping_mock() -> mockito::Mock {
+        mockito::mock("GET", "/ferris_index/ferris_collection/_mapping")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "collection",
+                    "action": "getMapping",
+                    "collection": "ferris_collection",
+                    "index": "ferris_index",
+                    "volatile": null,
+                    "result": {
+                        "properties": {
+                            "name": { "type": "keyword" },
+                            "age": { "type": "integer" },
+                            "crab": { "type": "boolean" },
+                            "birth": { "type": "date" },
+                            "location": { "type": "geo_point" },
+                            "address": {
+                                "properties": {
+                                    "zip": { "type": "integer" }
+                                }
+                            }
+                        }
+                    }
+                }"#,
+            )
+            .create()
+    }
+
+    #[test]
+    fn import_csv_ok() {
+        let _mapping = mapping_mock();
+        let bulk = mockito::mock("POST", "/ferris_index/ferris_collection/_bulk")
+            .match_body(mockito::Matcher::Json(json!({
+                "bulkData": [
+                    { "index": { "_id": "ferris" } },
+                    {
+                        "name": "Ferris",
+                        "age": 4,
+                        "crab": true,
+                        "birth": "2015-05-15T10:00:00",
+                        "location": { "lat": 43.6, "lon": 1.44 },
+                        "address": { "zip": 31000 }
+                    },
+                    { "index": {} },
+                    { "name": "Corro", "crab": false }
+                ]
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "bulk",
+                    "action": "import",
+                    "collection": "ferris_collection",
+                    "index": "ferris_index",
+                    "volatile": null,
+                    "result": {
+                        "successes": [
+                            { "_id": "ferris", "_source": { "name": "Ferris" }, "status": 201 }
+                        ],
+                        "errors": [
+                            {
+                                "document": { "body": { "name": "Corro", "crab": false } },
+                                "status": 400,
+                                "reason": "document rejected by a plugin"
+                            }
+                        ]
+                    }
+                }"#,
+            )
+            .expect(1)
+            .create();
+
+        let csv = "id;name;age;crab;birth;lat;lon;zip;comment\n\
+                   ferris;Ferris;4;yes;2015-05-15 10:00:00;43.6;1.44;31000;the best\n\
+                   crabby;Crabby;four;true;;;;;\n\
+                   truncated;Truncated\n\
+                   ;Corro;;0;;;;;\n";
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        k.connect().expect("Unable to connect to Kuzzle server");
+        let res = import_csv(
+            &k,
+            "ferris_index",
+            "ferris_collection",
+            csv.as_bytes(),
+            CsvOptions::new()
+                .set_delimiter(b';')
+                .set_id_column("id")
+                .map_column("zip", "address.zip")
+                .ignore_column("comment")
+                .add_geo_point("location", "lat", "lon"),
+            TransferOptions::new(),
+        );
+
+        assert!(res.is_ok());
+        let report = res.unwrap();
+        let rejected: Vec<(u64, &str)> = report
+            .rejected()
+            .iter()
+            .map(|row| (row.line(), row.reason().as_str()))
+            .collect();
+        assert_eq!(report.imported(), 1);
+        assert_eq!(
+            rejected,
+            vec![
+                (3, "column \"age\": \"four\" is not a valid integer"),
+                (4, "expected 9 fields, found 2"),
+                (5, "document rejected by a plugin"),
+            ]
+        );
+        bulk.assert();
+    }
+
+    #[test]
+    fn import_csv_fail_mapping_error() {
+        let _m = mockito::mock("GET", "/ferris_index/ferris_collection/_mapping")
+            .with_status(404)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 404,
+                    "error": {
+                        "message": "Collection \"ferris_index\":\"ferris_collection\" does not exist.",
+                        "status": 404,
+                        "stack": "NotFoundError: Collection \"ferris_index\":\"ferris_collection\" does not exist.\n"
+                    },
+                    "controller": "collection",
+                    "action": "getMapping",
+                    "collection": "ferris_collection",
+                    "index": "ferris_index",
+                    "volatile": null,
+                    "result": null
+                }"#,
+            )
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        k.connect().expect("Unable to connect to Kuzzle server");
+        let res = import_csv(
+            &k,
+            "ferris_index",
+            "ferris_collection",
+            "name\nFerris\n".as_bytes(),
+            CsvOptions::new(),
+            TransferOptions::new(),
+        );
+
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn import_csv_fail_duplicate_column() {
+        let mock = Mock::new();
+        let k = Kuzzle::new(mock.clone());
+        k.connect().unwrap();
+        let res = import_csv(
+            &k,
+            "ferris_index",
+            "ferris_collection",
+            "name,age,name\nFerris,4,Crabby\n".as_bytes(),
+            CsvOptions::new(),
+            TransferOptions::new(),
+        );
+
+        match res {
+            Err(Error::InvalidArgument(err)) => {
+                assert_eq!(err.message(), "duplicate column \"name\"")
+            }
+            _ => panic!("Expected an invalid argument error"),
+        }
+        assert!(mock.calls().is_empty());
+    }
+
+    #[test]
+    fn check_columns_ok() {
+        let headers = StringRecord::from(vec!["id", "name", "zip", "city", "lat", "lon"]);
+        let csv_options = CsvOptions::new()
+            .set_id_column("id")
+            .map_column("zip", "address.zip")
+            .map_column("city", "address.city")
+            .add_geo_point("location", "lat", "lon");
+
+        assert_eq!(check_columns(&headers, &csv_options), Ok(()));
+    }
+
+    #[test]
+    fn check_columns_fail_conflicting_fields() {
+        let headers = StringRecord::from(vec!["address", "zip", "lat", "lon"]);
+
+        assert_eq!(
+            check_columns(
+                &headers,
+                &CsvOptions::new().map_column("zip", "address.zip")
+            ),
+            Err("fields \"address\" and \"address.zip\" conflict".to_string())
+        );
+        assert_eq!(
+            check_columns(&headers, &CsvOptions::new().map_column("zip", "address")),
+            Err("fields \"address\" and \"address\" conflict".to_string())
+        );
+        assert_eq!(
+            check_columns(
+                &headers,
+                &CsvOptions::new().add_geo_point("zip", "lat", "lon")
+            ),
+            Err("fields \"zip\" and \"zip\" conflict".to_string())
+        );
+    }
+
+    #[test]
+    fn coerce_ok() {
+        assert_eq!(coerce("42", "long"), Ok(json!(42)));
+        assert_eq!(coerce(" 4.2 ", "float"), Ok(json!(4.2)));
+        assert_eq!(coerce("No", "boolean"), Ok(json!(false)));
+        assert_eq!(coerce("1550444792010", "date"), Ok(json!(1550444792010i64)));
+        assert_eq!(
+            coerce("2019-02-17T23:06:32+01:00", "date"),
+            Ok(json!("2019-02-17T23:06:32+01:00"))
+        );
+        assert_eq!(
+            coerce("43.6, 1.44", "geo_point"),
+            Ok(json!({ "lat": 43.6, "lon": 1.44 }))
+        );
+        assert_eq!(coerce("0042", "keyword"), Ok(json!("0042")));
+    }
+
+    #[test]
+    fn coerce_fail() {
+        assert!(coerce("4.2", "integer").is_err());
+        assert!(coerce("maybe", "boolean").is_err());
+        assert!(coerce("17/02/2019", "date").is_err());
+        assert!(coerce("43.6", "geo_point").is_err());
+    }
+}
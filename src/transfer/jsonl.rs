@@ -0,0 +1,574 @@
+use crate::kuzzle::Kuzzle;
+use crate::transfer::{Chunk, ImportReport, TransferOptions};
+use crate::types::{Error, QueryOptions, SdkError};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::io::{BufRead, Read, Write};
+
+/// A single JSONL line: a document identifier and its content.
+#[derive(Deserialize, Serialize)]
+struct Line {
+    #[serde(rename = "_id")]
+    id: Option<String>,
+    body: Map<String, Value>,
+}
+
+/// Streams every document of a collection to `writer`, one JSON object per line
+/// (`{"_id": "...", "body": {...}}`), using a search cursor.
+/// Returns the number of written documents.
+///
+/// # Example
+///
+/// ```
+/// use kuzzle_sdk::kuzzle::Kuzzle;
+/// use kuzzle_sdk::protocols::Http;
+/// use kuzzle_sdk::transfer::{self, TransferOptions};
+/// use kuzzle_sdk::types::KuzzleOptions;
+/// use std::io;
+///
+/// let kuzzle = Kuzzle::new(
+///     Http::new(
+///         KuzzleOptions::new("localhost", 7512)
+///     )
+/// );
+///
+/// let res = transfer::export_collection(
+///     &kuzzle,
+///     "ferris_index",
+///     "ferris_collection",
+///     &mut io::stdout(),
+///     TransferOptions::new(),
+/// );
+///
+/// ```
+///
+pub fn export_collection<W: Write>(
+    kuzzle: &Kuzzle,
+    index: &str,
+    collection: &str,
+    writer: &mut W,
+    mut options: TransferOptions,
+) -> Result<usize, Error> {
+    if *options.chunk_size() == 0 {
+        return Err(Error::InvalidArgument(SdkError::new(
+            "transfer::export_collection",
+            "chunk_size option must be greater than 0.",
+        )));
+    }
+
+    let offset = *options.offset();
+    let mut position = 0;
+    let mut exported = 0;
+    let mut result = kuzzle.document().search(
+        index,
+        collection,
+        &HashMap::new(),
+        QueryOptions::new()
+            .set_size(*options.chunk_size() as u64)
+            .set_scroll(options.scroll()),
+    )?;
+
+    while !result.hits().is_empty() {
+        for document in result.hits() {
+            position += 1;
+            if position <= offset {
+                continue;
+            }
+
+            let line = Line {
+                id: Some(document.id()),
+                body: document.content(),
+            };
+            serde_json::to_writer(&mut *writer, &line)?;
+            writer.write_all(b"\n")?;
+            exported += 1;
+        }
+        options.notify_progress(position);
+
+        let scroll_id = result.scroll_id();
+        if scroll_id.is_empty() || position as u64 >= result.total() {
+            break;
+        }
+        result = kuzzle
+            .document()
+            .scroll(&scroll_id, options.scroll(), QueryOptions::new())?;
+    }
+
+    writer.flush()?;
+    Ok(exported)
+}
+
+/// Reads JSONL documents from `reader` (as written by `export_collection`) and
+/// writes them to a collection by chunks, using `bulk:import`.
+/// Documents with an `_id` replace any existing document with the same identifier.
+/// Returns the number of imported documents, and the documents rejected by Kuzzle
+/// along with their line number.
+///
+/// # Example
+///
+/// ```
+/// use kuzzle_sdk::kuzzle::Kuzzle;
+/// use kuzzle_sdk::protocols::Http;
+/// use kuzzle_sdk::transfer::{self, TransferOptions};
+/// use kuzzle_sdk::types::KuzzleOptions;
+///
+/// let kuzzle = Kuzzle::new(
+///     Http::new(
+///         KuzzleOptions::new("localhost", 7512)
+///     )
+/// );
+///
+/// let dump = "{\"_id\":\"ferris\",\"body\":{\"name\":\"Ferris\"}}\n";
+/// let res = transfer::import_collection(
+///     &kuzzle,
+///     "ferris_index",
+///     "ferris_collection",
+///     dump.as_bytes(),
+///     TransferOptions::new().set_chunk_size(1000),
+/// );
+///
+/// ```
+///
+pub fn import_collection<R: BufRead>(
+    kuzzle: &Kuzzle,
+    index: &str,
+    collection: &str,
+    reader: R,
+    mut options: TransferOptions,
+) -> Result<ImportReport, Error> {
+    let chunk_size = *options.chunk_size();
+    if chunk_size == 0 {
+        return Err(Error::InvalidArgument(SdkError::new(
+            "transfer::import_collection",
+            "chunk_size option must be greater than 0.",
+        )));
+    }
+
+    let offset = *options.offset();
+    let mut position = 0;
+    let mut report = ImportReport::default();
+    let mut chunk = Chunk::default();
+
+    for (number, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        position += 1;
+        if position <= offset {
+            continue;
+        }
+
+        let document: Line = serde_json::from_str(&line).map_err(|err| {
+            SdkError::new(
+                "transfer::import_collection",
+                &format!("Invalid document at line {}: {}", number + 1, err),
+            )
+        })?;
+        chunk.push(number as u64 + 1, document.id, document.body);
+
+        if chunk.len() == chunk_size {
+            chunk.import(kuzzle, index, collection, &mut report)?;
+            options.notify_progress(position);
+        }
+    }
+
+    if !chunk.is_empty() {
+        chunk.import(kuzzle, index, collection, &mut report)?;
+        options.notify_progress(position);
+    }
+
+    Ok(report)
+}
+
+/// Writes the mapping of a collection to `writer`, as a JSON object.
+///
+/// # Example
+///
+/// ```
+/// use kuzzle_sdk::kuzzle::Kuzzle;
+/// use kuzzle_sdk::protocols::Http;
+/// use kuzzle_sdk::transfer;
+/// use kuzzle_sdk::types::KuzzleOptions;
+/// use std::io;
+///
+/// let kuzzle = Kuzzle::new(
+///     Http::new(
+///         KuzzleOptions::new("localhost", 7512)
+///     )
+/// );
+///
+/// let res = transfer::export_mapping(
+///     &kuzzle,
+///     "ferris_index",
+///     "ferris_collection",
+///     &mut io::stdout(),
+/// );
+///
+/// ```
+///
+pub fn export_mapping<W: Write>(
+    kuzzle: &Kuzzle,
+    index: &str,
+    collection: &str,
+    writer: &mut W,
+) -> Result<(), Error> {
+    let mapping = kuzzle
+        .collection()
+        .get_mapping(index, collection, QueryOptions::new())?;
+    serde_json::to_writer(&mut *writer, &mapping)?;
+    writer.write_all(b"\n")?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// Reads a JSON mapping from `reader` (as written by `export_mapping`)
+/// and applies it to a collection.
+///
+/// # Example
+///
+/// ```
+/// use kuzzle_sdk::kuzzle::Kuzzle;
+/// use kuzzle_sdk::protocols::Http;
+/// use kuzzle_sdk::transfer;
+/// use kuzzle_sdk::types::KuzzleOptions;
+///
+/// let kuzzle = Kuzzle::new(
+///     Http::new(
+///         KuzzleOptions::new("localhost", 7512)
+///     )
+/// );
+///
+/// let mapping = "{\"properties\":{\"name\":{\"type\":\"keyword\"}}}";
+/// let res = transfer::import_mapping(
+///     &kuzzle,
+///     "ferris_index",
+///     "ferris_collection",
+///     mapping.as_bytes(),
+/// );
+///
+/// ```
+///
+pub fn import_mapping<R: Read>(
+    kuzzle: &Kuzzle,
+    index: &str,
+    collection: &str,
+    reader: R,
+) -> Result<(), Error> {
+    let mapping: Map<String, Value> = serde_json::from_reader(reader)?;
+    kuzzle
+        .collection()
+        .update_mapping(index, collection, &mapping, QueryOptions::new())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocols::Http;
+    use crate::types::KuzzleOptions;
+    use mockito;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    #[test]
+    fn export_collection_ok() {
+        let _search = mockito::mock(
+            "POST",
+            mockito::Matcher::Regex(r"^/ferris_index/ferris_collection/_search\?".to_string()),
+        )
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            r#"{
+                "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                "status": 200,
+                "error": null,
+                "controller": "document",
+                "action": "search",
+                "collection": "ferris_collection",
+                "index": "ferris_index",
+                "volatile": null,
+                "result": {
+                    "scrollId": "ferrisScrollId",
+                    "total": 3,
+                    "hits": [
+                        { "_id": "ferris", "_source": { "name": "Ferris" } },
+                        { "_id": "corro", "_source": { "name": "Corro" } }
+                    ]
+                }
+            }"#,
+        )
+        .create();
+        let _scroll = mockito::mock(
+            "GET",
+            mockito::Matcher::Regex(r"^/_scroll/ferrisScrollId".to_string()),
+        )
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            r#"{
+                "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                "status": 200,
+                "error": null,
+                "controller": "document",
+                "action": "scroll",
+                "collection": null,
+                "index": null,
+                "volatile": null,
+                "result": {
+                    "scrollId": "ferrisScrollId",
+                    "total": 3,
+                    "hits": [
+                        { "_id": "rustacean", "_source": { "name": "Rustacean" } }
+                    ]
+                }
+            }"#,
+        )
+        .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        k.connect().expect("Unable to connect to Kuzzle server");
+        let positions = Rc::new(RefCell::new(Vec::new()));
+        let recorded = positions.clone();
+        let mut output: Vec<u8> = Vec::new();
+        let res = export_collection(
+            &k,
+            "ferris_index",
+            "ferris_collection",
+            &mut output,
+            TransferOptions::new()
+                .set_chunk_size(2)
+                .set_offset(1)
+                .set_progress(move |position| recorded.borrow_mut().push(position)),
+        );
+
+        assert!(res.is_ok());
+        assert_eq!(res.unwrap(), 2);
+        assert_eq!(*positions.borrow(), vec![2, 3]);
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "{\"_id\":\"corro\",\"body\":{\"name\":\"Corro\"}}\n\
+             {\"_id\":\"rustacean\",\"body\":{\"name\":\"Rustacean\"}}\n"
+        );
+    }
+
+    #[test]
+    fn export_collection_fail_error() {
+        let _m = mockito::mock(
+            "POST",
+            mockito::Matcher::Regex(r"^/ferris_index/ferris_collection/_search\?".to_string()),
+        )
+        .with_status(403)
+        .with_header("content-type", "application/json")
+        .with_body(
+            r#"{
+                "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                "status": 403,
+                "error": {
+                    "message": "Forbidden action [ferris_index/ferris_collection/document/search] for user -1",
+                    "status": 403,
+                    "stack": "ForbiddenError: Forbidden action [ferris_index/ferris_collection/document/search] for user -1\n"
+                },
+                "controller": "document",
+                "action": "search",
+                "collection": "ferris_collection",
+                "index": "ferris_index",
+                "volatile": null,
+                "result": null
+            }"#,
+        )
+        .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        k.connect().expect("Unable to connect to Kuzzle server");
+        let mut output: Vec<u8> = Vec::new();
+        let res = export_collection(
+            &k,
+            "ferris_index",
+            "ferris_collection",
+            &mut output,
+            TransferOptions::new(),
+        );
+
+        assert!(res.is_err());
+        assert!(output.is_empty());
+    }
+
+    #[test]
+    fn import_collection_ok() {
+        let first = mockito::mock("POST", "/ferris_index/ferris_collection/_bulk")
+            .match_body(
+                r#"{"bulkData":[{"index":{"_id":"corro"}},{"name":"Corro"},{"index":{}},{"name":"Anonymous"}]}"#,
+            )
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "bulk",
+                    "action": "import",
+                    "collection": "ferris_collection",
+                    "index": "ferris_index",
+                    "volatile": null,
+                    "result": {
+                        "successes": [
+                            { "_id": "corro", "_source": { "name": "Corro" }, "status": 200 }
+                        ],
+                        "errors": [
+                            {
+                                "document": { "body": { "name": "Anonymous" } },
+                                "status": 400,
+                                "reason": "document rejected by a plugin"
+                            }
+                        ]
+                    }
+                }"#,
+            )
+            .expect(1)
+            .create();
+        let last = mockito::mock("POST", "/ferris_index/ferris_collection/_bulk")
+            .match_body(r#"{"bulkData":[{"index":{"_id":"rustacean"}},{"name":"Rustacean"}]}"#)
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "bulk",
+                    "action": "import",
+                    "collection": "ferris_collection",
+                    "index": "ferris_index",
+                    "volatile": null,
+                    "result": {
+                        "successes": [
+                            { "_id": "rustacean", "_source": { "name": "Rustacean" }, "status": 201 }
+                        ],
+                        "errors": []
+                    }
+                }"#,
+            )
+            .expect(1)
+            .create();
+
+        let dump = "{\"_id\":\"ferris\",\"body\":{\"name\":\"Ferris\"}}\n\
+                    {\"_id\":\"corro\",\"body\":{\"name\":\"Corro\"}}\n\
+                    \n\
+                    {\"body\":{\"name\":\"Anonymous\"}}\n\
+                    {\"_id\":\"rustacean\",\"body\":{\"name\":\"Rustacean\"}}\n";
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        k.connect().expect("Unable to connect to Kuzzle server");
+        let positions = Rc::new(RefCell::new(Vec::new()));
+        let recorded = positions.clone();
+        let res = import_collection(
+            &k,
+            "ferris_index",
+            "ferris_collection",
+            dump.as_bytes(),
+            TransferOptions::new()
+                .set_chunk_size(2)
+                .set_offset(1)
+                .set_progress(move |position| recorded.borrow_mut().push(position)),
+        );
+
+        let report = res.unwrap();
+        assert_eq!(report.imported(), 2);
+        assert_eq!(report.rejected().len(), 1);
+        assert_eq!(report.rejected()[0].line(), 4);
+        assert_eq!(
+            report.rejected()[0].reason(),
+            "document rejected by a plugin"
+        );
+        assert_eq!(*positions.borrow(), vec![3, 4]);
+        first.assert();
+        last.assert();
+    }
+
+    #[test]
+    fn import_collection_fail_invalid_line() {
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        k.connect().expect("Unable to connect to Kuzzle server");
+        let res = import_collection(
+            &k,
+            "ferris_index",
+            "ferris_collection",
+            "{\"_id\":\"ferris\"}\n".as_bytes(),
+            TransferOptions::new(),
+        );
+
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn export_mapping_ok() {
+        let _m = mockito::mock("GET", "/ferris_index/ferris_collection/_mapping")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "collection",
+                    "action": "getMapping",
+                    "collection": "ferris_collection",
+                    "index": "ferris_index",
+                    "volatile": null,
+                    "result": {
+                        "properties": {
+                            "name": { "type": "keyword" }
+                        }
+                    }
+                }"#,
+            )
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        k.connect().expect("Unable to connect to Kuzzle server");
+        let mut output: Vec<u8> = Vec::new();
+        let res = export_mapping(&k, "ferris_index", "ferris_collection", &mut output);
+
+        assert!(res.is_ok());
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "{\"properties\":{\"name\":{\"type\":\"keyword\"}}}\n"
+        );
+    }
+
+    #[test]
+    fn import_mapping_ok() {
+        let _m = mockito::mock("PUT", "/ferris_index/ferris_collection/_mapping")
+            .match_body(r#"{"properties":{"name":{"type":"keyword"}}}"#)
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "requestId": "da9040aa-9529-4fb9-b627-a38736321364",
+                    "status": 200,
+                    "error": null,
+                    "controller": "collection",
+                    "action": "updateMapping",
+                    "collection": "ferris_collection",
+                    "index": "ferris_index",
+                    "volatile": null,
+                    "result": { "acknowledged": true }
+                }"#,
+            )
+            .create();
+
+        let k = Kuzzle::new(Http::new(KuzzleOptions::new("localhost", 7512)));
+        k.connect().expect("Unable to connect to Kuzzle server");
+        let res = import_mapping(
+            &k,
+            "ferris_index",
+            "ferris_collection",
+            "{\"properties\":{\"name\":{\"type\":\"keyword\"}}}".as_bytes(),
+        );
+
+        assert!(res.is_ok());
+    }
+}
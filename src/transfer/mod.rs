@@ -0,0 +1,230 @@
+//! Helpers moving documents in and out of Kuzzle collections.
+//!
+//! Documents are streamed by chunks so that collections never have to fit in memory.
+
+mod csv;
+mod jsonl;
+
+pub use self::csv::{import_csv, CsvOptions};
+pub use self::jsonl::{export_collection, export_mapping, import_collection, import_mapping};
+
+use crate::kuzzle::Kuzzle;
+use crate::types::{Error, QueryOptions};
+
+use serde_json::{json, Map, Value};
+
+/// Options are used to tune imports and exports behavior.
+pub struct TransferOptions {
+    _chunk_size: usize,
+    _offset: usize,
+    _scroll: String,
+    _progress: Option<Box<dyn FnMut(usize)>>,
+}
+
+impl Default for TransferOptions {
+    fn default() -> TransferOptions {
+        TransferOptions {
+            _chunk_size: 500,
+            _offset: 0,
+            _scroll: String::from("1m"),
+            _progress: None,
+        }
+    }
+}
+
+impl TransferOptions {
+    /// Returns a TransferOptions struct with default values.
+    /// When created this struct follow the builder pattern and permits
+    /// on the fly updates.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::transfer::TransferOptions;
+    /// let options = TransferOptions::new()
+    ///     .set_chunk_size(1000)
+    ///     .set_offset(5000)
+    ///     .set_progress(|position| println!("{} documents processed", position));
+    /// ```
+    pub fn new() -> TransferOptions {
+        Self::default()
+    }
+
+    pub fn chunk_size(&self) -> &usize {
+        &self._chunk_size
+    }
+
+    pub fn offset(&self) -> &usize {
+        &self._offset
+    }
+
+    pub fn scroll(&self) -> &String {
+        &self._scroll
+    }
+
+    /// Sets the number of documents sent or fetched per request.
+    pub fn set_chunk_size(mut self, chunk_size: usize) -> Self {
+        self._chunk_size = chunk_size;
+        self
+    }
+
+    /// Sets the number of documents to skip, used to resume an interrupted transfer.
+    pub fn set_offset(mut self, offset: usize) -> Self {
+        self._offset = offset;
+        self
+    }
+
+    /// Sets how long Kuzzle keeps export search cursors alive between two chunks.
+    pub fn set_scroll(mut self, scroll: &str) -> Self {
+        self._scroll = String::from(scroll);
+        self
+    }
+
+    /// Sets a callback invoked after each chunk with the position reached in the stream,
+    /// offset included. This position can be given back to `set_offset` to resume a transfer.
+    pub fn set_progress<F>(mut self, progress: F) -> Self
+    where
+        F: 'static + FnMut(usize),
+    {
+        self._progress = Some(Box::new(progress));
+        self
+    }
+
+    fn notify_progress(&mut self, position: usize) {
+        if let Some(progress) = &mut self._progress {
+            progress(position);
+        }
+    }
+}
+
+/// A row or line of an import input that has not been imported.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RejectedRow {
+    line: u64,
+    reason: String,
+}
+
+impl RejectedRow {
+    /// Line of the row in the input, CSV header included.
+    /// It is 0 if Kuzzle rejected a document which can't be told apart from another one.
+    pub fn line(&self) -> u64 {
+        self.line
+    }
+
+    pub fn reason(&self) -> &String {
+        &self.reason
+    }
+}
+
+/// Summary of an import.
+#[derive(Debug, Default)]
+pub struct ImportReport {
+    imported: usize,
+    rejected: Vec<RejectedRow>,
+}
+
+impl ImportReport {
+    /// Number of documents written by Kuzzle.
+    pub fn imported(&self) -> usize {
+        self.imported
+    }
+
+    /// Rows which could not be converted to documents, and documents rejected by Kuzzle,
+    /// in input order.
+    pub fn rejected(&self) -> &Vec<RejectedRow> {
+        &self.rejected
+    }
+
+    fn reject(&mut self, line: u64, reason: String) {
+        let index = self.rejected.partition_point(|row| row.line <= line);
+        self.rejected.insert(index, RejectedRow { line, reason });
+    }
+}
+
+/// Documents waiting to be sent with `bulk:import`, along with their line in the input.
+#[derive(Default)]
+struct Chunk {
+    bulk_data: Vec<Value>,
+    lines: Vec<u64>,
+}
+
+impl Chunk {
+    fn push(&mut self, line: u64, id: Option<String>, document: Map<String, Value>) {
+        self.bulk_data.push(match id {
+            Some(id) => json!({ "index": { "_id": id } }),
+            None => json!({ "index": {} }),
+        });
+        self.bulk_data.push(Value::Object(document));
+        self.lines.push(line);
+    }
+
+    fn len(&self) -> usize {
+        self.lines.len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+
+    /// Sends the documents and empties the chunk. Documents rejected by Kuzzle
+    /// are added to the report, with the reason given by Kuzzle.
+    fn import(
+        &mut self,
+        kuzzle: &Kuzzle,
+        index: &str,
+        collection: &str,
+        report: &mut ImportReport,
+    ) -> Result<(), Error> {
+        let result =
+            kuzzle
+                .bulk()
+                .import(index, collection, &self.bulk_data, QueryOptions::new())?;
+        let errors = result
+            .get("errors")
+            .and_then(Value::as_array)
+            .cloned()
+            .unwrap_or_default();
+
+        report.imported += match result.get("successes").and_then(Value::as_array) {
+            Some(successes) => successes.len(),
+            None => self.len().saturating_sub(errors.len()),
+        };
+
+        let mut rejected = vec![false; self.len()];
+        for error in &errors {
+            let reason = match &error["reason"] {
+                Value::String(reason) => reason.clone(),
+                Value::Null => error.to_string(),
+                reason => reason.to_string(),
+            };
+            let line = match self.find(&error["document"], &rejected) {
+                Some(position) => {
+                    rejected[position] = true;
+                    self.lines[position]
+                }
+                None => 0,
+            };
+            report.reject(line, reason);
+        }
+
+        self.bulk_data.clear();
+        self.lines.clear();
+        Ok(())
+    }
+
+    /// Returns the position of a document rejected by Kuzzle, matched on its
+    /// identifier or else on its content.
+    fn find(&self, document: &Value, rejected: &[bool]) -> Option<usize> {
+        let id = document["_id"].as_str();
+        let body = &document["body"];
+
+        (0..self.len())
+            .filter(|position| !rejected[*position])
+            .find(
+                |position| match (id, self.bulk_data[position * 2]["index"]["_id"].as_str()) {
+                    (Some(id), Some(sent)) => id == sent,
+                    _ => !body.is_null() && self.bulk_data[position * 2 + 1] == *body,
+                },
+            )
+    }
+}
@@ -0,0 +1,118 @@
+use chrono::{DateTime, TimeZone, Utc};
+
+/// An API key, as returned by `auth:createApiKey` or `security:createApiKey`.
+#[derive(Deserialize, Debug, Clone)]
+pub struct ApiKey {
+    #[serde(alias = "_id")]
+    _id: String,
+    #[serde(alias = "_source")]
+    _source: ApiKeySource,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+struct ApiKeySource {
+    #[serde(alias = "userId")]
+    _user_id: String,
+    #[serde(alias = "description", default)]
+    _description: String,
+    #[serde(alias = "expiresAt")]
+    _expires_at: Option<i64>,
+    #[serde(alias = "token")]
+    _token: Option<String>,
+}
+
+impl ApiKey {
+    pub fn id(&self) -> String {
+        self._id.clone()
+    }
+
+    /// Returns the unique identifier of the user owning the key.
+    pub fn user_id(&self) -> String {
+        self._source._user_id.clone()
+    }
+
+    pub fn description(&self) -> String {
+        self._source._description.clone()
+    }
+
+    /// Returns the key expiration date, or `None` if the key never expires.
+    pub fn expires_at(&self) -> Option<DateTime<Utc>> {
+        self._source
+            ._expires_at
+            .filter(|expires_at| *expires_at >= 0)
+            .and_then(|expires_at| Utc.timestamp_millis_opt(expires_at).single())
+    }
+
+    /// Returns the authentication token of the key.
+    /// Kuzzle only sends it back once, when the key is created:
+    /// it is empty for keys returned by a search.
+    pub fn token(&self) -> String {
+        self._source._token.clone().unwrap_or_default()
+    }
+}
+
+/// A page of API keys returned by `auth:searchApiKeys` or `security:searchApiKeys`.
+#[derive(Deserialize, Debug)]
+pub struct ApiKeySearchResult {
+    #[serde(alias = "total")]
+    _total: u64,
+    #[serde(alias = "hits")]
+    _hits: Vec<ApiKey>,
+}
+
+impl ApiKeySearchResult {
+    pub fn total(&self) -> u64 {
+        self._total
+    }
+
+    pub fn hits(&self) -> &Vec<ApiKey> {
+        &self._hits
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn deserialize_ok() {
+        let api_key: ApiKey = serde_json::from_value(json!({
+            "_id": "ferris-key",
+            "_source": {
+                "userId": "ferris",
+                "description": "Nightly backups",
+                "expiresAt": 1321085955000i64,
+                "ttl": 3600000,
+                "token": "aSuperApiKeyToken"
+            }
+        }))
+        .unwrap();
+
+        assert_eq!(api_key.id(), "ferris-key");
+        assert_eq!(api_key.user_id(), "ferris");
+        assert_eq!(api_key.description(), "Nightly backups");
+        assert_eq!(
+            api_key.expires_at(),
+            Utc.timestamp_millis_opt(1321085955000).single()
+        );
+        assert_eq!(api_key.token(), "aSuperApiKeyToken");
+    }
+
+    #[test]
+    fn deserialize_ok_never_expires() {
+        let api_key: ApiKey = serde_json::from_value(json!({
+            "_id": "ferris-key",
+            "_source": {
+                "userId": "ferris",
+                "description": "Nightly backups",
+                "expiresAt": -1,
+                "ttl": -1
+            }
+        }))
+        .unwrap();
+
+        assert_eq!(api_key.expires_at(), None);
+        assert_eq!(api_key.token(), "");
+    }
+}
@@ -0,0 +1,42 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// A CancellationToken aborts the requests it is given to, through `QueryOptions`.
+/// Clones share the same state: cancel one from another thread to abort
+/// an in-flight request, which then fails with `Error::Cancelled`.
+///
+/// Cancelling stops waiting for the response, but a request already sent
+/// may still be processed by Kuzzle. With the `Http` protocol, the request keeps
+/// running in the background until it completes or times out.
+///
+/// # Example
+///
+/// ```
+/// use kuzzle_sdk::types::{CancellationToken, QueryOptions};
+///
+/// let token = CancellationToken::new();
+/// let options = QueryOptions::new().set_cancellation_token(token.clone());
+///
+/// // From another thread
+/// token.cancel();
+/// assert!(options.cancellation_token().unwrap().is_cancelled());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    _cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    pub fn new() -> CancellationToken {
+        Default::default()
+    }
+
+    /// Cancels the requests using this token, or any of its clones.
+    pub fn cancel(&self) {
+        self._cancelled.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self._cancelled.load(Ordering::SeqCst)
+    }
+}
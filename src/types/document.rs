@@ -0,0 +1,19 @@
+use serde_json::{Map, Value};
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct Document {
+    #[serde(alias = "_id")]
+    _id: String,
+    #[serde(alias = "_source", default)]
+    _content: Map<String, Value>,
+}
+
+impl Document {
+    pub fn id(&self) -> String {
+        self._id.clone()
+    }
+
+    pub fn content(&self) -> Map<String, Value> {
+        self._content.clone()
+    }
+}
@@ -0,0 +1,172 @@
+use crate::types::SdkError;
+use std::fmt;
+use std::str::FromStr;
+
+/// Top level of the Kuzzle error hierarchy.
+///
+/// See https://docs.kuzzle.io/core/2/api/errors/error-codes/
+#[derive(Debug, Clone, PartialEq)]
+pub enum ErrorDomain {
+    Core,
+    Services,
+    Api,
+    Network,
+    Plugin,
+    Validation,
+    Security,
+    Cluster,
+    /// A domain unknown to this SDK version.
+    Other(String),
+}
+
+impl ErrorDomain {
+    /// Returns the domain name, as found in error ids.
+    pub fn name(&self) -> &str {
+        match self {
+            ErrorDomain::Core => "core",
+            ErrorDomain::Services => "services",
+            ErrorDomain::Api => "api",
+            ErrorDomain::Network => "network",
+            ErrorDomain::Plugin => "plugin",
+            ErrorDomain::Validation => "validation",
+            ErrorDomain::Security => "security",
+            ErrorDomain::Cluster => "cluster",
+            ErrorDomain::Other(name) => name,
+        }
+    }
+}
+
+impl<'a> From<&'a str> for ErrorDomain {
+    fn from(name: &'a str) -> ErrorDomain {
+        match name {
+            "core" => ErrorDomain::Core,
+            "services" => ErrorDomain::Services,
+            "api" => ErrorDomain::Api,
+            "network" => ErrorDomain::Network,
+            "plugin" => ErrorDomain::Plugin,
+            "validation" => ErrorDomain::Validation,
+            "security" => ErrorDomain::Security,
+            "cluster" => ErrorDomain::Cluster,
+            other => ErrorDomain::Other(other.to_string()),
+        }
+    }
+}
+
+/// A Kuzzle error identifier, made of a domain, a subdomain and an error name
+/// (e.g. `security.token.expired`).
+///
+/// # Example
+///
+/// ```
+/// use kuzzle_sdk::types::{ErrorDomain, ErrorId};
+///
+/// let id: ErrorId = "security.token.expired".parse().unwrap();
+/// assert_eq!(id.domain(), &ErrorDomain::Security);
+/// assert_eq!(id.subdomain(), "token");
+/// assert_eq!(id.error(), "expired");
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ErrorId {
+    _domain: ErrorDomain,
+    _subdomain: String,
+    _error: String,
+}
+
+impl ErrorId {
+    pub fn domain(&self) -> &ErrorDomain {
+        &self._domain
+    }
+
+    pub fn subdomain(&self) -> &str {
+        &self._subdomain
+    }
+
+    pub fn error(&self) -> &str {
+        &self._error
+    }
+
+    /// Returns true if this id matches `pattern`, which is either a full id
+    /// (`api.assert.missing_argument`), a subdomain (`api.assert`) or a domain (`api`).
+    pub fn is(&self, pattern: &str) -> bool {
+        let parts: Vec<&str> = pattern.split('.').collect();
+        let expected = [
+            self._domain.name(),
+            self._subdomain.as_str(),
+            self._error.as_str(),
+        ];
+
+        !pattern.is_empty()
+            && parts.len() <= expected.len()
+            && parts
+                .iter()
+                .zip(expected.iter())
+                .all(|(part, id_part)| part == id_part)
+    }
+}
+
+impl FromStr for ErrorId {
+    type Err = SdkError;
+
+    fn from_str(id: &str) -> Result<ErrorId, SdkError> {
+        let parts: Vec<&str> = id.split('.').collect();
+
+        if parts.len() != 3 || parts.iter().any(|part| part.is_empty()) {
+            return Err(SdkError::new(
+                "ErrorId::from_str",
+                &format!("\"{}\" is not a valid error id.", id),
+            ));
+        }
+
+        Ok(ErrorId {
+            _domain: ErrorDomain::from(parts[0]),
+            _subdomain: parts[1].to_string(),
+            _error: parts[2].to_string(),
+        })
+    }
+}
+
+impl fmt::Display for ErrorId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}.{}.{}",
+            self._domain.name(),
+            self._subdomain,
+            self._error
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_str_ok() {
+        let id: ErrorId = "plugin.custom.my_error".parse().unwrap();
+
+        assert_eq!(id.domain(), &ErrorDomain::Plugin);
+        assert_eq!(format!("{}", id), "plugin.custom.my_error");
+
+        let id: ErrorId = "unknown.custom.my_error".parse().unwrap();
+        assert_eq!(id.domain(), &ErrorDomain::Other("unknown".to_string()));
+    }
+
+    #[test]
+    fn from_str_fail() {
+        assert!("security.token".parse::<ErrorId>().is_err());
+        assert!("security..expired".parse::<ErrorId>().is_err());
+        assert!("a.b.c.d".parse::<ErrorId>().is_err());
+    }
+
+    #[test]
+    fn is_ok() {
+        let id: ErrorId = "security.token.expired".parse().unwrap();
+
+        assert!(id.is("security.token.expired"));
+        assert!(id.is("security.token"));
+        assert!(id.is("security"));
+        assert!(!id.is("security.token.invalid"));
+        assert!(!id.is("security.token.expired.more"));
+    }
+}
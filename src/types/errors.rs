@@ -1,5 +1,8 @@
+use crate::types::ErrorId;
+use serde_json::Value;
 use std::error;
 use std::fmt;
+use std::io;
 
 /// A KuzzleError is an error specific to Kuzzle backend. It's often seen in types::Response
 /// when request failed. It allow you to create your own througth the `new` constructor.
@@ -8,6 +11,16 @@ pub struct KuzzleError {
     status: Option<u16>,
     message: String,
     stack: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    code: Option<u32>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    props: Vec<Value>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    errors: Vec<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    count: Option<u64>,
 }
 
 impl KuzzleError {
@@ -16,7 +29,7 @@ impl KuzzleError {
     /// # Arguments
     ///
     /// * `status` - An `Option<u16>` that can be `Some(value)` or `None`.
-    /// Depends you want to specify a custom status code.
+    ///   Depends you want to specify a custom status code.
     /// * `message` - A string slice that holds your custom error message.
     ///
     /// # Example
@@ -32,9 +45,28 @@ impl KuzzleError {
             status,
             message: message.to_string(),
             stack: None,
+            id: None,
+            code: None,
+            props: Vec::new(),
+            errors: Vec::new(),
+            count: None,
         }
     }
 
+    /// Sets the Kuzzle error id (e.g. `security.token.expired`).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::types::KuzzleError;
+    /// let expired = KuzzleError::new(Some(401), "Token expired").set_id("security.token.expired");
+    /// assert!(expired.is("security.token"));
+    /// ```
+    pub fn set_id(mut self, id: &str) -> Self {
+        self.id = Some(id.to_string());
+        self
+    }
+
     /// KuzzleError status getter.
     pub fn status(&self) -> Option<u16> {
         self.status
@@ -52,13 +84,70 @@ impl KuzzleError {
             None => String::new(),
         }
     }
+
+    /// KuzzleError id getter (e.g. `api.assert.missing_argument`).
+    /// Empty with Kuzzle versions prior to 2.
+    pub fn id(&self) -> String {
+        match &self.id {
+            Some(id) => id.clone(),
+            None => String::new(),
+        }
+    }
+
+    /// Returns the parsed error id, if Kuzzle provided a valid one.
+    pub fn error_id(&self) -> Option<ErrorId> {
+        self.id.as_ref().and_then(|id| id.parse().ok())
+    }
+
+    /// KuzzleError code getter. This is the numeric counterpart of the error id.
+    pub fn code(&self) -> Option<u32> {
+        self.code
+    }
+
+    /// KuzzleError props getter: the values used to build the error message,
+    /// such as argument names, limits or sizes.
+    pub fn props(&self) -> &Vec<Value> {
+        &self.props
+    }
+
+    /// Failed items of a `PartialError`.
+    pub fn errors(&self) -> &Vec<Value> {
+        &self.errors
+    }
+
+    /// Number of failed items of a `PartialError`.
+    pub fn count(&self) -> u64 {
+        self.count.unwrap_or(self.errors.len() as u64)
+    }
+
+    /// Returns true if the error id matches `pattern`: either a full id
+    /// (`api.assert.missing_argument`), a subdomain (`api.assert`) or a domain (`api`).
+    pub fn is(&self, pattern: &str) -> bool {
+        match self.error_id() {
+            Some(id) => id.is(pattern),
+            None => false,
+        }
+    }
 }
 
 impl error::Error for KuzzleError {}
 
 impl fmt::Display for KuzzleError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        // Provide error description following https://docs-v2.kuzzle.io/api/1/errors
+        let status = match self.status {
+            Some(status) => status.to_string(),
+            None => String::from("-"),
+        };
+
+        if let Some(id) = &self.id {
+            return match &self.stack {
+                Some(stack) => write!(f, "[{}] {} {}", status, id, stack),
+                None => write!(f, "[{}] {} : {}", status, id, self.message),
+            };
+        }
+
+        // Kuzzle v1 errors have no id: provide error description
+        // following https://docs-v2.kuzzle.io/api/1/errors
         let description: &str = match &self.status {
             None => "UnidentifiedError",
             Some(status) => match &status {
@@ -80,15 +169,9 @@ impl fmt::Display for KuzzleError {
         match &self.stack {
             // If Some(stack) drop `self.message`
             // since there is an error message in `self.stack`...
-            Some(stack) => write!(f, "[{}] {}", self.status.unwrap(), stack),
+            Some(stack) => write!(f, "[{}] {}", status, stack),
             // ... else take `self.message`.
-            None => write!(
-                f,
-                "[{}] {} : {}",
-                self.status.unwrap(),
-                description,
-                self.message
-            ),
+            None => write!(f, "[{}] {} : {}", status, description, self.message),
         }
     }
 }
@@ -106,7 +189,7 @@ impl SdkError {
     /// # Arguments
     ///
     /// * `cause` - A `&str` containing name of the function, method or controller
-    /// that triggered the error.
+    ///   that triggered the error.
     /// * `message` - A `&str` slice that holds your custom error message.
     ///
     /// # Example
@@ -142,3 +225,289 @@ impl fmt::Display for SdkError {
         write!(f, "[{}] {}", self.cause, self.message)
     }
 }
+
+/// Any error returned by the SDK.
+///
+/// Errors sent back by Kuzzle are wrapped in `Api`, `Unauthorized` or `NotFound`,
+/// depending on their status.
+///
+/// # Example
+///
+/// ```
+/// use kuzzle_sdk::kuzzle::Kuzzle;
+/// use kuzzle_sdk::protocols::Http;
+/// use kuzzle_sdk::types::{KuzzleOptions, QueryOptions};
+/// use kuzzle_sdk::Error;
+///
+/// let kuzzle = Kuzzle::new(
+///     Http::new(
+///         KuzzleOptions::new("localhost", 7512)
+///     )
+/// );
+///
+/// match kuzzle.index().exists("ferris_index", QueryOptions::new()) {
+///     Ok(exists) => println!("ferris_index exists: {}", exists),
+///     Err(Error::NotConnected) => println!("Call connect() first"),
+///     Err(Error::Network(err)) => println!("Kuzzle is unreachable: {}", err),
+///     Err(err) => println!("{}", err),
+/// }
+/// ```
+///
+#[derive(Debug)]
+pub enum Error {
+    /// Kuzzle can't be reached, or the connection has been lost.
+    Network(Box<dyn error::Error + Send + Sync>),
+    /// Kuzzle did not answer in time.
+    Timeout,
+    /// The request has been cancelled with its `CancellationToken`.
+    Cancelled,
+    /// The protocol is not connected to a Kuzzle server.
+    NotConnected,
+    /// Arguments given to the SDK are invalid.
+    InvalidArgument(SdkError),
+    /// A payload can't be serialized or deserialized.
+    Serialization(serde_json::Error),
+    /// Reading or writing a local file, such as a token store or a fixture, failed.
+    /// Connection failures are reported as `Network` errors, whatever the protocol.
+    Io(io::Error),
+    /// The protocol has no route to the given controller action.
+    UnknownRoute { controller: String, action: String },
+    /// Kuzzle rejected the request credentials (401).
+    Unauthorized(Box<KuzzleError>),
+    /// The requested resource does not exist (404).
+    NotFound(Box<KuzzleError>),
+    /// Any other error returned by Kuzzle.
+    Api(Box<KuzzleError>),
+}
+
+impl Error {
+    /// Returns the error sent back by Kuzzle, if any.
+    pub fn kuzzle_error(&self) -> Option<&KuzzleError> {
+        match self {
+            Error::Unauthorized(err) | Error::NotFound(err) | Error::Api(err) => Some(err),
+            _ => None,
+        }
+    }
+
+    /// Returns true if this is an error sent back by Kuzzle whose id matches `pattern`.
+    /// See `KuzzleError::is`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kuzzle_sdk::types::KuzzleError;
+    /// use kuzzle_sdk::Error;
+    ///
+    /// let err = Error::from(
+    ///     KuzzleError::new(Some(400), "Missing argument \"body.foo\".")
+    ///         .set_id("api.assert.missing_argument")
+    /// );
+    /// assert!(err.is("api.assert.missing_argument"));
+    /// assert!(err.is("api.assert"));
+    /// assert!(!err.is("security"));
+    /// ```
+    pub fn is(&self, pattern: &str) -> bool {
+        match self.kuzzle_error() {
+            Some(err) => err.is(pattern),
+            None => false,
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Error::Network(err) => Some(err.as_ref()),
+            Error::InvalidArgument(err) => Some(err),
+            Error::Serialization(err) => Some(err),
+            Error::Io(err) => Some(err),
+            Error::Unauthorized(err) | Error::NotFound(err) | Error::Api(err) => Some(err),
+            Error::Timeout
+            | Error::Cancelled
+            | Error::NotConnected
+            | Error::UnknownRoute { .. } => None,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Network(err) => write!(f, "Network error: {}", err),
+            Error::Timeout => write!(f, "Request timed out"),
+            Error::Cancelled => write!(f, "Request cancelled"),
+            Error::NotConnected => write!(f, "Not connected to a Kuzzle server"),
+            Error::InvalidArgument(err) => write!(f, "{}", err),
+            Error::Serialization(err) => write!(f, "Serialization error: {}", err),
+            Error::Io(err) => write!(f, "I/O error: {}", err),
+            Error::UnknownRoute { controller, action } => write!(
+                f,
+                "Unable to find route for (controller/action) {}/{}",
+                controller, action
+            ),
+            Error::Unauthorized(err) | Error::NotFound(err) | Error::Api(err) => {
+                write!(f, "{}", err)
+            }
+        }
+    }
+}
+
+impl From<KuzzleError> for Error {
+    fn from(err: KuzzleError) -> Error {
+        match err.status() {
+            Some(401) => Error::Unauthorized(Box::new(err)),
+            Some(404) => Error::NotFound(Box::new(err)),
+            _ => Error::Api(Box::new(err)),
+        }
+    }
+}
+
+impl From<SdkError> for Error {
+    fn from(err: SdkError) -> Error {
+        Error::InvalidArgument(err)
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(err: serde_json::Error) -> Error {
+        Error::Serialization(err)
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Error {
+        Error::Io(err)
+    }
+}
+
+impl From<reqwest::Error> for Error {
+    fn from(err: reqwest::Error) -> Error {
+        if err.is_timeout() {
+            Error::Timeout
+        } else {
+            Error::Network(Box::new(err))
+        }
+    }
+}
+
+impl From<tungstenite::Error> for Error {
+    fn from(err: tungstenite::Error) -> Error {
+        match err {
+            // The socket error is kept as the source, rather than its wrapper
+            tungstenite::Error::Io(err) => Error::Network(Box::new(err)),
+            err => Error::Network(Box::new(err)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ErrorDomain;
+    use serde_json::json;
+
+    #[test]
+    fn from_tungstenite_error_ok() {
+        let refused = io::Error::new(io::ErrorKind::ConnectionRefused, "Connection refused");
+        let err = Error::from(tungstenite::Error::Io(refused));
+
+        assert!(matches!(err, Error::Network(_)));
+        assert_eq!(err.to_string(), "Network error: Connection refused");
+    }
+
+    #[test]
+    fn from_kuzzle_error_ok() {
+        let not_found = Error::from(KuzzleError::new(Some(404), "Document not found"));
+        let unauthorized = Error::from(KuzzleError::new(Some(401), "Token expired"));
+        let forbidden = Error::from(KuzzleError::new(Some(403), "Forbidden"));
+
+        assert!(matches!(not_found, Error::NotFound(_)));
+        assert!(matches!(unauthorized, Error::Unauthorized(_)));
+        assert!(matches!(forbidden, Error::Api(_)));
+        assert_eq!(forbidden.kuzzle_error().unwrap().status(), Some(403));
+    }
+
+    #[test]
+    fn kuzzle_error_deserialize_ok() {
+        let err: KuzzleError = serde_json::from_str(
+            r#"{
+                "message": "Missing argument \"body.foo\".",
+                "status": 400,
+                "id": "api.assert.missing_argument",
+                "code": 33816577,
+                "props": ["body.foo"],
+                "stack": null
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(err.id(), "api.assert.missing_argument");
+        assert_eq!(err.code(), Some(33_816_577));
+        assert_eq!(err.props(), &vec![json!("body.foo")]);
+        assert_eq!(err.error_id().unwrap().domain(), &ErrorDomain::Api);
+        assert!(err.is("api.assert.missing_argument"));
+        assert!(err.is("api.assert"));
+        assert!(err.is("api"));
+        assert!(!err.is("api.assert.invalid_type"));
+        assert!(!err.is("ap"));
+        assert!(!err.is(""));
+        assert_eq!(
+            format!("{}", err),
+            "[400] api.assert.missing_argument : Missing argument \"body.foo\"."
+        );
+    }
+
+    #[test]
+    fn kuzzle_error_deserialize_ok_numeric_props() {
+        let err: KuzzleError = serde_json::from_value(json!({
+            "message": "Number of documents exceeds the server configured value (10000).",
+            "status": 400,
+            "id": "services.storage.get_limit_exceeded",
+            "props": [10000],
+            "stack": null
+        }))
+        .unwrap();
+
+        assert_eq!(err.props(), &vec![json!(10000)]);
+    }
+
+    #[test]
+    fn kuzzle_error_deserialize_partial_error_ok() {
+        let err: KuzzleError = serde_json::from_str(
+            r#"{
+                "message": "Some actions failed",
+                "status": 206,
+                "id": "services.storage.incomplete_delete",
+                "code": 16777245,
+                "errors": [
+                    { "_id": "ferris", "reason": "not found" }
+                ],
+                "count": 1
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(err.count(), 1);
+        assert_eq!(err.errors()[0]["_id"], "ferris");
+        assert!(err.is("services.storage"));
+    }
+
+    #[test]
+    fn kuzzle_error_v1_ok() {
+        let err = KuzzleError::new(Some(404), "Document not found");
+
+        assert_eq!(err.id(), "");
+        assert_eq!(err.error_id(), None);
+        assert!(!err.is("services"));
+        assert_eq!(
+            format!("{}", err),
+            "[404] NotFoundError : Document not found"
+        );
+    }
+
+    #[test]
+    fn error_is_send_and_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<Error>();
+    }
+}
@@ -0,0 +1,38 @@
+use std::collections::HashMap;
+
+/// Status of Kuzzle or of one of its external services.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum HealthStatus {
+    Green,
+    Yellow,
+    Red,
+    #[serde(other)]
+    Unknown,
+}
+
+/// Result of `server:healthCheck`: the global node status and the status of each
+/// external service (`internalCache`, `memoryStorage`, `storageEngine`...).
+#[derive(Deserialize, Debug, Clone)]
+pub struct HealthCheck {
+    #[serde(alias = "status")]
+    _status: HealthStatus,
+    #[serde(alias = "services", default)]
+    _services: HashMap<String, HealthStatus>,
+}
+
+impl HealthCheck {
+    pub fn status(&self) -> &HealthStatus {
+        &self._status
+    }
+
+    pub fn services(&self) -> &HashMap<String, HealthStatus> {
+        &self._services
+    }
+
+    /// Returns `true` if the node and all its services are green.
+    pub fn is_healthy(&self) -> bool {
+        self._status == HealthStatus::Green
+            && self._services.values().all(|s| *s == HealthStatus::Green)
+    }
+}
@@ -0,0 +1,73 @@
+use serde_json::Value;
+
+/// An ItemError describes why a single item of a multi-document action failed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ItemError {
+    _id: Option<String>,
+    _document: Option<Value>,
+    _status: Option<u16>,
+    _reason: String,
+}
+
+impl ItemError {
+    /// Builds an ItemError from an entry of the `errors` list sent by Kuzzle.
+    /// Entries are either bare identifiers, or objects holding the failed
+    /// `document` (or its `_id`), a `status` and a `reason`.
+    pub(crate) fn from_value(item: &Value, default_reason: &str) -> ItemError {
+        if let Value::String(id) = item {
+            return ItemError {
+                _id: Some(id.clone()),
+                _document: None,
+                _status: None,
+                _reason: default_reason.to_string(),
+            };
+        }
+
+        let id = item
+            .get("_id")
+            .or_else(|| item.get("id"))
+            .or_else(|| item.pointer("/document/_id"))
+            .and_then(Value::as_str)
+            .map(String::from);
+        let reason = item
+            .get("reason")
+            .or_else(|| item.pointer("/error/message"))
+            .or_else(|| item.get("message"))
+            .and_then(Value::as_str)
+            .unwrap_or(default_reason);
+
+        ItemError {
+            _id: id,
+            _document: item.get("document").cloned(),
+            _status: item
+                .get("status")
+                .and_then(Value::as_u64)
+                .map(|status| status as u16),
+            _reason: reason.to_string(),
+        }
+    }
+
+    /// Identifier of the failed item (document, index, user...), if known.
+    pub fn id(&self) -> String {
+        match &self._id {
+            Some(id) => id.clone(),
+            None => String::new(),
+        }
+    }
+
+    /// Content of the failed document, as sent to Kuzzle.
+    pub fn document(&self) -> Value {
+        match &self._document {
+            Some(document) => document.clone(),
+            None => Value::Null,
+        }
+    }
+
+    pub fn status(&self) -> Option<u16> {
+        self._status
+    }
+
+    pub fn reason(&self) -> &String {
+        &self._reason
+    }
+}
@@ -1,15 +1,41 @@
+mod api_key;
+mod cancellation_token;
+mod document;
+mod error_id;
 mod errors;
+mod health_check;
+mod item_error;
+mod multi_result;
 mod options;
 mod request;
 mod response;
+mod search_result;
+mod server_config;
+mod server_info;
+mod session;
+mod stats_frame;
+mod tls_options;
 mod token_validity;
 mod user;
 mod user_right;
 
-pub use self::errors::{KuzzleError, SdkError};
+pub use self::api_key::{ApiKey, ApiKeySearchResult};
+pub use self::cancellation_token::CancellationToken;
+pub use self::document::Document;
+pub use self::error_id::{ErrorDomain, ErrorId};
+pub use self::errors::{Error, KuzzleError, SdkError};
+pub use self::health_check::{HealthCheck, HealthStatus};
+pub use self::item_error::ItemError;
+pub use self::multi_result::MultiResult;
 pub use self::options::{KuzzleOptions, OfflineMode, QueryOptions};
 pub use self::request::KuzzleRequest;
 pub use self::response::KuzzleResponse;
+pub use self::search_result::SearchResult;
+pub use self::server_config::{ServerConfig, ServerLimits};
+pub use self::server_info::ServerInfo;
+pub use self::session::Session;
+pub use self::stats_frame::StatsFrame;
+pub use self::tls_options::{ClientIdentity, TlsOptions};
 pub use self::token_validity::TokenValidity;
 pub use self::user::User;
 pub use self::user_right::UserRight;
@@ -0,0 +1,106 @@
+use crate::types::{Error, ItemError, KuzzleResponse};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+/// A MultiResult holds the outcome of a multi-document action
+/// (`document:mCreate`, `index:mDelete`, `security:mDeleteUsers`...).
+///
+/// Kuzzle executes each item independently: a partial failure doesn't make
+/// the whole call fail, failed items are listed in `errors` instead.
+#[derive(Debug, Clone)]
+pub struct MultiResult<T> {
+    _successes: Vec<T>,
+    _errors: Vec<ItemError>,
+}
+
+impl<T> MultiResult<T> {
+    pub fn successes(&self) -> &Vec<T> {
+        &self._successes
+    }
+
+    pub fn errors(&self) -> &Vec<ItemError> {
+        &self._errors
+    }
+
+    /// Returns true if at least one item failed.
+    pub fn has_errors(&self) -> bool {
+        !self._errors.is_empty()
+    }
+}
+
+impl<T: DeserializeOwned> MultiResult<T> {
+    /// Parses a multi-document action response.
+    ///
+    /// Kuzzle v2 answers with `{ successes, errors }`, while Kuzzle v1 answers
+    /// with the successful items and, on partial failure, a `PartialError` (206)
+    /// listing failed items. Any other error makes the whole call fail.
+    pub(crate) fn from_response(res: &KuzzleResponse) -> Result<MultiResult<T>, Error> {
+        let (successes, errors) = split_response(res)?;
+
+        Ok(MultiResult {
+            _successes: serde_json::from_value(Value::Array(successes))?,
+            _errors: errors,
+        })
+    }
+}
+
+impl MultiResult<String> {
+    /// Same as `from_response`, for actions returning identifiers:
+    /// successful items given as documents are reduced to their `_id`.
+    pub(crate) fn ids_from_response(res: &KuzzleResponse) -> Result<MultiResult<String>, Error> {
+        let (successes, errors) = split_response(res)?;
+
+        Ok(MultiResult {
+            _successes: successes
+                .into_iter()
+                .filter_map(|item| match item {
+                    Value::String(id) => Some(id),
+                    item => item.get("_id").and_then(Value::as_str).map(String::from),
+                })
+                .collect(),
+            _errors: errors,
+        })
+    }
+}
+
+fn split_response(res: &KuzzleResponse) -> Result<(Vec<Value>, Vec<ItemError>), Error> {
+    let mut errors: Vec<ItemError> = Vec::new();
+
+    if let Some(k_err) = res.error() {
+        if k_err.status() != Some(206) {
+            return Err(Error::from(k_err.clone()));
+        }
+
+        errors.extend(
+            k_err
+                .errors()
+                .iter()
+                .map(|item| ItemError::from_value(item, k_err.message())),
+        );
+    }
+
+    let successes = match res.result() {
+        Value::Array(items) => items.clone(),
+        Value::Object(result) => {
+            if let Some(Value::Array(items)) = result.get("errors") {
+                errors.extend(
+                    items
+                        .iter()
+                        .map(|item| ItemError::from_value(item, "Unknown error")),
+                );
+            }
+
+            match result
+                .get("successes")
+                .or_else(|| result.get("hits"))
+                .or_else(|| result.get("deleted"))
+            {
+                Some(Value::Array(items)) => items.clone(),
+                _ => Vec::new(),
+            }
+        }
+        _ => Vec::new(),
+    };
+
+    Ok((successes, errors))
+}
@@ -1,15 +1,18 @@
 /// Used to choose the offline mode behavior, `Manual` or `Auto`.
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum OfflineMode {
     Manual,
     Auto,
 }
 
+use crate::types::{CancellationToken, Error, KuzzleRequest, TlsOptions};
+use serde_json::Value;
+use std::collections::HashMap;
 use std::time;
 
 /// Options are used to configure Kuzzle SDK behavior.
 /// Use them when instanciate `Kuzzle` structure to pass it a set of options.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct KuzzleOptions {
     _auto_queue: bool,
     _auto_reconnect: bool,
@@ -17,12 +20,15 @@ pub struct KuzzleOptions {
     _auto_resubscribe: bool,
     _host: String,
     _port: u32,
+    _nodes: Vec<(String, u32)>,
     _offline_mode: OfflineMode,
     _queue_max_size: u32,
     _queue_ttl: time::Duration,
     _reconnection_delay: time::Duration,
     _replay_interval: time::Duration,
+    _request_timeout: Option<time::Duration>,
     _ssl_connection: bool,
+    _tls: TlsOptions,
 }
 
 impl Default for KuzzleOptions {
@@ -34,12 +40,15 @@ impl Default for KuzzleOptions {
             _auto_resubscribe: true,
             _host: String::from("localhost"),
             _port: 7512,
+            _nodes: Vec::new(),
             _offline_mode: OfflineMode::Manual,
             _queue_max_size: 500,
             _queue_ttl: time::Duration::from_millis(120000),
             _reconnection_delay: time::Duration::from_millis(1000),
             _replay_interval: time::Duration::from_millis(10),
+            _request_timeout: None,
             _ssl_connection: false,
+            _tls: TlsOptions::new(),
         }
     }
 }
@@ -102,6 +111,14 @@ impl KuzzleOptions {
         &self._port
     }
 
+    /// Returns the address of every node of the Kuzzle cluster, starting with `host` and `port`.
+    /// See `protocols::Cluster`.
+    pub fn nodes(&self) -> Vec<(String, u32)> {
+        let mut nodes = vec![(self._host.clone(), self._port)];
+        nodes.extend(self._nodes.iter().cloned());
+        nodes
+    }
+
     pub fn queue_max_size(&self) -> &u32 {
         &self._queue_max_size
     }
@@ -118,10 +135,21 @@ impl KuzzleOptions {
         &self._replay_interval
     }
 
+    /// Default time to wait for Kuzzle responses, `None` to wait forever.
+    /// It can be overridden per request with `QueryOptions::set_timeout`.
+    pub fn request_timeout(&self) -> &Option<time::Duration> {
+        &self._request_timeout
+    }
+
     pub fn ssl_connection(&self) -> &bool {
         &self._ssl_connection
     }
 
+    /// TLS settings, used when `ssl_connection` is enabled.
+    pub fn tls(&self) -> &TlsOptions {
+        &self._tls
+    }
+
     pub fn set_auto_queue(mut self, auto_queue: bool) -> Self {
         self._auto_queue = auto_queue;
         self
@@ -157,6 +185,12 @@ impl KuzzleOptions {
         self
     }
 
+    /// Adds a node to the Kuzzle cluster, in addition to `host` and `port`.
+    pub fn add_node(mut self, host: &str, port: u32) -> Self {
+        self._nodes.push((String::from(host), port));
+        self
+    }
+
     pub fn set_queue_max_size(mut self, max_size: u32) -> Self {
         self._queue_max_size = max_size;
         self
@@ -177,22 +211,406 @@ impl KuzzleOptions {
         self
     }
 
+    pub fn set_request_timeout(mut self, timeout: u64) -> Self {
+        self._request_timeout = Some(time::Duration::from_millis(timeout));
+        self
+    }
+
     pub fn set_ssl_connection(mut self, ssl: bool) -> Self {
         self._ssl_connection = ssl;
         self
     }
+
+    pub fn set_tls(mut self, tls: TlsOptions) -> Self {
+        self._tls = tls;
+        self
+    }
 }
 
+/// QueryOptions are used to configure a single request.
+///
+/// # Example
+///
+/// ```
+/// use kuzzle_sdk::types::QueryOptions;
+///
+/// // Fail with `Error::Timeout` if Kuzzle did not answer within 250ms
+/// let options = QueryOptions::new().set_timeout(250);
+///
+/// // Make written documents searchable before Kuzzle answers
+/// let options = QueryOptions::new().set_refresh("wait_for");
+/// ```
+#[derive(Debug, Clone)]
 pub struct QueryOptions {
     queuable: bool,
+    timeout: Option<time::Duration>,
+    deadline: Option<time::Instant>,
+    cancellation_token: Option<CancellationToken>,
+    refresh: Option<String>,
+    retry_on_conflict: Option<u32>,
+    volatile: HashMap<String, Value>,
+    from: Option<u64>,
+    size: Option<u64>,
+    scroll: Option<String>,
+    include_trash: bool,
+    silent: bool,
+    request_id: Option<String>,
+    jwt: Option<String>,
+    anonymous: bool,
+}
+
+impl Default for QueryOptions {
+    fn default() -> QueryOptions {
+        QueryOptions {
+            queuable: true,
+            timeout: None,
+            deadline: None,
+            cancellation_token: None,
+            refresh: None,
+            retry_on_conflict: None,
+            volatile: HashMap::new(),
+            from: None,
+            size: None,
+            scroll: None,
+            include_trash: false,
+            silent: false,
+            request_id: None,
+            jwt: None,
+            anonymous: false,
+        }
+    }
 }
 
 impl QueryOptions {
     pub fn new() -> QueryOptions {
-        QueryOptions { queuable: true }
+        Default::default()
     }
 
     pub fn queuable(&self) -> bool {
         self.queuable
     }
+
+    pub fn timeout(&self) -> Option<time::Duration> {
+        self.timeout
+    }
+
+    pub fn deadline(&self) -> Option<time::Instant> {
+        self.deadline
+    }
+
+    pub fn cancellation_token(&self) -> Option<&CancellationToken> {
+        self.cancellation_token.as_ref()
+    }
+
+    pub fn refresh(&self) -> Option<&str> {
+        self.refresh.as_deref()
+    }
+
+    pub fn retry_on_conflict(&self) -> Option<u32> {
+        self.retry_on_conflict
+    }
+
+    pub fn volatile(&self) -> &HashMap<String, Value> {
+        &self.volatile
+    }
+
+    pub fn from(&self) -> Option<u64> {
+        self.from
+    }
+
+    pub fn size(&self) -> Option<u64> {
+        self.size
+    }
+
+    pub fn scroll(&self) -> Option<&str> {
+        self.scroll.as_deref()
+    }
+
+    pub fn include_trash(&self) -> bool {
+        self.include_trash
+    }
+
+    pub fn silent(&self) -> bool {
+        self.silent
+    }
+
+    pub fn request_id(&self) -> Option<&str> {
+        self.request_id.as_deref()
+    }
+
+    pub fn jwt(&self) -> Option<&str> {
+        self.jwt.as_deref()
+    }
+
+    pub fn anonymous(&self) -> bool {
+        self.anonymous
+    }
+
+    /// Time to wait for the response, in milliseconds.
+    /// Overrides `KuzzleOptions::request_timeout`.
+    pub fn set_timeout(mut self, timeout: u64) -> Self {
+        self.timeout = Some(time::Duration::from_millis(timeout));
+        self
+    }
+
+    /// Instant after which the request fails with `Error::Timeout`,
+    /// whatever its timeout. Useful to share a latency budget between requests.
+    pub fn set_deadline(mut self, deadline: time::Instant) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    pub fn set_cancellation_token(mut self, token: CancellationToken) -> Self {
+        self.cancellation_token = Some(token);
+        self
+    }
+
+    /// Refresh policy of write requests: with `wait_for`, Kuzzle answers once
+    /// written documents are available to searches.
+    pub fn set_refresh(mut self, refresh: &str) -> Self {
+        self.refresh = Some(refresh.to_string());
+        self
+    }
+
+    /// Number of times an update is retried when it conflicts with another one.
+    pub fn set_retry_on_conflict(mut self, retries: u32) -> Self {
+        self.retry_on_conflict = Some(retries);
+        self
+    }
+
+    /// Adds data to the request volatile data, forwarded in
+    /// the realtime notifications it triggers.
+    pub fn add_to_volatile(mut self, key: &str, value: Value) -> Self {
+        self.volatile.insert(key.to_string(), value);
+        self
+    }
+
+    /// Offset of the first result of search requests.
+    pub fn set_from(mut self, from: u64) -> Self {
+        self.from = Some(from);
+        self
+    }
+
+    /// Maximum number of results of search requests.
+    pub fn set_size(mut self, size: u64) -> Self {
+        self.size = Some(size);
+        self
+    }
+
+    /// Lifetime of the search cursor (e.g. `1m`), making search results scrollable.
+    pub fn set_scroll(mut self, scroll: &str) -> Self {
+        self.scroll = Some(scroll.to_string());
+        self
+    }
+
+    /// Includes users and profiles in the trash in security requests.
+    pub fn set_include_trash(mut self, include_trash: bool) -> Self {
+        self.include_trash = include_trash;
+        self
+    }
+
+    /// If true, the request does not trigger realtime notifications.
+    pub fn set_silent(mut self, silent: bool) -> Self {
+        self.silent = silent;
+        self
+    }
+
+    /// Custom request id, generated by the protocol if not set.
+    pub fn set_request_id(mut self, request_id: &str) -> Self {
+        self.request_id = Some(request_id.to_string());
+        self
+    }
+
+    /// Token used by this request instead of the client one, to act on behalf
+    /// of another user. The client session is left untouched, including by
+    /// `login`, `logout` and `refresh_token`.
+    pub fn set_jwt(mut self, jwt: &str) -> Self {
+        self.jwt = Some(jwt.to_string());
+        self.anonymous = false;
+        self
+    }
+
+    /// If true, the request is sent without any token, whatever the client one.
+    /// As with `set_jwt`, the client session is left untouched.
+    pub fn set_anonymous(mut self, anonymous: bool) -> Self {
+        self.anonymous = anonymous;
+        if anonymous {
+            self.jwt = None;
+        }
+        self
+    }
+
+    /// Returns true if the request is not sent with the client token.
+    pub fn overrides_jwt(&self) -> bool {
+        self.anonymous || self.jwt.is_some()
+    }
+
+    /// Returns the token to send the request with, given the client one.
+    /// It is empty for anonymous requests.
+    pub fn request_jwt(&self, client_jwt: String) -> String {
+        match &self.jwt {
+            _ if self.anonymous => String::new(),
+            Some(jwt) => jwt.clone(),
+            None => client_jwt,
+        }
+    }
+
+    /// Adds the Kuzzle arguments of these options to the given request,
+    /// overriding arguments it already holds.
+    pub fn apply(&self, req: KuzzleRequest) -> KuzzleRequest {
+        let mut req = req;
+        let arguments = [
+            ("refresh", self.refresh.clone().map(Value::from)),
+            ("retryOnConflict", self.retry_on_conflict.map(Value::from)),
+            ("from", self.from.map(Value::from)),
+            ("size", self.size.map(Value::from)),
+            ("scroll", self.scroll.clone().map(Value::from)),
+            (
+                "includeTrash",
+                self.include_trash.then_some(Value::from(true)),
+            ),
+            ("silent", self.silent.then_some(Value::from(true))),
+        ];
+
+        for (name, value) in arguments.iter() {
+            if let Some(value) = value {
+                req = req.add_to_query_strings(name, value.clone());
+            }
+        }
+
+        for (key, value) in &self.volatile {
+            req = req.add_to_volatile(key, value.clone());
+        }
+
+        match &self.request_id {
+            Some(request_id) => req.set_request_id(request_id),
+            None => req,
+        }
+    }
+
+    /// Returns the instant the response must be received by: the earliest
+    /// of the deadline and the timeout, `default_timeout` if none is set.
+    pub fn expires_at(&self, default_timeout: Option<time::Duration>) -> Option<time::Instant> {
+        let timeout = self
+            .timeout
+            .or(default_timeout)
+            .map(|timeout| time::Instant::now() + timeout);
+
+        match (timeout, self.deadline) {
+            (Some(timeout), Some(deadline)) => Some(timeout.min(deadline)),
+            (timeout, deadline) => timeout.or(deadline),
+        }
+    }
+
+    /// Returns an error if the request has been cancelled or if `expires_at` is over.
+    pub fn check(&self, expires_at: Option<time::Instant>) -> Result<(), Error> {
+        if self
+            .cancellation_token
+            .as_ref()
+            .is_some_and(CancellationToken::is_cancelled)
+        {
+            return Err(Error::Cancelled);
+        }
+
+        match expires_at {
+            Some(expires_at) if expires_at <= time::Instant::now() => Err(Error::Timeout),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn expires_at_ok() {
+        let default = Some(Duration::from_secs(60));
+        let deadline = Instant::now() + Duration::from_secs(10);
+
+        assert_eq!(QueryOptions::new().expires_at(None), None);
+        assert!(QueryOptions::new().expires_at(default).unwrap() > deadline);
+        assert!(
+            QueryOptions::new()
+                .set_timeout(100)
+                .expires_at(default)
+                .unwrap()
+                < deadline
+        );
+        assert_eq!(
+            QueryOptions::new()
+                .set_deadline(deadline)
+                .expires_at(default),
+            Some(deadline)
+        );
+    }
+
+    #[test]
+    fn apply_ok() {
+        let req = KuzzleRequest::new("document", "search")
+            .add_to_query_strings("size", Value::from(10))
+            .add_to_volatile("origin", Value::from("request"));
+        let req = QueryOptions::new()
+            .set_refresh("wait_for")
+            .set_size(100)
+            .set_include_trash(false)
+            .set_silent(true)
+            .add_to_volatile("user", Value::from("ferris"))
+            .set_request_id("ferris-request")
+            .apply(req);
+
+        assert_eq!(req.query_strings()["refresh"], "wait_for");
+        assert_eq!(req.query_strings()["size"], 100);
+        assert_eq!(req.query_strings()["silent"], true);
+        assert!(!req.query_strings().contains_key("includeTrash"));
+        assert!(!req.query_strings().contains_key("from"));
+        assert_eq!(req.volatile()["origin"], "request");
+        assert_eq!(req.volatile()["user"], "ferris");
+        assert_eq!(req.request_id(), "ferris-request");
+    }
+
+    #[test]
+    fn request_jwt_ok() {
+        let client_jwt = || "clientToken".to_string();
+
+        assert_eq!(QueryOptions::new().request_jwt(client_jwt()), "clientToken");
+        assert_eq!(
+            QueryOptions::new()
+                .set_jwt("userToken")
+                .request_jwt(client_jwt()),
+            "userToken"
+        );
+        assert_eq!(
+            QueryOptions::new()
+                .set_jwt("userToken")
+                .set_anonymous(true)
+                .request_jwt(client_jwt()),
+            ""
+        );
+        assert_eq!(
+            QueryOptions::new()
+                .set_anonymous(true)
+                .set_jwt("userToken")
+                .request_jwt(client_jwt()),
+            "userToken"
+        );
+        assert!(!QueryOptions::new().overrides_jwt());
+        assert!(QueryOptions::new().set_anonymous(true).overrides_jwt());
+    }
+
+    #[test]
+    fn check_fail() {
+        let token = CancellationToken::new();
+        let options = QueryOptions::new().set_cancellation_token(token.clone());
+
+        assert!(options.check(None).is_ok());
+        assert!(matches!(
+            options.check(Some(Instant::now())),
+            Err(Error::Timeout)
+        ));
+
+        token.cancel();
+        assert!(matches!(options.check(None), Err(Error::Cancelled)));
+    }
 }
@@ -7,10 +7,13 @@ pub struct KuzzleRequest {
     _action: String,
     _index: Option<String>,
     _collection: Option<String>,
+    _id: Option<String>,
     _strategy: Option<String>,
     _jwt: Option<String>,
+    _request_id: Option<String>,
     _body: HashMap<String, Value>,
     _query_strings: HashMap<String, Value>,
+    _volatile: HashMap<String, Value>,
 }
 
 impl KuzzleRequest {
@@ -20,10 +23,13 @@ impl KuzzleRequest {
             _action: action.to_string(),
             _index: None,
             _collection: None,
+            _id: None,
             _strategy: None,
             _jwt: None,
+            _request_id: None,
             _body: HashMap::new(),
             _query_strings: HashMap::new(),
+            _volatile: HashMap::new(),
         }
     }
 
@@ -49,6 +55,13 @@ impl KuzzleRequest {
         }
     }
 
+    pub fn id(&self) -> String {
+        match &self._id {
+            Some(id) => id.clone(),
+            None => String::new(),
+        }
+    }
+
     pub fn strategy(&self) -> String {
         match &self._strategy {
             Some(strategy) => strategy.clone(),
@@ -63,6 +76,13 @@ impl KuzzleRequest {
         }
     }
 
+    pub fn request_id(&self) -> String {
+        match &self._request_id {
+            Some(request_id) => request_id.clone(),
+            None => String::new(),
+        }
+    }
+
     pub fn body(&self) -> &HashMap<String, Value> {
         &self._body
     }
@@ -71,11 +91,25 @@ impl KuzzleRequest {
         &self._query_strings
     }
 
+    pub fn volatile(&self) -> &HashMap<String, Value> {
+        &self._volatile
+    }
+
     pub fn set_index(mut self, index: &str) -> Self {
         self._index = Some(index.to_string());
         self
     }
 
+    pub fn set_collection(mut self, collection: &str) -> Self {
+        self._collection = Some(collection.to_string());
+        self
+    }
+
+    pub fn set_id(mut self, id: &str) -> Self {
+        self._id = Some(id.to_string());
+        self
+    }
+
     pub fn set_strategy(mut self, strategy: &str) -> Self {
         self._strategy = Some(strategy.to_string());
         self
@@ -86,6 +120,11 @@ impl KuzzleRequest {
         self
     }
 
+    pub fn set_request_id(mut self, request_id: &str) -> Self {
+        self._request_id = Some(request_id.to_string());
+        self
+    }
+
     pub fn set_body(mut self, body: HashMap<String, Value>) -> Self {
         self._body = body;
         self
@@ -100,4 +139,9 @@ impl KuzzleRequest {
         self._query_strings.insert(key.to_string(), value);
         self
     }
+
+    pub fn add_to_volatile(mut self, key: &str, value: Value) -> Self {
+        self._volatile.insert(key.to_string(), value);
+        self
+    }
 }
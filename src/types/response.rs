@@ -4,7 +4,7 @@ use std::collections::HashMap;
 
 /// A KuzzleResponse is a standardized result.
 /// This format is shared by all  API routes, including routes added by controller plugins.
-#[derive(Deserialize, Serialize, Debug, PartialEq)]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
 pub struct KuzzleResponse {
     #[serde(rename = "requestId")]
     request_id: String,
@@ -0,0 +1,40 @@
+use crate::types::Document;
+use serde_json::Value;
+
+/// A SearchResult holds a page of documents returned by `document:search`
+/// or `document:scroll`, along with the scroll identifier used to fetch the next one.
+#[derive(Deserialize, Debug)]
+pub struct SearchResult {
+    #[serde(alias = "total")]
+    _total: u64,
+    #[serde(alias = "hits")]
+    _hits: Vec<Document>,
+    #[serde(alias = "scrollId")]
+    _scroll_id: Option<String>,
+    #[serde(alias = "aggregations")]
+    _aggregations: Option<Value>,
+}
+
+impl SearchResult {
+    pub fn total(&self) -> u64 {
+        self._total
+    }
+
+    pub fn hits(&self) -> &Vec<Document> {
+        &self._hits
+    }
+
+    pub fn scroll_id(&self) -> String {
+        match &self._scroll_id {
+            Some(scroll_id) => scroll_id.clone(),
+            None => String::new(),
+        }
+    }
+
+    pub fn aggregations(&self) -> Value {
+        match &self._aggregations {
+            Some(aggregations) => aggregations.clone(),
+            None => Value::Null,
+        }
+    }
+}
@@ -0,0 +1,105 @@
+use serde_json::{Map, Value};
+
+/// Kuzzle requests and subscriptions limits, from `server:getConfig`.
+#[derive(Deserialize, Debug, Clone, Default)]
+pub struct ServerLimits {
+    #[serde(alias = "concurrentRequests")]
+    _concurrent_requests: Option<u64>,
+    #[serde(alias = "documentsFetchCount")]
+    _documents_fetch_count: Option<u64>,
+    #[serde(alias = "documentsWriteCount")]
+    _documents_write_count: Option<u64>,
+    #[serde(alias = "loginsPerSecond")]
+    _logins_per_second: Option<u64>,
+    #[serde(alias = "requestsBufferSize")]
+    _requests_buffer_size: Option<u64>,
+    #[serde(alias = "requestsBufferWarningThreshold")]
+    _requests_buffer_warning_threshold: Option<u64>,
+    #[serde(alias = "subscriptionConditionsCount")]
+    _subscription_conditions_count: Option<u64>,
+    #[serde(alias = "subscriptionMinterms")]
+    _subscription_minterms: Option<u64>,
+    #[serde(alias = "subscriptionRooms")]
+    _subscription_rooms: Option<u64>,
+    #[serde(alias = "subscriptionDocumentTTL")]
+    _subscription_document_ttl: Option<u64>,
+}
+
+impl ServerLimits {
+    pub fn concurrent_requests(&self) -> Option<u64> {
+        self._concurrent_requests
+    }
+
+    pub fn documents_fetch_count(&self) -> Option<u64> {
+        self._documents_fetch_count
+    }
+
+    pub fn documents_write_count(&self) -> Option<u64> {
+        self._documents_write_count
+    }
+
+    pub fn logins_per_second(&self) -> Option<u64> {
+        self._logins_per_second
+    }
+
+    pub fn requests_buffer_size(&self) -> Option<u64> {
+        self._requests_buffer_size
+    }
+
+    pub fn requests_buffer_warning_threshold(&self) -> Option<u64> {
+        self._requests_buffer_warning_threshold
+    }
+
+    pub fn subscription_conditions_count(&self) -> Option<u64> {
+        self._subscription_conditions_count
+    }
+
+    pub fn subscription_minterms(&self) -> Option<u64> {
+        self._subscription_minterms
+    }
+
+    pub fn subscription_rooms(&self) -> Option<u64> {
+        self._subscription_rooms
+    }
+
+    pub fn subscription_document_ttl(&self) -> Option<u64> {
+        self._subscription_document_ttl
+    }
+}
+
+/// A subset of `server:getConfig` result.
+/// Sections without a typed counterpart remain available through `others`.
+#[derive(Deserialize, Debug, Clone)]
+pub struct ServerConfig {
+    #[serde(alias = "version")]
+    _version: Option<String>,
+    #[serde(alias = "limits", default)]
+    _limits: ServerLimits,
+    #[serde(alias = "plugins", default)]
+    _plugins: Map<String, Value>,
+    #[serde(flatten)]
+    _others: Map<String, Value>,
+}
+
+impl ServerConfig {
+    pub fn version(&self) -> String {
+        match &self._version {
+            Some(version) => version.clone(),
+            None => String::new(),
+        }
+    }
+
+    pub fn limits(&self) -> &ServerLimits {
+        &self._limits
+    }
+
+    /// Plugins configuration, indexed by plugin name.
+    pub fn plugins(&self) -> &Map<String, Value> {
+        &self._plugins
+    }
+
+    /// Remaining configuration sections (`server`, `services`, `security`...).
+    pub fn others(&self) -> &Map<String, Value> {
+        &self._others
+    }
+}
@@ -0,0 +1,72 @@
+use serde_json::{Map, Value};
+
+#[derive(Deserialize, Debug, Clone)]
+struct KuzzleInfo {
+    #[serde(alias = "version")]
+    _version: Option<String>,
+    #[serde(alias = "nodeVersion")]
+    _node_version: Option<String>,
+    #[serde(alias = "memoryUsed")]
+    _memory_used: Option<u64>,
+    #[serde(alias = "uptime")]
+    _uptime: Option<String>,
+    #[serde(alias = "api", default)]
+    _api: Map<String, Value>,
+    #[serde(alias = "plugins", default)]
+    _plugins: Map<String, Value>,
+}
+
+/// A subset of `server:info` result: Kuzzle node information and external services status.
+#[derive(Deserialize, Debug, Clone)]
+pub struct ServerInfo {
+    #[serde(alias = "kuzzle")]
+    _kuzzle: KuzzleInfo,
+    #[serde(alias = "services", default)]
+    _services: Map<String, Value>,
+}
+
+impl ServerInfo {
+    /// Kuzzle version.
+    pub fn version(&self) -> String {
+        match &self._kuzzle._version {
+            Some(version) => version.clone(),
+            None => String::new(),
+        }
+    }
+
+    /// Node.js version running Kuzzle.
+    pub fn node_version(&self) -> String {
+        match &self._kuzzle._node_version {
+            Some(version) => version.clone(),
+            None => String::new(),
+        }
+    }
+
+    /// Memory used by the Kuzzle node, in bytes.
+    pub fn memory_used(&self) -> u64 {
+        self._kuzzle._memory_used.unwrap_or(0)
+    }
+
+    /// Kuzzle node uptime, as reported by the server (e.g. `"161036.945s"`).
+    pub fn uptime(&self) -> String {
+        match &self._kuzzle._uptime {
+            Some(uptime) => uptime.clone(),
+            None => String::new(),
+        }
+    }
+
+    /// Available API description, including routes added by plugins.
+    pub fn api(&self) -> &Map<String, Value> {
+        &self._kuzzle._api
+    }
+
+    /// Loaded plugins, indexed by name.
+    pub fn plugins(&self) -> &Map<String, Value> {
+        &self._kuzzle._plugins
+    }
+
+    /// External services (Elasticsearch, Redis...) information, indexed by service name.
+    pub fn services(&self) -> &Map<String, Value> {
+        &self._services
+    }
+}
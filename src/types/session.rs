@@ -0,0 +1,96 @@
+use chrono::{DateTime, TimeZone, Utc};
+
+/// An authentication session, as returned by `auth:login` and `auth:refreshToken`.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct Session {
+    #[serde(alias = "jwt", rename(serialize = "jwt"))]
+    _jwt: String,
+    #[serde(
+        alias = "_id",
+        rename(serialize = "_id"),
+        skip_serializing_if = "Option::is_none"
+    )]
+    _kuid: Option<String>,
+    #[serde(
+        alias = "expiresAt",
+        rename(serialize = "expiresAt"),
+        skip_serializing_if = "Option::is_none"
+    )]
+    _expires_at: Option<i64>,
+}
+
+impl Session {
+    /// Returns a session holding `jwt`, whose owner and expiration are unknown.
+    pub fn new(jwt: &str) -> Session {
+        Session {
+            _jwt: jwt.to_string(),
+            _kuid: None,
+            _expires_at: None,
+        }
+    }
+
+    pub fn jwt(&self) -> String {
+        self._jwt.clone()
+    }
+
+    /// Returns the unique identifier of the logged in user.
+    pub fn kuid(&self) -> String {
+        self._kuid.clone().unwrap_or_default()
+    }
+
+    /// Returns the token expiration date, or `None` if the token never expires.
+    pub fn expires_at(&self) -> Option<DateTime<Utc>> {
+        self._expires_at
+            .and_then(|expires_at| Utc.timestamp_millis_opt(expires_at).single())
+    }
+
+    /// Returns true if the token expires before `now`.
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at()
+            .is_some_and(|expires_at| expires_at <= now)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn deserialize_ok() {
+        let session: Session = serde_json::from_value(json!({
+            "_id": "user-kuid",
+            "jwt": "aSuperJwtToken",
+            "expiresAt": 1321085955000i64,
+            "ttl": 360000
+        }))
+        .unwrap();
+
+        assert_eq!(session.jwt(), "aSuperJwtToken");
+        assert_eq!(session.kuid(), "user-kuid");
+        assert_eq!(
+            session.expires_at(),
+            Some(Utc.timestamp_millis_opt(1321085955000).unwrap())
+        );
+        assert!(session.is_expired(Utc::now()));
+    }
+
+    #[test]
+    fn serialize_ok() {
+        let session = Session::new("aSuperJwtToken");
+
+        assert_eq!(
+            serde_json::to_value(&session).unwrap(),
+            json!({ "jwt": "aSuperJwtToken" })
+        );
+    }
+
+    #[test]
+    fn new_ok() {
+        let session = Session::new("aSuperJwtToken");
+
+        assert_eq!(session.kuid(), "");
+        assert_eq!(session.expires_at(), None);
+        assert!(!session.is_expired(Utc::now()));
+    }
+}
@@ -0,0 +1,44 @@
+use std::collections::HashMap;
+
+/// A StatsFrame is a snapshot of Kuzzle usage statistics, counted per network protocol
+/// (`websocket`, `http`, `mqtt`...).
+#[derive(Deserialize, Debug, Clone)]
+pub struct StatsFrame {
+    #[serde(alias = "completedRequests", default)]
+    _completed_requests: HashMap<String, u64>,
+    #[serde(alias = "connections", default)]
+    _connections: HashMap<String, u64>,
+    #[serde(alias = "failedRequests", default)]
+    _failed_requests: HashMap<String, u64>,
+    #[serde(alias = "ongoingRequests", default)]
+    _ongoing_requests: HashMap<String, u64>,
+    #[serde(alias = "timestamp")]
+    _timestamp: u64,
+}
+
+impl StatsFrame {
+    /// Number of requests completed per protocol.
+    pub fn completed_requests(&self) -> &HashMap<String, u64> {
+        &self._completed_requests
+    }
+
+    /// Number of opened connections per protocol.
+    pub fn connections(&self) -> &HashMap<String, u64> {
+        &self._connections
+    }
+
+    /// Number of failed requests per protocol.
+    pub fn failed_requests(&self) -> &HashMap<String, u64> {
+        &self._failed_requests
+    }
+
+    /// Number of requests being processed per protocol.
+    pub fn ongoing_requests(&self) -> &HashMap<String, u64> {
+        &self._ongoing_requests
+    }
+
+    /// Snapshot date, in Epoch-millis format.
+    pub fn timestamp(&self) -> u64 {
+        self._timestamp
+    }
+}
@@ -0,0 +1,96 @@
+use std::path::{Path, PathBuf};
+
+/// Client certificate presented to Kuzzle, for mutual TLS authentication.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ClientIdentity {
+    /// PEM encoded certificate (chain) and PKCS#8 private key files.
+    Pem { certificate: PathBuf, key: PathBuf },
+    /// PKCS#12 archive file and its password.
+    Pkcs12 { archive: PathBuf, password: String },
+}
+
+/// TLS settings used by both `Http` and `Websocket` protocols
+/// when `KuzzleOptions::ssl_connection` is enabled.
+///
+/// # Example
+///
+/// ```
+/// use kuzzle_sdk::types::{KuzzleOptions, TlsOptions};
+///
+/// let options = KuzzleOptions::new("10.0.0.12", 7443)
+///     .set_ssl_connection(true)
+///     .set_tls(
+///         TlsOptions::new()
+///             .set_ca_bundle("/etc/kuzzle/ca.pem")
+///             .set_client_certificate("/etc/kuzzle/client.pem", "/etc/kuzzle/client.key")
+///             .set_sni_hostname("kuzzle.example.com"),
+///     );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TlsOptions {
+    _ca_bundle: Option<PathBuf>,
+    _client_identity: Option<ClientIdentity>,
+    _accept_invalid_certs: bool,
+    _sni_hostname: Option<String>,
+}
+
+impl TlsOptions {
+    pub fn new() -> TlsOptions {
+        Default::default()
+    }
+
+    pub fn ca_bundle(&self) -> Option<&Path> {
+        self._ca_bundle.as_deref()
+    }
+
+    pub fn client_identity(&self) -> Option<&ClientIdentity> {
+        self._client_identity.as_ref()
+    }
+
+    pub fn accept_invalid_certs(&self) -> bool {
+        self._accept_invalid_certs
+    }
+
+    pub fn sni_hostname(&self) -> Option<&str> {
+        self._sni_hostname.as_deref()
+    }
+
+    /// Trusts the certificate authorities of the given PEM file,
+    /// in addition to the system ones.
+    pub fn set_ca_bundle<P: AsRef<Path>>(mut self, path: P) -> Self {
+        self._ca_bundle = Some(path.as_ref().to_path_buf());
+        self
+    }
+
+    /// Authenticates with a PEM certificate and its PKCS#8 private key.
+    pub fn set_client_certificate<P: AsRef<Path>>(mut self, certificate: P, key: P) -> Self {
+        self._client_identity = Some(ClientIdentity::Pem {
+            certificate: certificate.as_ref().to_path_buf(),
+            key: key.as_ref().to_path_buf(),
+        });
+        self
+    }
+
+    /// Authenticates with a certificate stored in a PKCS#12 archive.
+    pub fn set_client_pkcs12<P: AsRef<Path>>(mut self, archive: P, password: &str) -> Self {
+        self._client_identity = Some(ClientIdentity::Pkcs12 {
+            archive: archive.as_ref().to_path_buf(),
+            password: password.to_string(),
+        });
+        self
+    }
+
+    /// Accepts any server certificate, including self-signed or expired ones.
+    /// Use it for development only: it makes connections vulnerable to man-in-the-middle attacks.
+    pub fn set_accept_invalid_certs(mut self, accept: bool) -> Self {
+        self._accept_invalid_certs = accept;
+        self
+    }
+
+    /// Uses `hostname`, instead of the configured host, for SNI and certificate validation.
+    /// Useful when connecting to a node through its IP address.
+    pub fn set_sni_hostname(mut self, hostname: &str) -> Self {
+        self._sni_hostname = Some(hostname.to_string());
+        self
+    }
+}
@@ -1,3 +1,5 @@
+use chrono::{DateTime, TimeZone, Utc};
+
 #[derive(Deserialize)]
 pub struct TokenValidity {
     #[serde(alias = "valid")]
@@ -14,16 +16,12 @@ impl TokenValidity {
     }
 
     pub fn state(&self) -> String {
-        match &self._state {
-            Some(state) => state.clone(),
-            None => String::new(),
-        }
+        self._state.clone().unwrap_or_default()
     }
 
-    pub fn expires_at(&self) -> i64 {
-        match self._expires_at {
-            Some(timestamp) => timestamp,
-            None => 0,
-        }
+    /// Returns the token expiration date, or `None` if the token is invalid or never expires.
+    pub fn expires_at(&self) -> Option<DateTime<Utc>> {
+        self._expires_at
+            .and_then(|expires_at| Utc.timestamp_millis_opt(expires_at).single())
     }
 }